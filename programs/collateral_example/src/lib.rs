@@ -0,0 +1,175 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+// Example integrator: a lending market that accepts interest_vault shares as
+// collateral. It prices shares by reading the VaultState account zero-copy and
+// applying the vault's own math, which is what any composing program can rely on:
+// value = shares * pps / RAY, rounded down exactly like a Withdraw would.
+
+use bytemuck::{Pod, Zeroable};
+use core::mem::size_of;
+use interest_vault::{math, Discriminator, VaultState};
+use pinocchio::{
+    account_info::AccountInfo,
+    entrypoint,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+entrypoint!(process_instruction);
+
+// ---------- Constants ----------
+const OP_INIT_MARKET: u8 = 0;
+const OP_CHECK_BORROW: u8 = 1;
+
+const BPS_DENOM: u64 = 10_000;
+
+// SPL Token account layout: mint, owner, amount
+const TOKEN_ACCOUNT_MINT_OFFSET: usize = 0;
+const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+
+// ---------- Errors ----------
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MarketError {
+    AlreadyInitialized = 1,
+    WrongVault = 2,
+    WrongCollateralMint = 3,
+    InsufficientCollateral = 4,
+    MathOverflow = 5,
+}
+
+impl From<MarketError> for ProgramError {
+    fn from(e: MarketError) -> Self { ProgramError::Custom(e as u32) }
+}
+
+// ---------- State ----------
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct Market {
+    pub admin: Pubkey,
+    pub vault_program: Pubkey, // owner every accepted VaultState must have
+    pub vault_state: Pubkey,
+    pub ltv_bps: u16,          // max debt as a share of collateral value
+    pub _pad: [u8; 6],
+}
+
+// ---------- Helpers ----------
+fn load_mut<'a, T: Pod>(ai: &'a AccountInfo) -> Result<&'a mut T, ProgramError> {
+    let data = ai.try_borrow_mut_data()?;
+    if data.len() < size_of::<T>() { return Err(ProgramError::InvalidAccountData) }
+    let ptr = data.as_mut_ptr();
+    let slice = unsafe { core::slice::from_raw_parts_mut(ptr, size_of::<T>()) };
+    Ok(bytemuck::from_bytes_mut(slice))
+}
+
+fn load<'a, T: Pod>(ai: &'a AccountInfo) -> Result<&'a T, ProgramError> {
+    let data = ai.try_borrow_data()?;
+    if data.len() < size_of::<T>() { return Err(ProgramError::InvalidAccountData) }
+    let ptr = data.as_ptr();
+    let slice = unsafe { core::slice::from_raw_parts(ptr, size_of::<T>()) };
+    Ok(bytemuck::from_bytes(slice))
+}
+
+// Collateral value in USDC base units of `shares` at the vault's current pps.
+pub fn share_value(vault: &VaultState, shares: u64) -> Option<u64> {
+    math::assets_for_shares(shares as u128, vault.pps)?.try_into().ok()
+}
+
+// Largest debt `shares` can back at `ltv_bps`.
+pub fn max_borrow(vault: &VaultState, shares: u64, ltv_bps: u16) -> Option<u64> {
+    let value = share_value(vault, shares)? as u128;
+    (value * ltv_bps as u128 / BPS_DENOM as u128).try_into().ok()
+}
+
+// ---------- Entry ----------
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    ix_data: &[u8],
+) -> ProgramResult {
+    if ix_data.is_empty() { return Err(ProgramError::InvalidInstructionData) }
+    match ix_data[0] {
+        OP_INIT_MARKET  => op_init_market(program_id, accounts, &ix_data[1..]),
+        OP_CHECK_BORROW => op_check_borrow(accounts, &ix_data[1..]),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+// data: [vault_program: Pubkey, vault_state: Pubkey, ltv_bps:u16]
+fn op_init_market(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    // accounts:
+    // 0 [w] market (pre-allocated, owned by this program)
+    // 1 [s] admin
+    let [market, admin, ..] = accs else { return Err(ProgramError::NotEnoughAccountKeys) };
+    if !admin.is_signer { return Err(ProgramError::MissingRequiredSignature) }
+    if market.owner != program_id { return Err(ProgramError::IncorrectProgramId) }
+    if data.len() < 66 { return Err(ProgramError::InvalidInstructionData) }
+    let ltv_bps = u16::from_le_bytes(data[64..66].try_into().unwrap());
+    if ltv_bps as u64 > BPS_DENOM { return Err(ProgramError::InvalidInstructionData) }
+
+    let m = load_mut::<Market>(market)?;
+    if m.admin != Pubkey::default() { return Err(MarketError::AlreadyInitialized.into()) }
+    m.admin = *admin.key;
+    m.vault_program.copy_from_slice(&data[0..32]);
+    m.vault_state.copy_from_slice(&data[32..64]);
+    m.ltv_bps = ltv_bps;
+    Ok(())
+}
+
+// Fails unless `debt` is within the LTV of the share collateral; a real market would
+// run this before releasing a loan or after any collateral withdrawal.
+// data: [debt:u64]
+fn op_check_borrow(accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    // accounts:
+    // 0 []  market
+    // 1 []  vault_state (interest_vault)
+    // 2 []  collateral share token account
+    let [market, vault_state, collateral, ..] = accs else { return Err(ProgramError::NotEnoughAccountKeys) };
+    if data.len() < 8 { return Err(ProgramError::InvalidInstructionData) }
+    let debt = u64::from_le_bytes(data[0..8].try_into().unwrap());
+
+    let m = load::<Market>(market)?;
+    // the owner check is what makes the pps trustworthy: only the vault program can write it
+    if *vault_state.key != m.vault_state || *vault_state.owner != m.vault_program {
+        return Err(MarketError::WrongVault.into())
+    }
+    let vault = load::<VaultState>(vault_state)?;
+    if vault.discriminator != VaultState::DISCRIMINATOR { return Err(MarketError::WrongVault.into()) }
+
+    let d = collateral.try_borrow_data()?;
+    if d.len() < TOKEN_ACCOUNT_AMOUNT_OFFSET + 8 { return Err(ProgramError::InvalidAccountData) }
+    if d[TOKEN_ACCOUNT_MINT_OFFSET..TOKEN_ACCOUNT_MINT_OFFSET + 32] != vault.share_mint {
+        return Err(MarketError::WrongCollateralMint.into())
+    }
+    let shares = u64::from_le_bytes(d[TOKEN_ACCOUNT_AMOUNT_OFFSET..TOKEN_ACCOUNT_AMOUNT_OFFSET + 8].try_into().unwrap());
+
+    let limit = max_borrow(vault, shares, m.ltv_bps).ok_or(MarketError::MathOverflow)?;
+    if debt > limit { return Err(MarketError::InsufficientCollateral.into()) }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use interest_vault::math::RAY;
+
+    fn vault(pps: u128) -> VaultState {
+        VaultState { pps, ..VaultState::zeroed() }
+    }
+
+    #[test]
+    fn share_value_tracks_pps() {
+        assert_eq!(share_value(&vault(RAY), 1_000_000), Some(1_000_000));
+        assert_eq!(share_value(&vault(RAY + RAY / 2), 1_000_000), Some(1_500_000));
+        assert_eq!(share_value(&vault(RAY * 2), u64::MAX), None);
+    }
+
+    #[test]
+    fn max_borrow_applies_ltv() {
+        let v = vault(RAY * 2);
+        assert_eq!(max_borrow(&v, 1_000_000, 5_000), Some(1_000_000));
+        assert_eq!(max_borrow(&v, 1_000_000, 0), Some(0));
+        assert_eq!(max_borrow(&v, 1_000_000, 10_000), Some(2_000_000));
+    }
+}
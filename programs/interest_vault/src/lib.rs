@@ -5,28 +5,114 @@ use core::mem::size_of;
 use pinocchio::{
     account_info::AccountInfo,
     cpi,
-    entrypoint,
-    instruction::{AccountMeta, Instruction, Signer},
-    msg,
+    instruction::{AccountMeta, Instruction, Seed, Signer},
+    log::sol_log_data,
     program_error::ProgramError,
-    pubkey::Pubkey,
+    pubkey::{create_program_address, find_program_address, Pubkey},
     syscalls,
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
     ProgramResult,
 };
 
-entrypoint!(process_instruction);
+pub mod logfmt;
+pub mod math;
+use math::BPS_DENOM;
+
+#[cfg(not(any(feature = "no-alloc", feature = "no-entrypoint")))]
+pinocchio::entrypoint!(process_instruction);
+
+// CPIs are built from stack arrays only, so the program can run without a heap.
+#[cfg(all(feature = "no-alloc", not(feature = "no-entrypoint")))]
+pinocchio::program_entrypoint!(process_instruction);
+#[cfg(all(feature = "no-alloc", not(feature = "no-entrypoint")))]
+pinocchio::no_allocator!();
+#[cfg(all(feature = "no-alloc", not(feature = "no-entrypoint")))]
+pinocchio::nostd_panic_handler!();
 
 // ---------- Constants ----------
-const RAY: u128 = 1_000_000_000_000; // 1e12 fixed point PPS
 const SEED_VAULT: &[u8] = b"vault";
+const SEED_STATE: &[u8] = b"state";
 const SEED_AUTH: &[u8]  = b"vault_auth";
 const SEED_BOOST: &[u8] = b"boost";
 const SEED_CLAIMS: &[u8] = b"claims";
+const SEED_SHARES: &[u8] = b"shares";
+const SEED_PREFS: &[u8] = b"prefs";
+const SEED_POSITION: &[u8] = b"position";
+const SEED_ESCROW: &[u8] = b"escrow";
+const SEED_CLAIM_ESCROW: &[u8] = b"claim_escrow";
+const SEED_REGISTRY: &[u8] = b"registry";
+const SEED_REPORT: &[u8] = b"report";
+const SEED_BOOST_USDC: &[u8] = b"boost_usdc";
+
+// SPL Token (TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA)
+const TOKEN_PROGRAM_ID: Pubkey = [
+    6, 221, 246, 225, 215, 101, 161, 147, 217, 203, 225, 70, 206, 235, 121, 172,
+    28, 180, 133, 237, 95, 91, 55, 145, 58, 140, 245, 133, 126, 255, 0, 169,
+];
+// Token-2022 (TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb)
+const TOKEN_2022_PROGRAM_ID: Pubkey = [
+    6, 221, 246, 225, 238, 117, 143, 222, 24, 66, 93, 188, 228, 108, 205, 218,
+    182, 26, 252, 77, 131, 185, 13, 39, 254, 189, 249, 40, 216, 161, 139, 252,
+];
+// Token-2022 extension layout: base account padded to 165 bytes, then an
+// account-type byte, then TLV entries [type:u16, len:u16, value]
+const T22_ACCOUNT_TYPE_OFFSET: usize = 165;
+const T22_ACCOUNT_TYPE_MINT: u8 = 1;
+const T22_TLV_HEADER: usize = 4;
+// Multisig::LEN; an extended account that would come out this size is padded by 2
+const T22_MULTISIG_LEN: usize = 355;
+// spl_token_2022::extension::ExtensionType
+const EXT_TRANSFER_FEE_CONFIG: u16 = 1;
+const EXT_CONFIDENTIAL_TRANSFER_MINT: u16 = 4;
+const EXT_NON_TRANSFERABLE: u16 = 9;
+const EXT_TRANSFER_HOOK: u16 = 14;
+const EXT_PAUSABLE: u16 = 26;
+// TransferHook value: [authority:32, program_id:32]
+const TRANSFER_HOOK_PROGRAM_OFFSET: usize = 32;
+
+// Associated Token Account program (ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL)
+const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = [
+    140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142, 13, 131,
+    11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216, 219, 233, 248, 89,
+];
+const ATA_IX_CREATE_IDEMPOTENT: u8 = 1;
+
+// BPF upgradeable loader (BPFLoaderUpgradeab1e11111111111111111111111); ProgramData
+// PDA = [program_id]. Layout: [tag:u32 = 3, slot:u64, authority: Option<Pubkey> (u8 tag + 32)]
+const BPF_LOADER_UPGRADEABLE_ID: Pubkey = [
+    2, 168, 246, 145, 78, 136, 161, 176, 226, 16, 21, 62, 247, 99, 174, 43,
+    0, 194, 185, 61, 22, 193, 36, 210, 192, 83, 122, 16, 4, 128, 0, 0,
+];
+const PROGRAM_DATA_TAG: u32 = 3;
+const PROGRAM_DATA_AUTHORITY_OFFSET: usize = 12;
+
+// VaultState.upgrade_authority_status
+pub const UPGRADE_AUTHORITY_UNKNOWN: u8 = 0;   // never recorded
+pub const UPGRADE_AUTHORITY_IMMUTABLE: u8 = 1; // authority burned; the code can no longer change
+pub const UPGRADE_AUTHORITY_HELD: u8 = 2;      // upgradeable by VaultState.upgrade_authority
 
 // SPL Token discriminants (spl_token::instruction::TokenInstruction)
 const IX_TRANSFER_CHECKED: u8 = 12;
 const IX_MINT_TO_CHECKED:  u8 = 14;
 const IX_BURN_CHECKED:     u8 = 15;
+const IX_INITIALIZE_MINT2: u8 = 20;
+const IX_INITIALIZE_ACCOUNT3: u8 = 18;
+
+// System program: SystemInstruction::CreateAccount (u32 LE)
+const SYS_IX_CREATE_ACCOUNT: u32 = 0;
+const SYS_IX_TRANSFER: u32 = 2;
+
+// SPL Token account layout
+const TOKEN_ACCOUNT_LEN: usize = 165;
+const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+const TOKEN_ACCOUNT_STATE_OFFSET: usize = 108;
+const TOKEN_ACCOUNT_INITIALIZED: u8 = 1; // 0 uninitialized, 2 frozen
+
+// SPL Mint layout
+const MINT_LEN: usize = 82;
+const MINT_SUPPLY_OFFSET: usize = 36;
+const MINT_DECIMALS_OFFSET: usize = 44;
+const MINT_INITIALIZED_OFFSET: usize = 45;
 
 // Our instruction tags
 const OP_INIT:    u8 = 0;
@@ -35,469 +121,4085 @@ const OP_WITHDRAW:u8 = 2;
 const OP_DONATE:  u8 = 3;
 const OP_POSTROOT:u8 = 4;
 const OP_CLAIM:   u8 = 5;
+const OP_SET_YIELD_ORACLE: u8 = 6;
+const OP_SET_DONORS: u8 = 7;
+const OP_SET_BURN_DUST: u8 = 8;
+const OP_INIT_FROM_TEMPLATE: u8 = 9;
+const OP_SET_AUTO_CLAIM: u8 = 10;
+const OP_CRANK_CLAIM: u8 = 11;
+const OP_CLAIM_MANY: u8 = 12;
+const OP_COMMIT_WEIGHTS: u8 = 13;
+const OP_RECONCILE_RENT: u8 = 14;
+const OP_SET_ROOT_SIGNERS: u8 = 15;
+const OP_SET_PPS_CEILING: u8 = 16;
+const OP_RECORD_UPGRADE_AUTHORITY: u8 = 17;
+const OP_SETTLE: u8 = 18;
+const OP_PROPOSE_ADMIN: u8 = 19;
+const OP_ACCEPT_ADMIN: u8 = 20;
+const OP_SET_PAUSE_FLAGS: u8 = 21;
+const OP_OPEN_BOOST_ESCROW: u8 = 22;
+const OP_SET_GUARDIAN: u8 = 23;
+const OP_ENTER_EMERGENCY: u8 = 24;
+const OP_QUOTE_CLAIM: u8 = 25;
+const OP_SET_DEPOSIT_CAP: u8 = 26;
+const OP_SET_USER_DEPOSIT_CAP: u8 = 27;
+const OP_CHECK_SHARE_SUPPLY: u8 = 28;
+const OP_REPAIR_SHARE_SUPPLY: u8 = 29;
+const OP_WITHDRAW_EXACT: u8 = 30;
+const OP_WITHDRAW_ALL: u8 = 31;
+const OP_DEPOSIT_FOR: u8 = 32;
+const OP_CLAIM_COMPOUND: u8 = 33;
+const OP_OPEN_CLAIM_PAGE: u8 = 34;
+const OP_CLAIM_EPOCHS: u8 = 35;
+const OP_OPEN_DISTRIBUTOR: u8 = 36;
+const OP_CLAWBACK: u8 = 37;
+const OP_FINALIZE_EPOCH: u8 = 38;
+const OP_OPEN_CLAIM_ESCROW: u8 = 39;
+const OP_REDEEM_ESCROW: u8 = 40;
+const OP_SET_SUCCESSOR: u8 = 41;
+const OP_MIGRATE_POSITION: u8 = 42;
+const OP_SET_LIVENESS_WINDOW: u8 = 43;
+const OP_TRIP_WATCHDOG: u8 = 44;
+const OP_RECORD_ARCHIVE: u8 = 45;
+const OP_REPORT: u8 = 46;
+// Tag bit, not an op: runs the op as usual, then sets return data to [units:u64], the
+// compute units the op consumed (CPIs included). For simulations that size compute
+// budgets; it replaces any return data the op set itself (QuoteClaim's payout).
+const OP_MEASURE: u8 = 0x80;
+
+const MAX_DONORS: usize = 4;
+const MAX_ROOT_SIGNERS: usize = 3;
+
+// Initial pps_ceiling = starting pps * this; a 1000x share price is not a real outcome
+const DEFAULT_PPS_CEILING_MULT: u128 = 1_000;
+
+// Donation source categories (DonateEvent.category)
+const DONATION_STRATEGY_YIELD: u8 = 0;
+const DONATION_GRANT: u8 = 1;
+const DONATION_REBATE: u8 = 2;
+const DONATION_PENALTY: u8 = 3;
+
+// Claim bits are paged: page 0 is [b"claims", vault_pda, epoch_le] (the original single
+// bitmap), page p > 0 appends p as u16 le. Each page is one ClaimBitmap256.
+const CLAIM_PAGE_BITS: u32 = 256;
+const MAX_CLAIM_PAGES: u32 = 256;
+
+// The vault registry is a header [b"registry"] holding the count and pages
+// [b"registry", page:u32 le] of REGISTRY_PAGE_VAULTS vault_states each, in init order.
+pub const REGISTRY_PAGE_VAULTS: usize = 64;
+
+// How long a posted epoch stays claimable; after that the operator may claw back the rest
+const CLAIM_WINDOW_SLOTS: u64 = 19_440_000; // ~90 days at 400ms slots
+
+// ClaimMany bounds; the hash scratch space lives on the stack
+const MAX_MULTI_LEAVES: usize = 8;
+const MAX_CLAIM_EPOCHS: usize = 8;
+const MAX_MULTI_HASHES: usize = 32;
+
+// First deposit into an empty vault (total_shares == 0)
+const BOOTSTRAP_MIN_DEPOSIT: u64 = 1_000_000;   // 1 USDC at 6 decimals
+const BOOTSTRAP_LOCKED_SHARES: u64 = 1_000_000; // kept in supply until the lock expires
+const BOOTSTRAP_LOCK_SLOTS: u64 = 216_000;      // ~1 day at 400ms slots
+
+// VaultState.flags pause bits; each halts one op family independently
+pub const PAUSE_DEPOSIT: u32 = 1 << 0;
+pub const PAUSE_WITHDRAW: u32 = 1 << 1;
+pub const PAUSE_DONATE: u32 = 1 << 2;
+pub const PAUSE_CLAIM: u32 = 1 << 3;
+// withdraw-only emergency mode: freezes every pause-gated op and pps, but overrides
+// PAUSE_WITHDRAW so depositors can always exit at the last pps
+pub const PAUSE_EMERGENCY: u32 = 1 << 4;
+const PAUSE_ALL: u32 = PAUSE_DEPOSIT | PAUSE_WITHDRAW | PAUSE_DONATE | PAUSE_CLAIM | PAUSE_EMERGENCY;
+// bits the operator may flip on its own; deposit/withdraw gating stays with the admin
+const OPERATOR_PAUSE_BITS: u32 = PAUSE_DONATE | PAUSE_CLAIM;
+
+// Settle crank tip: a cut of the base being folded in, paid to the caller
+const SETTLE_TIP_BPS: u64 = 10;      // 0.1%
+const SETTLE_TIP_MAX: u64 = 10_000;  // 0.01 USDC at 6 decimals
+
+// ---------- Errors ----------
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VaultError {
+    AlreadyClaimed = 1,
+    Unauthorized = 2,     // signer holds none of the roles the op accepts
+    OracleAccountMismatch = 3,
+    OracleEpochMismatch = 4,
+    OracleYieldOutOfTolerance = 5,
+    DonorNotAllowed = 6,
+    ConfidentialMintUnsupported = 7,
+    ClaimIndexOutOfRange = 8,
+    RolloverDistributorClosed = 9,
+    MathOverflow = 10,
+    InvalidBoostBps = 11,
+    BootstrapDepositTooSmall = 12,
+    BootstrapSharesLocked = 13,
+    AutoClaimNotEnabled = 14,
+    PayoutAccountMismatch = 15,
+    WeightCommitmentMissing = 16,
+    WeightCommitmentMismatch = 17,
+    EpochClosed = 18,
+    RootThresholdNotMet = 19,
+    CorruptedState = 20,
+    NothingToSettle = 21,
+    Paused = 22,
+    BoostEscrowMismatch = 23,
+    DepositCapExceeded = 24,
+    UserDepositCapExceeded = 25,
+    SlippageExceeded = 26,
+    ShareSupplyDrift = 27,
+    ClaimWindowClosed = 28,
+    ClaimWindowOpen = 29,
+    EpochFinalized = 30,
+    NotAdmin = 31,
+    NotOperator = 32,
+    NotPendingAdmin = 33,
+    NotGuardian = 34,     // EnterEmergency by a key that is neither guardian nor admin
+    PauseBitsNotAllowed = 35, // operator tried to flip a deposit/withdraw/emergency bit
+    PayoutAccountUnusable = 36, // claimer USDC account frozen or closed and no claim escrow passed
+    NoSuccessor = 37,           // MigratePosition without a registered successor, or with accounts for another one
+    SuccessorMismatch = 38,     // MigratePosition names a successor program or vault_state other than the registered one
+    OperatorActive = 39,        // TripWatchdog with the watchdog off or inside the liveness window
+    ClawbackPending = 40,       // RecordArchive while the epoch still holds unclaimed boost
+    ArchiveRecorded = 41,       // RecordArchive on an epoch that already has an archive hash
+    MintExtensionUnsupported = 42, // Token-2022 mint is non-transferable or has a transfer hook program
+    InvalidTokenProgram = 43,   // token_program is not SPL Token / Token-2022, or not the usdc_mint's owner
+    TokenAccountMismatch = 44,  // a vault-side token account with the wrong mint or owner, or not a token account
+    ShareMintAuthorityMismatch = 45, // share mint's mint authority is not auth_pda
+    WeightSumMismatch = 46,     // a claim would take the epoch's claimed leaf weight past total_weight
+}
+
+impl From<VaultError> for ProgramError {
+    fn from(e: VaultError) -> Self { ProgramError::Custom(e as u32) }
+}
 
 // ---------- State ----------
 #[repr(C)]
 #[derive(Clone, Copy, Pod, Zeroable)]
 pub struct VaultState {
+    pub discriminator: [u8; DISCRIMINATOR_LEN],
     pub admin: Pubkey,
     pub operator: Pubkey,
     pub usdc_mint: Pubkey,
     pub share_mint: Pubkey,
     pub vault_pda: Pubkey,
+    pub auth_pda: Pubkey,  // share mint authority; kept apart from vault_pda, which owns the token accounts
     pub vault_bump: u8,
-    pub _pad1: [u8; 7],
+    pub share_mint_bump: u8,
+    pub auth_bump: u8,
+    pub share_decimals: u8, // may differ from the USDC mint's; math::initial_pps accounts for the gap
+    pub state_bump: u8,     // canonical bump of this account's PDA ([b"state", usdc_mint, seed_admin, vault_id])
+    pub _pad1: [u8; 3],
     pub total_shares: u128,
-    pub pps: u128,            // fixed-point, starts at RAY
+    pub pps: u128,            // fixed-point, starts at math::initial_pps
     pub buffered_base: u64,   // base USDC donated when total_shares == 0
     pub last_settle_slot: u64,
+    pub yield_oracle: Pubkey,       // attestation account checked by donate; default = disabled
+    pub oracle_tolerance_bps: u16,  // allowed |donated - reported| relative to reported
+    pub _pad2: [u8; 6],
+    pub donor_allowlist: [Pubkey; MAX_DONORS], // extra donors besides the operator
+    pub donor_allowlist_len: u8,
+    pub restrict_donors: u8,        // 1 = only operator + allow-list may donate
+    pub burn_dust: u8,              // 1 = withdraw burns leftover shares worth < 1 USDC base unit
+    pub _pad3: [u8; 5],
+    pub bootstrap_locked_shares: u64, // total_shares may not drop below this before the unlock slot
+    pub bootstrap_unlock_slot: u64,
+    pub root_signers: [Pubkey; MAX_ROOT_SIGNERS], // data providers that co-sign PostRoot
+    pub root_signers_len: u8,
+    pub root_threshold: u8,         // distinct root_signers required on PostRoot; 0 = the operator alone
+    pub _pad4: [u8; 14],
+    pub pps_ceiling: u128,          // loads fail above this; set at init, adjustable by the admin
+    pub upgrade_authority: Pubkey,  // program upgrade authority as of the last RecordUpgradeAuthority
+    pub upgrade_authority_slot: u64, // slot it was recorded at
+    pub upgrade_authority_status: u8, // UPGRADE_AUTHORITY_*
+    pub _pad5: [u8; 7],
+    pub pending_admin: Pubkey,      // proposed by the admin; becomes admin once it signs AcceptAdmin
+    pub seed_admin: Pubkey,         // admin in the vault_pda seeds; fixed at init, survives admin rotation
+    pub flags: u32,                 // PAUSE_* bits
+    pub _pad6: [u8; 4],
+    pub vault_id: u64,              // picked at init; tells apart one admin's vaults on the same mint
+    pub guardian: Pubkey,           // may switch on PAUSE_EMERGENCY; only the admin switches it off
+    pub deposit_cap: u64,           // max total_managed_assets a deposit may reach; 0 = uncapped
+    pub user_deposit_cap: u64,      // max cumulative deposits per wallet (UserPosition); 0 = uncapped
+    pub successor_program: Pubkey,  // program of the successor vault; MigratePosition deposits there
+    pub successor: Pubkey,          // successor vault_state registered by the admin; default = none
+    pub liveness_slots: u64,        // silence after which anyone may TripWatchdog; 0 = watchdog off
+    pub last_activity_slot: u64,    // last donation, clawback or settle that folded base
+    pub vault_usdc: Pubkey,         // vault_pda's USDC ATA, created at init; the only vault USDC account ops accept
+    pub boost_usdc: Pubkey,         // shared boost account ([b"boost_usdc", vault_pda]), created at init
 }
 
 #[repr(C)]
 #[derive(Clone, Copy, Pod, Zeroable)]
 pub struct BoostDistributor {
+    pub discriminator: [u8; DISCRIMINATOR_LEN],
     pub epoch: u64,
     pub root: [u8; 32],
+    pub leaf_count: u32,  // leaves in the posted tree; claims must use index < leaf_count
+    pub escrow: u8,       // 1 = boost is held in this epoch's own escrow ([b"escrow", distributor])
+    pub bump: u8,         // canonical bump of this account's PDA, stored by OpenDistributor
+    pub escrow_bump: u8,  // canonical bump of the escrow PDA, stored by OpenBoostEscrow
+    pub finalized: u8,    // 1 = FinalizeEpoch locked root/total_weight/leaf_count; PostRoot refuses to replace them
+    pub _pad0: [u8; 8],
     pub total_weight: u128,
     pub boost_total: u64, // total USDC allocated to boost for this epoch
-    pub _pad: [u8; 8],
+    pub claimed_total: u64, // paid out so far; never exceeds boost_total
+    pub weight_commitment: [u8; 32], // pre-announced keccak of the epoch's tree; PostRoot must match it
+    pub bitmap_page_count: u16, // claim bitmap pages, set by PostRoot to ceil(leaf_count / bitmap_page_size)
+    pub bitmap_page_size: u16,  // leaves per page; 0 for roots posted before paging (read as CLAIM_PAGE_BITS)
+    pub _pad1: [u8; 4],
+    pub expiry_slot: u64,  // claims stop and Clawback opens at this slot; set by PostRoot, 0 = never expires
+    pub clawed_back: u64,  // unclaimed boost returned to the vault by Clawback
+    pub _pad2: [u8; 8],
+    pub archive_hash: [u8; 32], // sha256 of the epoch's off-chain archive, set once by RecordArchive
+    pub claimed_weight: u128,   // leaf weight claimed so far; never exceeds total_weight
 }
 
-#[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
-pub struct ClaimBitmap256 {
-    pub words: [u8; 32], // 256 claim bits
-}
+impl VaultState {
+    // USDC the vault is accountable for: share value at current pps plus base still
+    // waiting to be folded in. The vault has no strategies, so this is all liquid;
+    // invested balances get added here once they exist so caps/previews stay unified.
+    pub fn total_managed_assets(&self) -> Option<u64> {
+        let liquid = math::assets_for_shares(self.total_shares, self.pps)?;
+        let total = liquid.checked_add(self.buffered_base as u128)?;
+        u64::try_from(total).ok()
+    }
 
-// ---------- Helpers ----------
-fn load_mut<'a, T: Pod>(ai: &'a AccountInfo) -> Result<&'a mut T, ProgramError> {
-    let data = ai.try_borrow_mut_data()?;
-    if data.len() < size_of::<T>() { return Err(ProgramError::InvalidAccountData) }
-    let ptr = data.as_mut_ptr();
-    let slice = unsafe { core::slice::from_raw_parts_mut(ptr, size_of::<T>()) };
-    Ok(bytemuck::from_bytes_mut(slice))
+    // No donation or settle for more than liveness_slots: the operator is presumed gone.
+    pub fn operator_silent(&self, slot: u64) -> bool {
+        self.liveness_slots != 0 && slot.saturating_sub(self.last_activity_slot) > self.liveness_slots
+    }
 }
 
-fn check_signer(ai: &AccountInfo) -> ProgramResult {
-    if !ai.is_signer { return Err(ProgramError::MissingRequiredSignature) }
-    Ok(())
-}
+impl BoostDistributor {
+    // Once the root is posted the epoch's payouts are fixed (boost_total * weight / total_weight),
+    // so its funding window is closed and later boost rolls over to the next epoch.
+    pub fn funding_closed(&self) -> bool {
+        self.root != [0u8; 32]
+    }
 
-fn derive_vault_pda(program_id: &Pubkey, usdc_mint: &Pubkey, admin: &Pubkey) -> (Pubkey, u8) {
-    // SAFETY: use runtime syscall
-    let seeds: [&[u8]; 3] = [SEED_VAULT, usdc_mint.as_ref(), admin.as_ref()];
-    let mut out = Pubkey::default();
-    let mut bump: u8 = 0;
-    unsafe {
-        syscalls::sol_try_find_program_address(&seeds, program_id, &mut out, &mut bump);
+    // (page, bit within the page) holding leaf `index`'s claim bit
+    pub fn claim_page(&self, index: u32) -> (u16, u32) {
+        let size = if self.bitmap_page_size == 0 { CLAIM_PAGE_BITS } else { self.bitmap_page_size as u32 };
+        ((index / size) as u16, index % size)
     }
-    (out, bump)
-}
 
-fn keccak256(chunks: &[&[u8]], out: &mut [u8; 32]) {
-    let mut total_len = 0usize;
-    for c in chunks { total_len += c.len(); }
-    let mut tmp = [0u8; 512]; // small stack buffer for short inputs
-    let mut written = 0;
-    for c in chunks {
-        let n = c.len();
-        tmp[written..written+n].copy_from_slice(c);
-        written += n;
+    pub fn claims_expired(&self, slot: u64) -> bool {
+        self.expiry_slot != 0 && slot >= self.expiry_slot
     }
-    unsafe { syscalls::sol_keccak256(&tmp[..written], out) };
-}
 
-fn verify_merkle(root: &[u8; 32], leaf: &[u8; 32], proof: &[[u8;32]]) -> bool {
-    let mut cur = *leaf;
-    let mut buf = [0u8; 32];
-    for node in proof {
-        // sort orderless: hash(min||max)
-        let (a, b) = if cur <= *node { (&cur, node) } else { (node, &cur) };
-        keccak256(&[a, b], &mut buf);
-        cur = buf;
+    // Nothing is owed or left to claw back: the epoch's record can be frozen into an archive.
+    pub fn settled(&self, slot: u64) -> bool {
+        self.claims_expired(slot) && self.claimed_total.checked_add(self.clawed_back) == Some(self.boost_total)
     }
-    &cur == root
 }
 
-// SPL Token CPI data builders (checked variants)
-fn data_transfer_checked(amount: u64, decimals: u8) -> [u8; 1+8+1] {
-    let mut d = [0u8; 10];
-    d[0] = IX_TRANSFER_CHECKED;
-    d[1..9].copy_from_slice(&amount.to_le_bytes());
-    d[9] = decimals;
-    d
-}
-fn data_mint_to_checked(amount: u64, decimals: u8) -> [u8; 1+8+1] {
-    let mut d = [0u8; 10];
-    d[0] = IX_MINT_TO_CHECKED;
-    d[1..9].copy_from_slice(&amount.to_le_bytes());
-    d[9] = decimals;
-    d
-}
-fn data_burn_checked(amount: u64, decimals: u8) -> [u8; 1+8+1] {
-    let mut d = [0u8; 10];
-    d[0] = IX_BURN_CHECKED;
-    d[1..9].copy_from_slice(&amount.to_le_bytes());
-    d[9] = decimals;
-    d
+// Written by an off-chain attestor (e.g. a Switchboard function) at the head of
+// the account configured as `VaultState.yield_oracle`.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct YieldAttestation {
+    pub epoch: u64,
+    pub reported_yield: u64, // USDC base units earned off-chain for `epoch`
 }
 
-fn ix(program: &AccountInfo, data: Vec<u8>, metas: Vec<AccountMeta>) -> Instruction {
-    Instruction { program_id: *program.key, accounts: metas, data }
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct ClaimBitmap256 {
+    pub discriminator: [u8; DISCRIMINATOR_LEN],
+    pub words: [u8; CLAIM_PAGE_BITS as usize / 8], // 256 claim bits
+    pub bump: u8, // canonical bump of this page's PDA, stored by OpenClaimPage
 }
 
-fn vault_signer<'a>(vault_state: &VaultState) -> Signer<'a> {
-    // signer seeds = [SEED_VAULT, usdc, admin, [bump]]
-    let mut bump = [0u8; 1];
-    bump[0] = vault_state.vault_bump;
-    Signer::new(&*SEED_VAULT, &vault_state.usdc_mint.to_bytes(), &vault_state.admin.to_bytes(), &bump)
+// Every vault InitializeVault created, so frontends can list them without a
+// getProgramAccounts scan. PDA [b"registry"]
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct VaultRegistry {
+    pub discriminator: [u8; DISCRIMINATOR_LEN],
+    pub count: u64, // vault `i` is entry i % REGISTRY_PAGE_VAULTS of page i / REGISTRY_PAGE_VAULTS
+    pub bump: u8,
+    pub _pad: [u8; 7],
 }
 
-// ---------- Entry ----------
-pub fn process_instruction(
-    program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    ix_data: &[u8],
-) -> ProgramResult {
-    if ix_data.is_empty() { return Err(ProgramError::InvalidInstructionData) }
-    match ix_data[0] {
-        OP_INIT    => op_init(program_id, accounts, &ix_data[1..]),
-        OP_DEPOSIT => op_deposit(accounts, &ix_data[1..]),
-        OP_WITHDRAW=> op_withdraw(accounts, &ix_data[1..]),
-        OP_DONATE  => op_donate(accounts, &ix_data[1..]),
-        OP_POSTROOT=> op_post_root(accounts, &ix_data[1..]),
-        OP_CLAIM   => op_claim(accounts, &ix_data[1..]),
-        _ => Err(ProgramError::InvalidInstructionData),
-    }
+// PDA [b"registry", page:u32 le]; created by the init that registers its first vault
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct RegistryPage {
+    pub discriminator: [u8; DISCRIMINATOR_LEN],
+    pub page: u32,
+    pub len: u16, // filled entries of `vaults`
+    pub bump: u8,
+    pub _pad: u8,
+    pub vaults: [Pubkey; REGISTRY_PAGE_VAULTS],
 }
 
-// data: [decimals:u8]
-fn op_init(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
-    // accounts:
-    // 0 [w] vault_state
-    // 1 []  admin (signer)
-    // 2 []  operator
-    // 3 []  usdc_mint
-    // 4 []  share_mint
-    // 5 []  vault_pda
-    let [a0,a1,a2,a3,a4,a5, ..] = accs else { return Err(ProgramError::NotEnoughAccountKeys) };
-    check_signer(a1)?;
-    let decimals = data.get(0).ok_or(ProgramError::InvalidInstructionData)?.to_owned();
-    let st = load_mut::<VaultState>(a0)?;
-    let (vault_pda, bump) = derive_vault_pda(program_id, a3.key, a1.key);
-
-    if *a5.key != vault_pda { return Err(ProgramError::InvalidSeeds) }
+// Per-user auto-claim opt-in, PDA [b"prefs", vault_state, user]
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct UserPrefs {
+    pub discriminator: [u8; DISCRIMINATOR_LEN],
+    pub vault_state: Pubkey,
+    pub user: Pubkey,
+    pub auto_claim: u8, // 1 = the operator crank may claim for this user
+    pub compound: u8,   // 1 = crank claims are deposited as shares, 0 = sent to the user's USDC ATA
+    pub bump: u8,
+    pub _pad: [u8; 5],
+}
 
-    *st = VaultState {
-        admin: *a1.key,
-        operator: *a2.key,
-        usdc_mint: *a3.key,
-        share_mint: *a4.key,
-        vault_pda,
-        vault_bump: bump,
-        _pad1: [0;7],
-        total_shares: 0,
-        pps: RAY, // 1.0
-        buffered_base: 0,
-        last_settle_slot: 0,
-    };
+// Per-wallet deposit tally at [b"position", vault_state, user], created by the user's
+// first Deposit that passes it. Withdrawals do not reduce it.
+//
+// The epoch_* fields cover deposits made in Clock epoch `epoch` only and reset on the
+// first deposit of a later epoch. A snapshot tool weighting by time in vault takes a
+// deposit's contribution as shares * (epoch_end_slot - deposit_slot), which summed is
+// epoch_shares * epoch_end_slot - epoch_share_slots.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct UserPosition {
+    pub discriminator: [u8; DISCRIMINATOR_LEN],
+    pub vault_state: Pubkey,
+    pub user: Pubkey,
+    pub deposited: u64, // cumulative USDC deposited through Deposit
+    pub bump: u8,
+    pub _pad: [u8; 15],
+    pub epoch: u64,              // Clock epoch the epoch_* sums belong to
+    pub last_deposit_slot: u64,
+    pub epoch_share_slots: u128, // sum of shares * slot over this epoch's deposits
+    pub epoch_shares: u64,       // shares minted by this epoch's deposits
+    pub _pad1: [u8; 8],
+}
 
-    msg!("vault initialized, decimals={}", decimals as u64);
-    Ok(())
+// The operator's performance report for one epoch at [b"report", vault_pda, epoch_le].
+// Report creates it and nothing writes it again, so the reports are an append-only
+// history depositors can read without trusting a dashboard. The yield, fee and PnL
+// figures are the operator's own; boost_allocated and pps are copied from chain state.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct EpochReport {
+    pub discriminator: [u8; DISCRIMINATOR_LEN],
+    pub epoch: u64,
+    pub pps: u128,            // vault pps when the report landed
+    pub gross_yield: u64,     // USDC earned over the epoch, before fees
+    pub fees: u64,            // part of gross_yield the operator kept; never more than gross_yield
+    pub boost_allocated: u64, // the epoch distributor's boost_total (fixed once its root is posted)
+    pub strategy_pnl: i64,    // realized strategy profit, negative for a loss
+    pub reported_slot: u64,
+    pub operator: Pubkey,
+    pub bump: u8,
+    pub _pad: [u8; 7],
 }
 
-// data: [amount_usdc:u64, usdc_decimals:u8]
-fn op_deposit(accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
-    // accounts:
-    // 0 [w] vault_state
-    // 1 []  vault_pda
-    // 2 [s] user
-    // 3 [w] user_usdc_ata
-    // 4 [w] vault_usdc_ata
-    // 5 [w] share_mint
-    // 6 [w] user_share_ata
-    // 7 []  token_program
-    // 8 []  usdc_mint
-    let [a0,a1,a2,a3,a4,a5,a6,a7,a8, ..] = accs else { return Err(ProgramError::NotEnoughAccountKeys) };
-    check_signer(a2)?;
-    let amount = u64::from_le_bytes(data[..8].try_into().unwrap());
-    let usdc_decimals = data[8];
+pub const DISCRIMINATOR_LEN: usize = 8;
 
-    let st = load_mut::<VaultState>(a0)?;
-    if *a1.key != st.vault_pda { return Err(ProgramError::InvalidSeeds) }
-    if *a5.key != st.share_mint || *a8.key != st.usdc_mint { return Err(ProgramError::InvalidArgument) }
+// First 8 bytes of every state account the program owns: sha256("account:<Type>")[..8],
+// the scheme Anchor uses, so explorers that know it can label the accounts. `load` and
+// `load_mut` refuse an account whose tag is not its type's, so one account type can never
+// be read as another (a claims page as a distributor, say). `LEN` is the account size
+// to allocate; creation sites and off-chain tools use it rather than their own size_of.
+pub trait Discriminator: Pod {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN];
+    const LEN: usize = size_of::<Self>();
+}
 
-    // 1) pull USDC from user -> vault ATA
-    {
-        let metas = vec![
-            AccountMeta::new(*a3.key, true),       // src
-            AccountMeta::new_readonly(*a8.key, false), // mint
-            AccountMeta::new(*a4.key, false),      // dst
-            AccountMeta::new_readonly(*a2.key, true),  // owner
-        ];
-        let data = data_transfer_checked(amount, usdc_decimals).to_vec();
-        let ix = ix(a7, data, metas);
-        cpi::invoke(&ix, &[a7,a3,a8,a4,a2])?;
-    }
+impl Discriminator for VaultState { const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [228, 196, 82, 165, 98, 210, 235, 152]; }
+impl Discriminator for BoostDistributor { const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [255, 241, 180, 222, 75, 209, 56, 154]; }
+impl Discriminator for ClaimBitmap256 { const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [25, 187, 72, 81, 60, 241, 172, 74]; }
+impl Discriminator for UserPrefs { const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [149, 40, 201, 44, 154, 96, 80, 150]; }
+impl Discriminator for UserPosition { const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [251, 248, 209, 245, 83, 234, 17, 27]; }
+impl Discriminator for VaultRegistry { const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [15, 54, 133, 46, 80, 169, 250, 79]; }
+impl Discriminator for RegistryPage { const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [190, 151, 207, 163, 226, 253, 16, 250]; }
+impl Discriminator for EpochReport { const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [26, 58, 58, 64, 205, 34, 79, 32]; }
 
-    // settle buffered if any and shares > 0
-    if st.buffered_base > 0 && st.total_shares > 0 {
-        let delta = ((st.buffered_base as u128) * RAY) / st.total_shares;
-        st.pps = st.pps.checked_add(delta).ok_or(ProgramError::InvalidInstructionData)?;
-        st.buffered_base = 0;
+impl UserPosition {
+    // adds `amount` USDC to the cumulative tally and `shares` to this epoch's weight
+    fn record_deposit(&mut self, amount: u64, shares: u64, clock: &Clock) -> ProgramResult {
+        self.deposited = self.deposited.checked_add(amount).ok_or(VaultError::MathOverflow)?;
+        if self.epoch != clock.epoch {
+            self.epoch = clock.epoch;
+            self.epoch_shares = 0;
+            self.epoch_share_slots = 0;
+        }
+        let share_slots = (shares as u128).checked_mul(clock.slot as u128).ok_or(VaultError::MathOverflow)?;
+        self.epoch_share_slots = self.epoch_share_slots.checked_add(share_slots).ok_or(VaultError::MathOverflow)?;
+        self.epoch_shares = self.epoch_shares.checked_add(shares).ok_or(VaultError::MathOverflow)?;
+        self.last_deposit_slot = clock.slot;
+        Ok(())
     }
+}
 
-    // 2) mint vault shares to user
-    let shares = if st.total_shares == 0 {
-        // first depositor: 1:1
-        amount as u128 * RAY / st.pps
-    } else {
-        amount as u128 * RAY / st.pps
-    };
-    let mint_amt: u64 = shares.try_into().map_err(|_| ProgramError::InvalidInstructionData)?;
-    {
-        let metas = vec![
-            AccountMeta::new(*a5.key, false), // mint
-            AccountMeta::new(*a6.key, false), // dst
-            AccountMeta::new_readonly(*a1.key, false), // mint authority (vault_pda)
-        ];
-        let data = data_mint_to_checked(mint_amt, 6).to_vec(); // share mint uses 6 decimals too (convention)
-        let ix = ix(a7, data, metas);
-        let signer = vault_signer(st);
-        cpi::invoke_signed(&ix, &[a7,a5,a6,a1], &[&signer])?;
-    }
-
-    st.total_shares = st.total_shares.checked_add(shares).ok_or(ProgramError::InvalidInstructionData)?;
-    Ok(())
-}
-
-// data: [shares:u64, usdc_decimals:u8]
-fn op_withdraw(accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
-    // accounts:
-    // 0 [w] vault_state
-    // 1 []  vault_pda
-    // 2 [s] user
-    // 3 [w] user_usdc_ata
-    // 4 [w] vault_usdc_ata
-    // 5 [w] share_mint
-    // 6 [w] user_share_ata
-    // 7 []  token_program
-    // 8 []  usdc_mint
-    let [a0,a1,a2,a3,a4,a5,a6,a7,a8, ..] = accs else { return Err(ProgramError::NotEnoughAccountKeys) };
-    check_signer(a2)?;
-    let shares_burn: u64 = u64::from_le_bytes(data[..8].try_into().unwrap());
-    let usdc_decimals = data[8];
+// ---------- Events ----------
+// Emitted via sol_log_data as [tag:u8, event bytes, EventStamp] in every build
+// profile; indexers decode them, so the layouts below are append-only.
+const EVT_INIT:     u8 = 0;
+const EVT_DEPOSIT:  u8 = 1;
+const EVT_WITHDRAW: u8 = 2;
+const EVT_DONATE:   u8 = 3;
+const EVT_POSTROOT: u8 = 4;
+const EVT_CLAIM:    u8 = 5;
+const EVT_DUST_BURNED: u8 = 6;
+const EVT_WEIGHTS_COMMITTED: u8 = 7;
+const EVT_RENT_RECONCILED: u8 = 8;
+const EVT_UPGRADE_AUTHORITY_RECORDED: u8 = 9;
+const EVT_SETTLED: u8 = 10;
+const EVT_ADMIN_TRANSFERRED: u8 = 11;
+const EVT_PAUSE_FLAGS_SET: u8 = 12;
+const EVT_SHARE_SUPPLY_REPAIRED: u8 = 13;
+const EVT_CLAWBACK: u8 = 14;
+const EVT_EPOCH_FINALIZED: u8 = 15;
+const EVT_ESCROW_REDEEMED: u8 = 16;
+const EVT_POSITION_MIGRATED: u8 = 17;
+const EVT_EPOCH_ARCHIVED: u8 = 18;
+const EVT_EPOCH_REPORTED: u8 = 19;
 
-    let st = load_mut::<VaultState>(a0)?;
-    if *a1.key != st.vault_pda { return Err(ProgramError::InvalidSeeds) }
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct InitEvent {
+    pub vault_state: Pubkey,
+    pub admin: Pubkey,
+    pub operator: Pubkey,
+    pub usdc_mint: Pubkey,
+    pub share_mint: Pubkey,
+}
 
-    // burn shares from user
-    {
-        let metas = vec![
-            AccountMeta::new(*a6.key, false), // account to burn from
-            AccountMeta::new(*a5.key, false), // share mint
-            AccountMeta::new_readonly(*a2.key, true), // owner is user
-        ];
-        let data = data_burn_checked(shares_burn, 6).to_vec();
-        let ix = ix(a7, data, metas);
-        cpi::invoke(&ix, &[a7,a6,a5,a2])?;
-    }
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct DepositEvent {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub shares: u64,
+    pub pps: u128,
+    pub recipient: Pubkey, // owner of the share account credited; `user` unless DepositFor
+}
 
-    // send USDC to user equal to shares * pps
-    let shares_u128 = shares_burn as u128;
-    let amount_out_u128 = shares_u128
-        .checked_mul(st.pps).ok_or(ProgramError::InvalidInstructionData)?
-        / RAY;
-    let amount_out: u64 = amount_out_u128.try_into().map_err(|_| ProgramError::InvalidInstructionData)?;
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct WithdrawEvent {
+    pub user: Pubkey,
+    pub shares: u64,
+    pub amount: u64,
+    pub pps: u128,
+    pub recipient: Pubkey, // owner of the USDC account paid
+}
 
-    // transfer vault USDC -> user USDC using vault signer
-    {
-        let metas = vec![
-            AccountMeta::new(*a4.key, false), // src vault
-            AccountMeta::new_readonly(*a8.key, false), // mint
-            AccountMeta::new(*a3.key, false), // dst
-            AccountMeta::new_readonly(*a1.key, false), // owner vault_pda
-        ];
-        let data = data_transfer_checked(amount_out, usdc_decimals).to_vec();
-        let ix = ix(a7, data, metas);
-        let signer = vault_signer(st);
-        cpi::invoke_signed(&ix, &[a7,a4,a8,a3,a1], &[&signer])?;
-    }
-
-    st.total_shares = st.total_shares.checked_sub(shares_u128).ok_or(ProgramError::InvalidInstructionData)?;
-    Ok(())
-}
-
-// data: [amount_usdc:u64, epoch:u64, boost_bps:u16, usdc_decimals:u8]
-fn op_donate(accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
-    // accounts:
-    // 0 [w] vault_state
-    // 1 []  vault_pda
-    // 2 [s] operator (or anyone)
-    // 3 [w] operator_usdc_ata
-    // 4 [w] vault_usdc_ata
-    // 5 [w] boost_usdc_ata   (owned by vault_pda)
-    // 6 []  token_program
-    // 7 []  usdc_mint
-    // 8 [w] boost_distributor (for epoch)  (optional writable if present)
-    let [a0,a1,a2,a3,a4,a5,a6,a7,a8, ..] = accs else { return Err(ProgramError::NotEnoughAccountKeys) };
-    check_signer(a2)?;
-    let amount = u64::from_le_bytes(data[0..8].try_into().unwrap());
-    let epoch  = u64::from_le_bytes(data[8..16].try_into().unwrap());
-    let boost_bps = u16::from_le_bytes(data[16..18].try_into().unwrap()) as u64;
-    let usdc_decimals = data[18];
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct DonateEvent {
+    pub donor: Pubkey,
+    pub memo: [u8; 32],
+    pub epoch: u64,
+    pub amount: u64,
+    pub boost: u64,
+    pub base: u64,
+    pub pps: u128,
+    pub category: u8, // DONATION_* source, for breaking pps growth down by origin
+    pub _pad: [u8; 15],
+}
 
-    let st = load_mut::<VaultState>(a0)?;
-    if *a1.key != st.vault_pda { return Err(ProgramError::InvalidSeeds) }
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct PostRootEvent {
+    pub epoch: u64,
+    pub _pad: [u8; 8],
+    pub total_weight: u128,
+    pub root: [u8; 32],
+    pub leaf_count: u32,
+    pub _pad1: [u8; 12],
+}
 
-    // operator_ata -> vault_ata
-    {
-        let metas = vec![
-            AccountMeta::new(*a3.key, true),
-            AccountMeta::new_readonly(*a7.key, false),
-            AccountMeta::new(*a4.key, false),
-            AccountMeta::new_readonly(*a2.key, true),
-        ];
-        let ix = ix(a6, data_transfer_checked(amount, usdc_decimals).to_vec(), metas);
-        cpi::invoke(&ix, &[a6,a3,a7,a4,a2])?;
-    }
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct ClaimEvent {
+    pub weight: u128,
+    pub claimer: Pubkey,
+    pub epoch: u64,
+    pub amount: u64,
+    pub index: u32,
+    pub _pad: [u8; 12],
+}
 
-    let boost = amount * boost_bps / 10_000;
-    let base  = amount - boost;
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct EventStamp {
+    pub slot: u64,
+    pub unix_timestamp: i64,
+}
 
-    // vault_ata -> boost_ata (boost part) signed by vault
-    if boost > 0 {
-        let metas = vec![
-            AccountMeta::new(*a4.key, false),
-            AccountMeta::new_readonly(*a7.key, false),
-            AccountMeta::new(*a5.key, false),
-            AccountMeta::new_readonly(*a1.key, false),
-        ];
-        let ix = ix(a6, data_transfer_checked(boost, usdc_decimals).to_vec(), metas);
-        let signer = vault_signer(st);
-        cpi::invoke_signed(&ix, &[a6,a4,a7,a5,a1], &[&signer])?;
-    }
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct DustBurnedEvent {
+    pub user: Pubkey,
+    pub shares: u64,
+}
 
-    // bump PPS or buffer
-    if st.total_shares > 0 {
-        let delta = ((base as u128) * RAY) / st.total_shares;
-        st.pps = st.pps.checked_add(delta).ok_or(ProgramError::InvalidInstructionData)?;
-    } else {
-        st.buffered_base = st.buffered_base.saturating_add(base);
-    }
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct WeightsCommittedEvent {
+    pub epoch: u64,
+    pub commitment: [u8; 32],
+}
 
-    // Optional: update boost distributor (if provided)
-    if a8.owner == a0.owner && a8.data_len() >= size_of::<BoostDistributor>() {
-        let bd = load_mut::<BoostDistributor>(a8)?;
-        if bd.epoch == 0 { bd.epoch = epoch; }
-        if bd.epoch != epoch { return Err(ProgramError::InvalidArgument) }
-        bd.boost_total = bd.boost_total.saturating_add(boost);
-    }
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct RentReconciledEvent {
+    pub account: Pubkey,
+    pub lamports_before: u64,
+    pub lamports_after: u64,
+}
 
-    Ok(())
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct UpgradeAuthorityRecordedEvent {
+    pub authority: Pubkey, // default when immutable
+    pub status: u8,
+    pub _pad: [u8; 7],
 }
 
-// data: [epoch:u64, total_weight:u128, root: [u8;32]]
-fn op_post_root(accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
-    // accounts:
-    // 0 [w] vault_state
-    // 1 []  operator (signer)
-    // 2 [w] boost_distributor (PDA)
-    let [a0,a1,a2, ..] = accs else { return Err(ProgramError::NotEnoughAccountKeys) };
-    check_signer(a1)?;
-    let _st = load_mut::<VaultState>(a0)?; // enforce ownership but we don't use fields
-    let epoch = u64::from_le_bytes(data[0..8].try_into().unwrap());
-    let total_weight = u128::from_le_bytes(data[8..24].try_into().unwrap());
-    let mut root = [0u8;32];
-    root.copy_from_slice(&data[24..56]);
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct SettledEvent {
+    pub caller_usdc_ata: Pubkey, // default when no tip was requested
+    pub folded_base: u64,
+    pub tip: u64,
+    pub pps: u128,
+}
 
-    let bd = load_mut::<BoostDistributor>(a2)?;
-    bd.epoch = epoch;
-    bd.total_weight = total_weight;
-    bd.root = root;
-    Ok(())
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct AdminTransferredEvent {
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
 }
 
-// data: [epoch:u64, index:u32, weight:u128, proof_len:u8, proof_nodes... (32b each)]
-fn op_claim(accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
-    // accounts:
-    // 0 [w] vault_state
-    // 1 []  vault_pda
-    // 2 [s] claimer
-    // 3 [w] boost_distributor
-    // 4 [w] claims_bitmap
-    // 5 [w] boost_usdc_ata (owned by vault_pda)
-    // 6 [w] claimer_usdc_ata
-    // 7 []  token_program
-    // 8 []  usdc_mint
-    let [a0,a1,a2,a3,a4,a5,a6,a7,a8, ..] = accs else { return Err(ProgramError::NotEnoughAccountKeys) };
-    check_signer(a2)?;
-    let epoch = u64::from_le_bytes(data[0..8].try_into().unwrap());
-    let index = u32::from_le_bytes(data[8..12].try_into().unwrap());
-    let weight = u128::from_le_bytes(data[12..28].try_into().unwrap());
-    let proof_len = data[28] as usize;
-    let mut off = 29usize;
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct PauseFlagsSetEvent {
+    pub authority: Pubkey,
+    pub old_flags: u32,
+    pub new_flags: u32,
+}
 
-    let st = load_mut::<VaultState>(a0)?;
-    if *a1.key != st.vault_pda { return Err(ProgramError::InvalidSeeds) }
-    let bd = load_mut::<BoostDistributor>(a3)?;
-    if bd.epoch != epoch { return Err(ProgramError::InvalidArgument) }
-    if bd.total_weight == 0 { return Err(ProgramError::InvalidInstructionData) }
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct ShareSupplyRepairedEvent {
+    pub old_total_shares: u128,
+    pub new_total_shares: u128,
+}
 
-    // bitmap
-    let bm = load_mut::<ClaimBitmap256>(a4)?;
-    let bit = (index & 7) as u8;
-    let byte = (index / 8) as usize;
-    if byte >= bm.words.len() { return Err(ProgramError::InvalidInstructionData) }
-    let mask = 1u8 << bit;
-    if (bm.words[byte] & mask) != 0 { return Err(ProgramError::Custom(1)) } // already claimed
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct ClawbackEvent {
+    pub epoch: u64,
+    pub amount: u64,
+    pub pps: u128, // after the amount is folded in; unchanged while it waits in buffered_base
+}
 
-    // proof
-    let mut leaf = [0u8;32];
-    let mut idx_le = [0u8;4];
-    idx_le.copy_from_slice(&index.to_le_bytes());
-    // domain-separated leaf: keccak(b"weight", index, claimer, weight)
-    keccak256(&[
-        b"weight",
-        &idx_le,
-        a2.key.as_ref(),
-        &weight.to_le_bytes(),
-    ], &mut leaf);
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct EpochFinalizedEvent {
+    pub root: [u8; 32],
+    pub epoch: u64,
+}
 
-    // read proof nodes
-    let nodes = proof_len;
-    if data.len() < off + nodes*32 { return Err(ProgramError::InvalidInstructionData) }
-    let mut proof = [[0u8;32]; 16];
-    if nodes > 16 { return Err(ProgramError::InvalidInstructionData) }
-    for i in 0..nodes {
-        proof[i].copy_from_slice(&data[off..off+32]);
-        off += 32;
-    }
-    let ok = verify_merkle(&bd.root, &leaf, &proof[..nodes]);
-    if !ok { return Err(ProgramError::InvalidArgument) }
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct EscrowRedeemedEvent {
+    pub claimer: Pubkey,
+    pub amount: u64,
+}
 
-    // compute claim amount
-    let claim_u128 = (bd.boost_total as u128)
-        .saturating_mul(weight)
-        / bd.total_weight;
-    let claim: u64 = claim_u128.try_into().map_err(|_| ProgramError::InvalidInstructionData)?;
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct PositionMigratedEvent {
+    pub user: Pubkey,
+    pub successor: Pubkey,
+    pub amount: u64, // USDC the burned shares paid, all deposited into the successor
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct EpochArchivedEvent {
+    pub archive_hash: [u8; 32],
+    pub epoch: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct EpochReportedEvent {
+    pub epoch: u64,
+    pub gross_yield: u64,
+    pub fees: u64,
+    pub boost_allocated: u64,
+    pub strategy_pnl: i64,
+}
+
+fn emit<T: Pod>(tag: u8, clock: &Clock, event: &T) {
+    let stamp = EventStamp { slot: clock.slot, unix_timestamp: clock.unix_timestamp };
+    sol_log_data(&[&[tag], bytemuck::bytes_of(event), bytemuck::bytes_of(&stamp)]);
+}
+
+// Human-readable logs for devnet debugging; compiled out of the default build to save CUs.
+// msg! cannot format u128, so pass pps and share totals through logfmt::dec / logfmt::ray.
+macro_rules! vlog {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "verbose-logs")]
+        pinocchio::msg!($($arg)*);
+    };
+}
+
+// ---------- Helpers ----------
+fn cast_mut<'a, T: Pod>(ai: &'a AccountInfo) -> Result<&'a mut T, ProgramError> {
+    let data = ai.try_borrow_mut_data()?;
+    if data.len() < size_of::<T>() { return Err(ProgramError::InvalidAccountData) }
+    let ptr = data.as_mut_ptr();
+    let slice = unsafe { core::slice::from_raw_parts_mut(ptr, size_of::<T>()) };
+    Ok(bytemuck::from_bytes_mut(slice))
+}
+
+// plain Pod read without a discriminator; for accounts the program does not own
+fn cast<'a, T: Pod>(ai: &'a AccountInfo) -> Result<&'a T, ProgramError> {
+    let data = ai.try_borrow_data()?;
+    if data.len() < size_of::<T>() { return Err(ProgramError::InvalidAccountData) }
+    let ptr = data.as_ptr();
+    let slice = unsafe { core::slice::from_raw_parts(ptr, size_of::<T>()) };
+    Ok(bytemuck::from_bytes(slice))
+}
+
+// A T is a program-owned account carrying T's tag; the owner check comes first, since any
+// program can write a matching tag into an account it owns.
+fn load_mut<'a, T: Discriminator>(program_id: &Pubkey, ai: &'a AccountInfo) -> Result<&'a mut T, ProgramError> {
+    if ai.owner != program_id { return Err(ProgramError::IncorrectProgramId) }
+    let t = cast_mut::<T>(ai)?;
+    if bytemuck::bytes_of(t)[..8] != T::DISCRIMINATOR { return Err(ProgramError::InvalidAccountData) }
+    Ok(t)
+}
+
+fn load<'a, T: Discriminator>(program_id: &Pubkey, ai: &'a AccountInfo) -> Result<&'a T, ProgramError> {
+    if ai.owner != program_id { return Err(ProgramError::IncorrectProgramId) }
+    let t = cast::<T>(ai)?;
+    if bytemuck::bytes_of(t)[..8] != T::DISCRIMINATOR { return Err(ProgramError::InvalidAccountData) }
+    Ok(t)
+}
+
+// Tags a freshly created (still zeroed) account as a T; fails on one that already has a tag.
+fn init_account<'a, T: Discriminator>(ai: &'a AccountInfo) -> Result<&'a mut T, ProgramError> {
+    let t = cast_mut::<T>(ai)?;
+    let bytes = bytemuck::bytes_of_mut(t);
+    if bytes[..DISCRIMINATOR_LEN] != [0; DISCRIMINATOR_LEN] { return Err(ProgramError::AccountAlreadyInitialized) }
+    bytes[..DISCRIMINATOR_LEN].copy_from_slice(&T::DISCRIMINATOR);
+    Ok(t)
+}
+
+// pps can only grow from its starting value, so anything outside [PPS_FLOOR, pps_ceiling]
+// means a math bug or corrupted state; halt rather than pay out against it.
+fn check_pps(st: &VaultState) -> ProgramResult {
+    if st.pps < math::PPS_FLOOR || st.pps > st.pps_ceiling { return Err(VaultError::CorruptedState.into()) }
+    Ok(())
+}
+
+// Role checks on a key whose signature the op's Accounts::parse already required.
+fn require_admin(st: &VaultState, key: &Pubkey) -> ProgramResult {
+    if *key != st.admin { return Err(VaultError::NotAdmin.into()) }
+    Ok(())
+}
+
+fn require_operator(st: &VaultState, key: &Pubkey) -> ProgramResult {
+    if *key != st.operator { return Err(VaultError::NotOperator.into()) }
+    Ok(())
+}
+
+fn check_not_paused(st: &VaultState, bit: u32) -> ProgramResult {
+    if st.flags & PAUSE_EMERGENCY != 0 {
+        return if bit == PAUSE_WITHDRAW { Ok(()) } else { Err(VaultError::Paused.into()) }
+    }
+    if st.flags & bit != 0 { return Err(VaultError::Paused.into()) }
+    Ok(())
+}
+
+// The user's position, created on first use; Deposit then records into it.
+fn load_position<'a>(
+    program_id: &Pubkey, vault_state: &AccountInfo, user: &AccountInfo, accs: PositionAccounts<'a>,
+) -> Result<&'a mut UserPosition, ProgramError> {
+    let PositionAccounts { position, system_program } = accs;
+    let (expected, bump) = derive_user_position(program_id, vault_state.key, user.key);
+    if *position.key != expected { return Err(ProgramError::InvalidSeeds) }
+    if position.data_is_empty() {
+        let lamports = Rent::get()?.minimum_balance(UserPosition::LEN);
+        let metas = [AccountMeta::writable_signer(user.key), AccountMeta::writable_signer(position.key)];
+        let data = data_create_account(lamports, UserPosition::LEN as u64, program_id);
+        let bump = [bump];
+        let seeds = [Seed::from(SEED_POSITION), Seed::from(vault_state.key.as_ref()), Seed::from(user.key.as_ref()), Seed::from(bump.as_ref())];
+        cpi::invoke_signed(&ix(system_program, &data, &metas), &[user,position], &[Signer::from(&seeds)])?;
+        init_account::<UserPosition>(position)?;
+    }
+    let p = load_mut::<UserPosition>(program_id, position)?;
+    p.vault_state = *vault_state.key;
+    p.user = *user.key;
+    p.bump = bump;
+    Ok(p)
+}
+
+fn check_user_deposit_cap(st: &VaultState, p: &UserPosition) -> ProgramResult {
+    if st.user_deposit_cap != 0 && p.deposited > st.user_deposit_cap {
+        return Err(VaultError::UserDepositCapExceeded.into())
+    }
+    Ok(())
+}
+
+// run after the deposit is counted; donations may still grow assets past the cap
+fn check_deposit_cap(st: &VaultState) -> ProgramResult {
+    if st.deposit_cap == 0 { return Ok(()) }
+    let total = st.total_managed_assets().ok_or(VaultError::MathOverflow)?;
+    if total > st.deposit_cap { return Err(VaultError::DepositCapExceeded.into()) }
+    Ok(())
+}
+
+// A vault_state is the account at the PDA its own fields name. Without this, a second
+// program-owned account carrying the tag (say, a stale copy) could stand in for it.
+fn check_vault_state_key(program_id: &Pubkey, vault_state: &AccountInfo, st: &VaultState) -> ProgramResult {
+    let expected = stored_pda(&[
+        SEED_STATE, st.usdc_mint.as_ref(), vault_seed_admin(st).as_ref(), &st.vault_id.to_le_bytes(), &[st.state_bump],
+    ], program_id)?;
+    if *vault_state.key != expected { return Err(ProgramError::InvalidSeeds) }
+    Ok(())
+}
+
+fn load_vault_mut<'a>(program_id: &Pubkey, ai: &'a AccountInfo) -> Result<&'a mut VaultState, ProgramError> {
+    let st = load_mut::<VaultState>(program_id, ai)?;
+    check_vault_state_key(program_id, ai, st)?;
+    check_pps(st)?;
+    Ok(st)
+}
+
+fn load_vault<'a>(program_id: &Pubkey, ai: &'a AccountInfo) -> Result<&'a VaultState, ProgramError> {
+    let st = load::<VaultState>(program_id, ai)?;
+    check_vault_state_key(program_id, ai, st)?;
+    check_pps(st)?;
+    Ok(st)
+}
+
+fn check_signer(ai: &AccountInfo) -> ProgramResult {
+    if !ai.is_signer { return Err(ProgramError::MissingRequiredSignature) }
+    Ok(())
+}
+
+// (type, value) of each Token-2022 extension on a mint; nothing for a plain SPL mint
+fn mint_extensions(data: &[u8]) -> impl Iterator<Item = (u16, &[u8])> + '_ {
+    let extended = data.len() > T22_ACCOUNT_TYPE_OFFSET && data[T22_ACCOUNT_TYPE_OFFSET] == T22_ACCOUNT_TYPE_MINT;
+    let mut off = if extended { T22_ACCOUNT_TYPE_OFFSET + 1 } else { data.len() };
+    core::iter::from_fn(move || {
+        let head = data.get(off..off + T22_TLV_HEADER)?;
+        let ty = u16::from_le_bytes([head[0], head[1]]);
+        let len = u16::from_le_bytes([head[2], head[3]]) as usize;
+        if ty == 0 { return None } // uninitialized tail
+        let value = data.get(off + T22_TLV_HEADER..off + T22_TLV_HEADER + len)?;
+        off += T22_TLV_HEADER + len;
+        Some((ty, value))
+    })
+}
+
+fn mint_has_extension(data: &[u8], ext: u16) -> bool {
+    mint_extensions(data).any(|(ty, _)| ty == ext)
+}
+
+// Confidential-transfer balances are encrypted, so the vault can never observe
+// what it received; refuse such mints up front instead of failing inside a CPI.
+// Non-transferable mints can't move at all, and a transfer hook would need its
+// extra accounts on every transfer the vault makes.
+fn check_mint_supported(mint: &AccountInfo) -> ProgramResult {
+    if *mint.owner != TOKEN_2022_PROGRAM_ID { return Ok(()) }
+    let data = mint.try_borrow_data()?;
+    if mint_has_extension(&data, EXT_CONFIDENTIAL_TRANSFER_MINT) {
+        return Err(VaultError::ConfidentialMintUnsupported.into())
+    }
+    for (ty, value) in mint_extensions(&data) {
+        let hooked = ty == EXT_TRANSFER_HOOK
+            && value.get(TRANSFER_HOOK_PROGRAM_OFFSET..TRANSFER_HOOK_PROGRAM_OFFSET + 32).is_some_and(|p| p != [0u8; 32]);
+        if ty == EXT_NON_TRANSFERABLE || hooked { return Err(VaultError::MintExtensionUnsupported.into()) }
+    }
+    Ok(())
+}
+
+// The token program is whichever one owns the mint, SPL Token or Token-2022. Anything
+// else passed as token_program would be invoked with vault_pda's signature.
+fn check_token_program(token_program: &AccountInfo, mint: &AccountInfo) -> ProgramResult {
+    if *token_program.key != TOKEN_PROGRAM_ID && *token_program.key != TOKEN_2022_PROGRAM_ID {
+        return Err(VaultError::InvalidTokenProgram.into())
+    }
+    if mint.owner != token_program.key { return Err(VaultError::InvalidTokenProgram.into()) }
+    Ok(())
+}
+
+fn check_vault_mint(st: &VaultState, usdc_mint: &AccountInfo, token_program: &AccountInfo) -> ProgramResult {
+    if *usdc_mint.key != st.usdc_mint { return Err(ProgramError::InvalidArgument) }
+    check_token_program(token_program, usdc_mint)
+}
+
+// Size of a token account for this mint. InitializeAccount3 on Token-2022 only accepts
+// an account sized for the account-side extensions the mint requires.
+fn token_account_len(mint: &AccountInfo) -> Result<usize, ProgramError> {
+    Ok(account_len_for_mint(&mint.try_borrow_data()?))
+}
+
+fn account_len_for_mint(mint_data: &[u8]) -> usize {
+    let ext: usize = mint_extensions(mint_data).map(|(ty, _)| match ty {
+        EXT_TRANSFER_FEE_CONFIG => T22_TLV_HEADER + 8, // TransferFeeAmount { withheld_amount }
+        EXT_TRANSFER_HOOK => T22_TLV_HEADER + 1,       // TransferHookAccount { transferring }
+        EXT_PAUSABLE => T22_TLV_HEADER,                // PausableAccount
+        _ => 0,
+    }).sum();
+    if ext == 0 { return TOKEN_ACCOUNT_LEN }
+    let len = T22_ACCOUNT_TYPE_OFFSET + 1 + ext;
+    if len == T22_MULTISIG_LEN { len + 2 } else { len }
+}
+
+// The base layout shared by SPL Token and Token-2022 accounts, as far as the vault reads it.
+#[derive(Debug, PartialEq)]
+struct TokenAccount {
+    mint: Pubkey,
+    owner: Pubkey,
+    amount: u64,
+}
+
+fn unpack_token_account(data: &[u8]) -> Result<TokenAccount, ProgramError> {
+    if data.len() < TOKEN_ACCOUNT_LEN || data[TOKEN_ACCOUNT_STATE_OFFSET] == 0 { return Err(ProgramError::InvalidAccountData) }
+    Ok(TokenAccount {
+        mint: data[0..32].try_into().unwrap(),
+        owner: data[32..TOKEN_ACCOUNT_AMOUNT_OFFSET].try_into().unwrap(),
+        amount: u64::from_le_bytes(data[TOKEN_ACCOUNT_AMOUNT_OFFSET..TOKEN_ACCOUNT_AMOUNT_OFFSET + 8].try_into().unwrap()),
+    })
+}
+
+#[derive(Debug, PartialEq)]
+struct Mint {
+    mint_authority: Option<Pubkey>,
+    supply: u64,
+    decimals: u8,
+}
+
+fn unpack_mint(data: &[u8]) -> Result<Mint, ProgramError> {
+    if data.len() < MINT_LEN || data[MINT_INITIALIZED_OFFSET] != 1 { return Err(ProgramError::InvalidAccountData) }
+    // COption<Pubkey>: u32 tag, then the key
+    let mint_authority = match u32::from_le_bytes(data[0..4].try_into().unwrap()) {
+        0 => None,
+        1 => Some(data[4..36].try_into().unwrap()),
+        _ => return Err(ProgramError::InvalidAccountData),
+    };
+    Ok(Mint {
+        mint_authority,
+        supply: u64::from_le_bytes(data[MINT_SUPPLY_OFFSET..MINT_SUPPLY_OFFSET + 8].try_into().unwrap()),
+        decimals: data[MINT_DECIMALS_OFFSET],
+    })
+}
+
+// Every account is writable by whoever builds the transaction, so a token account the
+// vault pays from, pays into or prices shares on must be `owner`'s account for `mint`
+// under the vault's token program; otherwise a deposit into the caller's own account
+// would still mint shares.
+fn check_token_account(ai: &AccountInfo, token_program: &AccountInfo, mint: &Pubkey, owner: &Pubkey) -> ProgramResult {
+    if ai.owner != token_program.key { return Err(VaultError::TokenAccountMismatch.into()) }
+    let ta = unpack_token_account(&ai.try_borrow_data()?).map_err(|_| VaultError::TokenAccountMismatch)?;
+    if ta.mint != *mint || ta.owner != *owner { return Err(VaultError::TokenAccountMismatch.into()) }
+    Ok(())
+}
+
+// the vault's share mint, which only auth_pda can mint
+fn check_share_mint(st: &VaultState, share_mint: &AccountInfo, token_program: &AccountInfo) -> ProgramResult {
+    if *share_mint.key != st.share_mint { return Err(ProgramError::InvalidArgument) }
+    if share_mint.owner != token_program.key { return Err(VaultError::InvalidTokenProgram.into()) }
+    let m = unpack_mint(&share_mint.try_borrow_data()?)?;
+    if m.mint_authority != Some(st.auth_pda) { return Err(VaultError::ShareMintAuthorityMismatch.into()) }
+    Ok(())
+}
+
+// The vault's USDC account: exactly the vault_pda ATA created at init.
+fn check_vault_usdc(st: &VaultState, ai: &AccountInfo, token_program: &AccountInfo) -> ProgramResult {
+    if *ai.key != st.vault_usdc { return Err(VaultError::TokenAccountMismatch.into()) }
+    check_token_account(ai, token_program, &st.usdc_mint, &st.vault_pda)
+}
+
+// The shared boost account epochs without an escrow are funded into and paid from:
+// exactly the one created at init.
+fn check_boost_usdc(st: &VaultState, ai: &AccountInfo) -> ProgramResult {
+    if *ai.key != st.boost_usdc { return Err(VaultError::BoostEscrowMismatch.into()) }
+    Ok(())
+}
+
+fn token_account_amount(ai: &AccountInfo) -> Result<u64, ProgramError> {
+    Ok(unpack_token_account(&ai.try_borrow_data()?)?.amount)
+}
+
+fn token_account_owner(ai: &AccountInfo) -> Result<Pubkey, ProgramError> {
+    let data = ai.try_borrow_data()?;
+    if data.len() < TOKEN_ACCOUNT_AMOUNT_OFFSET { return Err(ProgramError::InvalidAccountData) }
+    Ok(data[32..TOKEN_ACCOUNT_AMOUNT_OFFSET].try_into().unwrap())
+}
+
+// false for a closed account (no data) and for a frozen one; transfers into either fail
+fn token_account_open(ai: &AccountInfo, token_program: &Pubkey) -> Result<bool, ProgramError> {
+    if ai.owner != token_program { return Ok(false) }
+    let data = ai.try_borrow_data()?;
+    Ok(data.len() >= TOKEN_ACCOUNT_LEN && data[TOKEN_ACCOUNT_STATE_OFFSET] == TOKEN_ACCOUNT_INITIALIZED)
+}
+
+fn mint_supply(mint: &AccountInfo) -> Result<u64, ProgramError> {
+    Ok(unpack_mint(&mint.try_borrow_data()?)?.supply)
+}
+
+fn mint_decimals(mint: &AccountInfo) -> Result<u8, ProgramError> {
+    Ok(unpack_mint(&mint.try_borrow_data()?)?.decimals)
+}
+
+fn derive_vault_pda(program_id: &Pubkey, usdc_mint: &Pubkey, admin: &Pubkey, vault_id: u64) -> (Pubkey, u8) {
+    find_program_address(&[SEED_VAULT, usdc_mint.as_ref(), admin.as_ref(), &vault_id.to_le_bytes()], program_id)
+}
+
+// one vault_state per (usdc_mint, admin, vault_id), created by InitializeVault
+fn derive_vault_state(program_id: &Pubkey, usdc_mint: &Pubkey, admin: &Pubkey, vault_id: u64) -> (Pubkey, u8) {
+    find_program_address(&[SEED_STATE, usdc_mint.as_ref(), admin.as_ref(), &vault_id.to_le_bytes()], program_id)
+}
+
+fn derive_auth_pda(program_id: &Pubkey, vault_pda: &Pubkey) -> (Pubkey, u8) {
+    find_program_address(&[SEED_AUTH, vault_pda.as_ref()], program_id)
+}
+
+fn derive_share_mint(program_id: &Pubkey, vault_state: &Pubkey) -> (Pubkey, u8) {
+    find_program_address(&[SEED_SHARES, vault_state.as_ref()], program_id)
+}
+
+fn derive_boost_usdc(program_id: &Pubkey, vault_pda: &Pubkey) -> (Pubkey, u8) {
+    find_program_address(&[SEED_BOOST_USDC, vault_pda.as_ref()], program_id)
+}
+
+fn derive_boost_distributor(program_id: &Pubkey, vault_pda: &Pubkey, epoch: u64) -> (Pubkey, u8) {
+    find_program_address(&[SEED_BOOST, vault_pda.as_ref(), &epoch.to_le_bytes()], program_id)
+}
+
+// Program-owned PDAs keep the canonical bump find_program_address gave at creation, so
+// later checks re-derive with create_program_address and that bump: one hash instead of
+// a bump search, and no other bump can name a second account for the same seeds.
+fn stored_pda(seeds: &[&[u8]], program_id: &Pubkey) -> Result<Pubkey, ProgramError> {
+    create_program_address(seeds, program_id).map_err(|_| ProgramError::InvalidSeeds)
+}
+
+// an epoch's distributor is the program-owned account at its PDA, never any other account
+fn check_distributor(program_id: &Pubkey, vault_pda: &Pubkey, distributor: &AccountInfo, epoch: u64) -> ProgramResult {
+    if distributor.owner != program_id { return Err(ProgramError::IncorrectProgramId) }
+    let bump = [load::<BoostDistributor>(program_id, distributor)?.bump];
+    let expected = stored_pda(&[SEED_BOOST, vault_pda.as_ref(), &epoch.to_le_bytes(), &bump], program_id)?;
+    if *distributor.key != expected { return Err(ProgramError::InvalidSeeds) }
+    Ok(())
+}
+
+// the escrow OpenBoostEscrow created for `distributor`
+fn boost_escrow_address(program_id: &Pubkey, distributor: &Pubkey, bd: &BoostDistributor) -> Result<Pubkey, ProgramError> {
+    stored_pda(&[SEED_ESCROW, distributor.as_ref(), &[bd.escrow_bump]], program_id)
+}
+
+fn derive_boost_escrow(program_id: &Pubkey, distributor: &Pubkey) -> (Pubkey, u8) {
+    find_program_address(&[SEED_ESCROW, distributor.as_ref()], program_id)
+}
+
+fn derive_claims_bitmap(program_id: &Pubkey, vault_pda: &Pubkey, epoch: u64) -> (Pubkey, u8) {
+    find_program_address(&[SEED_CLAIMS, vault_pda.as_ref(), &epoch.to_le_bytes()], program_id)
+}
+
+fn derive_registry(program_id: &Pubkey) -> (Pubkey, u8) {
+    find_program_address(&[SEED_REGISTRY], program_id)
+}
+
+fn derive_registry_page(program_id: &Pubkey, page: u32) -> (Pubkey, u8) {
+    find_program_address(&[SEED_REGISTRY, &page.to_le_bytes()], program_id)
+}
+
+fn derive_claims_page(program_id: &Pubkey, vault_pda: &Pubkey, epoch: u64, page: u16) -> (Pubkey, u8) {
+    if page == 0 { return derive_claims_bitmap(program_id, vault_pda, epoch) }
+    find_program_address(&[SEED_CLAIMS, vault_pda.as_ref(), &epoch.to_le_bytes(), &page.to_le_bytes()], program_id)
+}
+
+// the bitmap passed for a claim must be the page that holds its index
+fn check_claims_page(program_id: &Pubkey, vault_pda: &Pubkey, bd: &BoostDistributor, bitmap: &AccountInfo, index: u32) -> ProgramResult {
+    if bitmap.owner != program_id { return Err(ProgramError::IncorrectProgramId) }
+    let (page, _) = bd.claim_page(index);
+    let bump = [load::<ClaimBitmap256>(program_id, bitmap)?.bump];
+    let epoch_le = bd.epoch.to_le_bytes();
+    let page_le = page.to_le_bytes();
+    let expected = if page == 0 {
+        stored_pda(&[SEED_CLAIMS, vault_pda.as_ref(), &epoch_le, &bump], program_id)?
+    } else {
+        stored_pda(&[SEED_CLAIMS, vault_pda.as_ref(), &epoch_le, &page_le, &bump], program_id)?
+    };
+    if *bitmap.key != expected { return Err(ProgramError::InvalidSeeds) }
+    Ok(())
+}
+
+fn derive_epoch_report(program_id: &Pubkey, vault_pda: &Pubkey, epoch: u64) -> (Pubkey, u8) {
+    find_program_address(&[SEED_REPORT, vault_pda.as_ref(), &epoch.to_le_bytes()], program_id)
+}
+
+fn derive_user_prefs(program_id: &Pubkey, vault_state: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    find_program_address(&[SEED_PREFS, vault_state.as_ref(), user.as_ref()], program_id)
+}
+
+fn derive_claim_escrow(program_id: &Pubkey, vault_state: &Pubkey, claimer: &Pubkey) -> (Pubkey, u8) {
+    find_program_address(&[SEED_CLAIM_ESCROW, vault_state.as_ref(), claimer.as_ref()], program_id)
+}
+
+fn derive_user_position(program_id: &Pubkey, vault_state: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    find_program_address(&[SEED_POSITION, vault_state.as_ref(), user.as_ref()], program_id)
+}
+
+fn keccak256(chunks: &[&[u8]], out: &mut [u8; 32]) {
+    let mut total_len = 0usize;
+    for c in chunks { total_len += c.len(); }
+    let mut tmp = [0u8; 512]; // small stack buffer for short inputs
+    let mut written = 0;
+    for c in chunks {
+        let n = c.len();
+        tmp[written..written+n].copy_from_slice(c);
+        written += n;
+    }
+    unsafe { syscalls::sol_keccak256(&tmp[..written], out) };
+}
+
+fn verify_merkle(root: &[u8; 32], leaf: &[u8; 32], proof: &[[u8;32]]) -> bool {
+    let mut cur = *leaf;
+    let mut buf = [0u8; 32];
+    for node in proof {
+        // sort orderless: hash(min||max)
+        let (a, b) = if cur <= *node { (&cur, node) } else { (node, &cur) };
+        keccak256(&[a, b], &mut buf);
+        cur = buf;
+    }
+    &cur == root
+}
+// Multiproof check for several leaves of one tree (same pair hashing as verify_merkle).
+// `leaves` are in tree order; each flag says whether the next hash's second input is
+// taken from the leaves/computed hashes (true) or from `proof` (false).
+fn verify_multiproof(root: &[u8; 32], leaves: &[[u8; 32]], proof: &[u8], flags: &[u8]) -> bool {
+    let proof_len = proof.len() / 32;
+    let total = flags.len();
+    if leaves.len() + proof_len != total + 1 || total > MAX_MULTI_HASHES { return false }
+    let node = |i: usize| -> [u8; 32] { proof[i * 32..i * 32 + 32].try_into().unwrap() };
+
+    let mut hashes = [[0u8; 32]; MAX_MULTI_HASHES];
+    let (mut leaf_pos, mut hash_pos, mut proof_pos) = (0usize, 0usize, 0usize);
+    for i in 0..total {
+        let a = if leaf_pos < leaves.len() { leaf_pos += 1; leaves[leaf_pos - 1] } else { hash_pos += 1; hashes[hash_pos - 1] };
+        let b = if flags[i] != 0 {
+            if leaf_pos < leaves.len() { leaf_pos += 1; leaves[leaf_pos - 1] } else { hash_pos += 1; hashes[hash_pos - 1] }
+        } else {
+            if proof_pos >= proof_len { return false }
+            proof_pos += 1;
+            node(proof_pos - 1)
+        };
+        let (lo, hi) = if a <= b { (&a, &b) } else { (&b, &a) };
+        keccak256(&[lo, hi], &mut hashes[i]);
+    }
+    let computed = if total > 0 {
+        if proof_pos != proof_len { return false }
+        hashes[total - 1]
+    } else if let Some(l) = leaves.first() {
+        *l
+    } else {
+        return false
+    };
+    &computed == root
+}
+
+
+// SPL Token CPI data builders (checked variants)
+fn data_transfer_checked(amount: u64, decimals: u8) -> [u8; 1+8+1] {
+    let mut d = [0u8; 10];
+    d[0] = IX_TRANSFER_CHECKED;
+    d[1..9].copy_from_slice(&amount.to_le_bytes());
+    d[9] = decimals;
+    d
+}
+fn data_mint_to_checked(amount: u64, decimals: u8) -> [u8; 1+8+1] {
+    let mut d = [0u8; 10];
+    d[0] = IX_MINT_TO_CHECKED;
+    d[1..9].copy_from_slice(&amount.to_le_bytes());
+    d[9] = decimals;
+    d
+}
+fn data_burn_checked(amount: u64, decimals: u8) -> [u8; 1+8+1] {
+    let mut d = [0u8; 10];
+    d[0] = IX_BURN_CHECKED;
+    d[1..9].copy_from_slice(&amount.to_le_bytes());
+    d[9] = decimals;
+    d
+}
+
+fn data_initialize_mint2(decimals: u8, mint_authority: &Pubkey) -> [u8; 1+1+32+1] {
+    let mut d = [0u8; 35];
+    d[0] = IX_INITIALIZE_MINT2;
+    d[1] = decimals;
+    d[2..34].copy_from_slice(mint_authority.as_ref());
+    d[34] = 0; // no freeze authority
+    d
+}
+
+// the account is its own owner, so only the program (via its PDA seeds) can move funds
+fn data_initialize_account3(owner: &Pubkey) -> [u8; 1+32] {
+    let mut d = [0u8; 33];
+    d[0] = IX_INITIALIZE_ACCOUNT3;
+    d[1..33].copy_from_slice(owner.as_ref());
+    d
+}
+
+fn data_system_transfer(lamports: u64) -> [u8; 4+8] {
+    let mut d = [0u8; 12];
+    d[0..4].copy_from_slice(&SYS_IX_TRANSFER.to_le_bytes());
+    d[4..12].copy_from_slice(&lamports.to_le_bytes());
+    d
+}
+
+fn data_create_account(lamports: u64, space: u64, owner: &Pubkey) -> [u8; 4+8+8+32] {
+    let mut d = [0u8; 52];
+    d[0..4].copy_from_slice(&SYS_IX_CREATE_ACCOUNT.to_le_bytes());
+    d[4..12].copy_from_slice(&lamports.to_le_bytes());
+    d[12..20].copy_from_slice(&space.to_le_bytes());
+    d[20..52].copy_from_slice(owner.as_ref());
+    d
+}
+
+// SPL Token CPI account metas, in the token program's account order
+fn metas_transfer_checked<'a>(src: &'a Pubkey, mint: &'a Pubkey, dst: &'a Pubkey, owner: &'a Pubkey) -> [AccountMeta<'a>; 4] {
+    [
+        AccountMeta::writable(src),
+        AccountMeta::readonly(mint),
+        AccountMeta::writable(dst),
+        AccountMeta::readonly_signer(owner),
+    ]
+}
+fn metas_mint_to_checked<'a>(mint: &'a Pubkey, dst: &'a Pubkey, authority: &'a Pubkey) -> [AccountMeta<'a>; 3] {
+    [
+        AccountMeta::writable(mint),
+        AccountMeta::writable(dst),
+        AccountMeta::readonly_signer(authority),
+    ]
+}
+fn metas_burn_checked<'a>(account: &'a Pubkey, mint: &'a Pubkey, owner: &'a Pubkey) -> [AccountMeta<'a>; 3] {
+    [
+        AccountMeta::writable(account),
+        AccountMeta::writable(mint),
+        AccountMeta::readonly_signer(owner),
+    ]
+}
+
+// Stack-only CPI instruction: metas and data are fixed-size arrays owned by the caller.
+fn ix<'a, const N: usize, const D: usize>(
+    program: &'a AccountInfo,
+    data: &'a [u8; D],
+    metas: &'a [AccountMeta<'a>; N],
+) -> Instruction<'a, 'a, 'a, 'a> {
+    Instruction { program_id: program.key, accounts: metas, data }
+}
+
+// Vaults initialized before seed_admin existed have it zeroed; their admin never changed
+// (AcceptAdmin backfills it first), so the current admin is still the seed.
+fn vault_seed_admin(st: &VaultState) -> &Pubkey {
+    if st.seed_admin == Pubkey::default() { &st.admin } else { &st.seed_admin }
+}
+
+fn vault_seeds<'a>(vault_state: &'a VaultState, bump: &'a [u8; 1]) -> [Seed<'a>; 5] {
+    // signer seeds = [SEED_VAULT, usdc, seed_admin, vault_id_le, [bump]]; the stored u64 is
+    // its own little-endian seed bytes on SBF
+    [
+        Seed::from(SEED_VAULT),
+        Seed::from(vault_state.usdc_mint.as_ref()),
+        Seed::from(vault_seed_admin(vault_state).as_ref()),
+        Seed::from(bytemuck::bytes_of(&vault_state.vault_id)),
+        Seed::from(bump.as_ref()),
+    ]
+}
+
+fn auth_seeds<'a>(vault_state: &'a VaultState, bump: &'a [u8; 1]) -> [Seed<'a>; 3] {
+    // signer seeds = [SEED_AUTH, vault_pda, [bump]]
+    [
+        Seed::from(SEED_AUTH),
+        Seed::from(vault_state.vault_pda.as_ref()),
+        Seed::from(bump.as_ref()),
+    ]
+}
+
+// ---------- Accounts ----------
+// Each op's account list is parsed into a named struct in one place: count,
+// signer and writable flags are checked here, so handlers never index by position.
+
+fn check_writable(ai: &AccountInfo) -> ProgramResult {
+    if !ai.is_writable { return Err(ProgramError::InvalidAccountData) }
+    Ok(())
+}
+
+struct InitAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    admin: &'a AccountInfo,
+    operator: &'a AccountInfo,
+    usdc_mint: &'a AccountInfo,
+    share_mint: &'a AccountInfo,
+    vault_pda: &'a AccountInfo,
+    system_program: &'a AccountInfo,
+    token_program: &'a AccountInfo,
+    registry: &'a AccountInfo,
+    registry_page: &'a AccountInfo, // the page the new vault lands on: registry.count / REGISTRY_PAGE_VAULTS
+    vault_usdc_ata: &'a AccountInfo,
+    boost_usdc: &'a AccountInfo,
+    ata_program: &'a AccountInfo,
+}
+
+impl<'a> InitAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [
+            vault_state, admin, operator, usdc_mint, share_mint, vault_pda, system_program, token_program, registry, registry_page,
+            vault_usdc_ata, boost_usdc, ata_program, ..
+        ] = accs
+        else { return Err(ProgramError::NotEnoughAccountKeys) };
+        for ai in [vault_state, admin, share_mint, registry, registry_page, vault_usdc_ata, boost_usdc] { check_writable(ai)?; }
+        check_signer(admin)?;
+        if *ata_program.key != ASSOCIATED_TOKEN_PROGRAM_ID { return Err(ProgramError::IncorrectProgramId) }
+        Ok(Self {
+            vault_state, admin, operator, usdc_mint, share_mint, vault_pda, system_program, token_program, registry, registry_page,
+            vault_usdc_ata, boost_usdc, ata_program,
+        })
+    }
+}
+
+struct DepositAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    mint_authority: &'a AccountInfo,
+    user: &'a AccountInfo,
+    user_usdc_ata: &'a AccountInfo,
+    vault_usdc_ata: &'a AccountInfo,
+    share_mint: &'a AccountInfo,
+    user_share_ata: &'a AccountInfo,
+    token_program: &'a AccountInfo,
+    usdc_mint: &'a AccountInfo,
+    position: Option<PositionAccounts<'a>>,
+}
+
+// required while a per-user deposit cap is set; the user pays for the first one
+struct PositionAccounts<'a> {
+    position: &'a AccountInfo,
+    system_program: &'a AccountInfo,
+}
+
+impl<'a> DepositAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [vault_state, mint_authority, user, user_usdc_ata, vault_usdc_ata, share_mint, user_share_ata, token_program, usdc_mint, rest @ ..] = accs
+        else { return Err(ProgramError::NotEnoughAccountKeys) };
+        check_signer(user)?;
+        for ai in [vault_state, user_usdc_ata, vault_usdc_ata, share_mint, user_share_ata] { check_writable(ai)?; }
+        let position = match rest {
+            [position, system_program, ..] => {
+                check_writable(position)?;
+                Some(PositionAccounts { position, system_program })
+            }
+            _ => None,
+        };
+        Ok(Self { vault_state, mint_authority, user, user_usdc_ata, vault_usdc_ata, share_mint, user_share_ata, token_program, usdc_mint, position })
+    }
+}
+
+struct WithdrawAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    vault_pda: &'a AccountInfo,
+    user: &'a AccountInfo,
+    user_usdc_ata: &'a AccountInfo,
+    vault_usdc_ata: &'a AccountInfo,
+    share_mint: &'a AccountInfo,
+    user_share_ata: &'a AccountInfo,
+    token_program: &'a AccountInfo,
+    usdc_mint: &'a AccountInfo,
+}
+
+impl<'a> WithdrawAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [vault_state, vault_pda, user, user_usdc_ata, vault_usdc_ata, share_mint, user_share_ata, token_program, usdc_mint, ..] = accs
+        else { return Err(ProgramError::NotEnoughAccountKeys) };
+        check_signer(user)?;
+        for ai in [vault_state, user_usdc_ata, vault_usdc_ata, share_mint, user_share_ata] { check_writable(ai)?; }
+        Ok(Self { vault_state, vault_pda, user, user_usdc_ata, vault_usdc_ata, share_mint, user_share_ata, token_program, usdc_mint })
+    }
+}
+
+struct DonateAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    vault_pda: &'a AccountInfo,
+    donor: &'a AccountInfo,
+    donor_usdc_ata: &'a AccountInfo,
+    vault_usdc_ata: &'a AccountInfo,
+    boost_usdc_ata: &'a AccountInfo,
+    token_program: &'a AccountInfo,
+    usdc_mint: &'a AccountInfo,
+    boost_distributor: &'a AccountInfo,
+    // optional trailing accounts (oracle, next distributor), consumed in order by the op
+    extra: &'a [AccountInfo],
+}
+
+impl<'a> DonateAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [vault_state, vault_pda, donor, donor_usdc_ata, vault_usdc_ata, boost_usdc_ata, token_program, usdc_mint, boost_distributor, extra @ ..] = accs
+        else { return Err(ProgramError::NotEnoughAccountKeys) };
+        check_signer(donor)?;
+        for ai in [vault_state, donor_usdc_ata, vault_usdc_ata, boost_usdc_ata] { check_writable(ai)?; }
+        Ok(Self { vault_state, vault_pda, donor, donor_usdc_ata, vault_usdc_ata, boost_usdc_ata, token_program, usdc_mint, boost_distributor, extra })
+    }
+}
+
+// the operator only has to sign; its key is not stored
+struct CommitWeightsAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    operator: &'a AccountInfo,
+    boost_distributor: &'a AccountInfo,
+}
+
+impl<'a> CommitWeightsAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [vault_state, operator, boost_distributor, ..] = accs
+        else { return Err(ProgramError::NotEnoughAccountKeys) };
+        check_signer(operator)?;
+        check_writable(boost_distributor)?;
+        Ok(Self { vault_state, operator, boost_distributor })
+    }
+}
+
+struct ReportAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    operator: &'a AccountInfo,
+    boost_distributor: &'a AccountInfo,
+    report: &'a AccountInfo,
+    system_program: &'a AccountInfo,
+}
+
+impl<'a> ReportAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [vault_state, operator, boost_distributor, report, system_program, ..] = accs
+        else { return Err(ProgramError::NotEnoughAccountKeys) };
+        check_signer(operator)?;
+        for ai in [operator, report] { check_writable(ai)?; }
+        Ok(Self { vault_state, operator, boost_distributor, report, system_program })
+    }
+}
+
+struct PostRootAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    operator: &'a AccountInfo,
+    boost_distributor: &'a AccountInfo,
+    co_signers: &'a [AccountInfo], // extra root signers when a threshold is configured
+}
+
+impl<'a> PostRootAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [vault_state, operator, boost_distributor, co_signers @ ..] = accs
+        else { return Err(ProgramError::NotEnoughAccountKeys) };
+        check_signer(operator)?;
+        check_writable(boost_distributor)?;
+        Ok(Self { vault_state, operator, boost_distributor, co_signers })
+    }
+}
+
+struct ClawbackAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    vault_pda: &'a AccountInfo,
+    operator: &'a AccountInfo,
+    boost_distributor: &'a AccountInfo,
+    boost_usdc_ata: &'a AccountInfo,
+    vault_usdc_ata: &'a AccountInfo,
+    token_program: &'a AccountInfo,
+    usdc_mint: &'a AccountInfo,
+}
+
+impl<'a> ClawbackAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [vault_state, vault_pda, operator, boost_distributor, boost_usdc_ata, vault_usdc_ata, token_program, usdc_mint, ..] = accs
+        else { return Err(ProgramError::NotEnoughAccountKeys) };
+        check_signer(operator)?;
+        for ai in [vault_state, boost_distributor, boost_usdc_ata, vault_usdc_ata] { check_writable(ai)?; }
+        Ok(Self { vault_state, vault_pda, operator, boost_distributor, boost_usdc_ata, vault_usdc_ata, token_program, usdc_mint })
+    }
+}
+
+struct ClaimAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    vault_pda: &'a AccountInfo,
+    claimer: &'a AccountInfo,
+    boost_distributor: &'a AccountInfo,
+    claims_bitmap: &'a AccountInfo,
+    boost_usdc_ata: &'a AccountInfo,
+    claimer_usdc_ata: &'a AccountInfo,
+    token_program: &'a AccountInfo,
+    usdc_mint: &'a AccountInfo,
+    claim_escrow: Option<&'a AccountInfo>, // only needed when claimer_usdc_ata is frozen or closed
+    create_ata: Option<CreateAtaAccounts<'a>>, // only needed when claimer_usdc_ata does not exist yet
+}
+
+// payer(ws), system_program, associated_token_program: lets Claim create the claimer's
+// USDC ATA itself; the payer may be the claimer or a relayer
+struct CreateAtaAccounts<'a> {
+    payer: &'a AccountInfo,
+    system_program: &'a AccountInfo,
+    ata_program: &'a AccountInfo,
+}
+
+impl<'a> ClaimAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [vault_state, vault_pda, claimer, boost_distributor, claims_bitmap, boost_usdc_ata, claimer_usdc_ata, token_program, usdc_mint, rest @ ..] = accs
+        else { return Err(ProgramError::NotEnoughAccountKeys) };
+        check_signer(claimer)?;
+        for ai in [claims_bitmap, boost_usdc_ata, claimer_usdc_ata] { check_writable(ai)?; }
+        let claim_escrow = rest.first();
+        if let Some(ai) = claim_escrow { check_writable(ai)?; }
+        let create_ata = match rest.get(1..4) {
+            Some([payer, system_program, ata_program]) => {
+                check_signer(payer)?;
+                check_writable(payer)?;
+                if *ata_program.key != ASSOCIATED_TOKEN_PROGRAM_ID { return Err(ProgramError::IncorrectProgramId) }
+                Some(CreateAtaAccounts { payer, system_program, ata_program })
+            }
+            _ => None,
+        };
+        Ok(Self {
+            vault_state, vault_pda, claimer, boost_distributor, claims_bitmap, boost_usdc_ata, claimer_usdc_ata, token_program, usdc_mint,
+            claim_escrow, create_ata,
+        })
+    }
+}
+
+// read-only; nobody signs
+struct QuoteClaimAccounts<'a> {
+    boost_distributor: &'a AccountInfo,
+    claims_bitmap: &'a AccountInfo,
+    claimer: &'a AccountInfo,
+}
+
+impl<'a> QuoteClaimAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [boost_distributor, claims_bitmap, claimer, ..] = accs
+        else { return Err(ProgramError::NotEnoughAccountKeys) };
+        Ok(Self { boost_distributor, claims_bitmap, claimer })
+    }
+}
+
+// the sender only has to sign (and pay fees)
+struct ClaimManyAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    vault_pda: &'a AccountInfo,
+    boost_distributor: &'a AccountInfo,
+    claims_bitmap: &'a AccountInfo,
+    boost_usdc_ata: &'a AccountInfo,
+    token_program: &'a AccountInfo,
+    usdc_mint: &'a AccountInfo,
+    destinations: &'a [AccountInfo], // one claimer-owned USDC account per leaf, in leaf order
+}
+
+impl<'a> ClaimManyAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [vault_state, vault_pda, sender, boost_distributor, claims_bitmap, boost_usdc_ata, token_program, usdc_mint, destinations @ ..] = accs
+        else { return Err(ProgramError::NotEnoughAccountKeys) };
+        check_signer(sender)?;
+        check_writable(claims_bitmap)?;
+        check_writable(boost_usdc_ata)?;
+        for ai in destinations { check_writable(ai)?; }
+        Ok(Self { vault_state, vault_pda, boost_distributor, claims_bitmap, boost_usdc_ata, token_program, usdc_mint, destinations })
+    }
+}
+
+struct ClaimEpochsAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    vault_pda: &'a AccountInfo,
+    claimer: &'a AccountInfo,
+    claimer_usdc_ata: &'a AccountInfo,
+    token_program: &'a AccountInfo,
+    usdc_mint: &'a AccountInfo,
+    entries: &'a [AccountInfo], // per claimed epoch: distributor, bitmap page, boost account; then the optional claim escrow
+}
+
+impl<'a> ClaimEpochsAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [vault_state, vault_pda, claimer, claimer_usdc_ata, token_program, usdc_mint, entries @ ..] = accs
+        else { return Err(ProgramError::NotEnoughAccountKeys) };
+        check_signer(claimer)?;
+        check_writable(claimer_usdc_ata)?;
+        for ai in entries { check_writable(ai)?; }
+        Ok(Self { vault_state, vault_pda, claimer, claimer_usdc_ata, token_program, usdc_mint, entries })
+    }
+}
+
+// the payer funds the escrow's rent; anyone may open one for any claimer
+struct OpenClaimEscrowAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    payer: &'a AccountInfo,
+    claimer: &'a AccountInfo,
+    claim_escrow: &'a AccountInfo,
+    usdc_mint: &'a AccountInfo,
+    system_program: &'a AccountInfo,
+    token_program: &'a AccountInfo,
+}
+
+impl<'a> OpenClaimEscrowAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [vault_state, payer, claimer, claim_escrow, usdc_mint, system_program, token_program, ..] = accs
+        else { return Err(ProgramError::NotEnoughAccountKeys) };
+        check_signer(payer)?;
+        for ai in [payer, claim_escrow] { check_writable(ai)?; }
+        Ok(Self { vault_state, payer, claimer, claim_escrow, usdc_mint, system_program, token_program })
+    }
+}
+
+struct RedeemEscrowAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    claimer: &'a AccountInfo,
+    claim_escrow: &'a AccountInfo,
+    claimer_usdc_ata: &'a AccountInfo,
+    token_program: &'a AccountInfo,
+    usdc_mint: &'a AccountInfo,
+}
+
+impl<'a> RedeemEscrowAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [vault_state, claimer, claim_escrow, claimer_usdc_ata, token_program, usdc_mint, ..] = accs
+        else { return Err(ProgramError::NotEnoughAccountKeys) };
+        check_signer(claimer)?;
+        for ai in [claim_escrow, claimer_usdc_ata] { check_writable(ai)?; }
+        Ok(Self { vault_state, claimer, claim_escrow, claimer_usdc_ata, token_program, usdc_mint })
+    }
+}
+
+struct AutoClaimAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    user: &'a AccountInfo,
+    prefs: &'a AccountInfo,
+    system_program: &'a AccountInfo,
+}
+
+impl<'a> AutoClaimAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [vault_state, user, prefs, system_program, ..] = accs
+        else { return Err(ProgramError::NotEnoughAccountKeys) };
+        check_signer(user)?;
+        check_writable(user)?;
+        check_writable(prefs)?;
+        Ok(Self { vault_state, user, prefs, system_program })
+    }
+}
+
+// only needed when the claimer compounds
+struct CompoundAccounts<'a> {
+    mint_authority: &'a AccountInfo,
+    share_mint: &'a AccountInfo,
+    claimer_share_ata: &'a AccountInfo,
+}
+
+struct ClaimCompoundAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    vault_pda: &'a AccountInfo,
+    claimer: &'a AccountInfo,
+    boost_distributor: &'a AccountInfo,
+    claims_bitmap: &'a AccountInfo,
+    boost_usdc_ata: &'a AccountInfo,
+    vault_usdc_ata: &'a AccountInfo,
+    token_program: &'a AccountInfo,
+    usdc_mint: &'a AccountInfo,
+    compound: CompoundAccounts<'a>,
+}
+
+impl<'a> ClaimCompoundAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [vault_state, vault_pda, claimer, boost_distributor, claims_bitmap, boost_usdc_ata, vault_usdc_ata, token_program, usdc_mint, mint_authority, share_mint, claimer_share_ata, ..] = accs
+        else { return Err(ProgramError::NotEnoughAccountKeys) };
+        check_signer(claimer)?;
+        for ai in [vault_state, claims_bitmap, boost_usdc_ata, vault_usdc_ata, share_mint, claimer_share_ata] { check_writable(ai)?; }
+        Ok(Self {
+            vault_state, vault_pda, claimer, boost_distributor, claims_bitmap, boost_usdc_ata, vault_usdc_ata, token_program, usdc_mint,
+            compound: CompoundAccounts { mint_authority, share_mint, claimer_share_ata },
+        })
+    }
+}
+
+struct CrankClaimAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    vault_pda: &'a AccountInfo,
+    operator: &'a AccountInfo,
+    claimer: &'a AccountInfo,
+    prefs: &'a AccountInfo,
+    boost_distributor: &'a AccountInfo,
+    claims_bitmap: &'a AccountInfo,
+    boost_usdc_ata: &'a AccountInfo,
+    destination: &'a AccountInfo, // claimer's USDC ATA, or vault_usdc_ata when compounding
+    token_program: &'a AccountInfo,
+    usdc_mint: &'a AccountInfo,
+    compound: Option<CompoundAccounts<'a>>,
+}
+
+impl<'a> CrankClaimAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [vault_state, vault_pda, operator, claimer, prefs, boost_distributor, claims_bitmap, boost_usdc_ata, destination, token_program, usdc_mint, rest @ ..] = accs
+        else { return Err(ProgramError::NotEnoughAccountKeys) };
+        check_signer(operator)?;
+        for ai in [vault_state, claims_bitmap, boost_usdc_ata, destination] { check_writable(ai)?; }
+        let compound = match rest {
+            [mint_authority, share_mint, claimer_share_ata, ..] => {
+                check_writable(share_mint)?;
+                check_writable(claimer_share_ata)?;
+                Some(CompoundAccounts { mint_authority, share_mint, claimer_share_ata })
+            }
+            _ => None,
+        };
+        Ok(Self {
+            vault_state, vault_pda, operator, claimer, prefs, boost_distributor, claims_bitmap, boost_usdc_ata, destination,
+            token_program, usdc_mint, compound,
+        })
+    }
+}
+
+struct ReconcileRentAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    admin: &'a AccountInfo,
+    target: &'a AccountInfo,
+    system_program: &'a AccountInfo,
+}
+
+impl<'a> ReconcileRentAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [vault_state, admin, target, system_program, ..] = accs
+        else { return Err(ProgramError::NotEnoughAccountKeys) };
+        check_signer(admin)?;
+        check_writable(admin)?;
+        check_writable(target)?;
+        Ok(Self { vault_state, admin, target, system_program })
+    }
+}
+
+// permissionless: anyone may refresh the recorded upgrade authority
+struct RecordUpgradeAuthorityAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    program_data: &'a AccountInfo,
+}
+
+impl<'a> RecordUpgradeAuthorityAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [vault_state, program_data, ..] = accs else { return Err(ProgramError::NotEnoughAccountKeys) };
+        check_writable(vault_state)?;
+        Ok(Self { vault_state, program_data })
+    }
+}
+
+struct OpenBoostEscrowAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    operator: &'a AccountInfo,
+    boost_distributor: &'a AccountInfo,
+    escrow: &'a AccountInfo,
+    usdc_mint: &'a AccountInfo,
+    system_program: &'a AccountInfo,
+    token_program: &'a AccountInfo,
+}
+
+impl<'a> OpenBoostEscrowAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [vault_state, operator, boost_distributor, escrow, usdc_mint, system_program, token_program, ..] = accs
+        else { return Err(ProgramError::NotEnoughAccountKeys) };
+        check_signer(operator)?;
+        for ai in [operator, boost_distributor, escrow] { check_writable(ai)?; }
+        Ok(Self { vault_state, operator, boost_distributor, escrow, usdc_mint, system_program, token_program })
+    }
+}
+
+struct OpenDistributorAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    payer: &'a AccountInfo,
+    boost_distributor: &'a AccountInfo,
+    system_program: &'a AccountInfo,
+}
+
+impl<'a> OpenDistributorAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [vault_state, payer, boost_distributor, system_program, ..] = accs
+        else { return Err(ProgramError::NotEnoughAccountKeys) };
+        check_signer(payer)?;
+        for ai in [payer, boost_distributor] { check_writable(ai)?; }
+        Ok(Self { vault_state, payer, boost_distributor, system_program })
+    }
+}
+
+struct OpenClaimPageAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    payer: &'a AccountInfo,
+    boost_distributor: &'a AccountInfo,
+    page: &'a AccountInfo,
+    system_program: &'a AccountInfo,
+}
+
+impl<'a> OpenClaimPageAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [vault_state, payer, boost_distributor, page, system_program, ..] = accs
+        else { return Err(ProgramError::NotEnoughAccountKeys) };
+        check_signer(payer)?;
+        for ai in [payer, page] { check_writable(ai)?; }
+        Ok(Self { vault_state, payer, boost_distributor, page, system_program })
+    }
+}
+
+// only needed when the settle caller wants the tip
+struct SettleTipAccounts<'a> {
+    vault_pda: &'a AccountInfo,
+    vault_usdc_ata: &'a AccountInfo,
+    caller_usdc_ata: &'a AccountInfo,
+    token_program: &'a AccountInfo,
+    usdc_mint: &'a AccountInfo,
+}
+
+// permissionless; no signer beyond the fee payer
+struct SettleAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    tip: Option<SettleTipAccounts<'a>>,
+}
+
+impl<'a> SettleAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [vault_state, rest @ ..] = accs else { return Err(ProgramError::NotEnoughAccountKeys) };
+        check_writable(vault_state)?;
+        let tip = match rest {
+            [vault_pda, vault_usdc_ata, caller_usdc_ata, token_program, usdc_mint, ..] => {
+                check_writable(vault_usdc_ata)?;
+                check_writable(caller_usdc_ata)?;
+                Some(SettleTipAccounts { vault_pda, vault_usdc_ata, caller_usdc_ata, token_program, usdc_mint })
+            }
+            _ => None,
+        };
+        Ok(Self { vault_state, tip })
+    }
+}
+
+struct AcceptAdminAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    new_admin: &'a AccountInfo,
+}
+
+impl<'a> AcceptAdminAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [vault_state, new_admin, ..] = accs else { return Err(ProgramError::NotEnoughAccountKeys) };
+        check_writable(vault_state)?;
+        check_signer(new_admin)?;
+        Ok(Self { vault_state, new_admin })
+    }
+}
+
+// Withdraw's accounts, then the successor program and its Deposit accounts
+struct MigrateAccounts<'a> {
+    successor_program: &'a AccountInfo,
+    successor_state: &'a AccountInfo,
+    successor_mint_authority: &'a AccountInfo,
+    successor_usdc_ata: &'a AccountInfo,
+    successor_share_mint: &'a AccountInfo,
+    user_successor_share_ata: &'a AccountInfo,
+}
+
+impl<'a> MigrateAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [successor_program, successor_state, successor_mint_authority, successor_usdc_ata, successor_share_mint, user_successor_share_ata, ..] =
+            accs.get(9..).unwrap_or(&[])
+        else { return Err(ProgramError::NotEnoughAccountKeys) };
+        for ai in [successor_state, successor_usdc_ata, successor_share_mint, user_successor_share_ata] { check_writable(ai)?; }
+        Ok(Self { successor_program, successor_state, successor_mint_authority, successor_usdc_ata, successor_share_mint, user_successor_share_ata })
+    }
+}
+
+// admin-only config ops
+struct AdminAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    admin: &'a AccountInfo,
+}
+
+impl<'a> AdminAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [vault_state, admin, ..] = accs else { return Err(ProgramError::NotEnoughAccountKeys) };
+        check_writable(vault_state)?;
+        check_signer(admin)?;
+        Ok(Self { vault_state, admin })
+    }
+}
+
+// read-only; nobody signs
+struct ShareSupplyAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    share_mint: &'a AccountInfo,
+}
+
+impl<'a> ShareSupplyAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [vault_state, share_mint, ..] = accs else { return Err(ProgramError::NotEnoughAccountKeys) };
+        Ok(Self { vault_state, share_mint })
+    }
+}
+
+struct RepairShareSupplyAccounts<'a> {
+    vault_state: &'a AccountInfo,
+    admin: &'a AccountInfo,
+    share_mint: &'a AccountInfo,
+}
+
+impl<'a> RepairShareSupplyAccounts<'a> {
+    fn parse(accs: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let [vault_state, admin, share_mint, ..] = accs else { return Err(ProgramError::NotEnoughAccountKeys) };
+        check_writable(vault_state)?;
+        check_signer(admin)?;
+        Ok(Self { vault_state, admin, share_mint })
+    }
+}
+
+// ---------- Entry ----------
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    ix_data: &[u8],
+) -> ProgramResult {
+    let (&tag, data) = ix_data.split_first().ok_or(ProgramError::InvalidInstructionData)?;
+    if tag & OP_MEASURE == 0 { return dispatch(program_id, accounts, tag, data) }
+    let start = unsafe { syscalls::sol_remaining_compute_units() };
+    dispatch(program_id, accounts, tag & !OP_MEASURE, data)?;
+    let used = start.saturating_sub(unsafe { syscalls::sol_remaining_compute_units() });
+    cpi::set_return_data(&used.to_le_bytes());
+    Ok(())
+}
+
+fn dispatch(program_id: &Pubkey, accounts: &[AccountInfo], op: u8, data: &[u8]) -> ProgramResult {
+    match op {
+        OP_INIT    => op_init(program_id, accounts, data),
+        OP_DEPOSIT => op_deposit(program_id, accounts, data),
+        OP_WITHDRAW=> op_withdraw(program_id, accounts, data),
+        OP_DONATE  => op_donate(program_id, accounts, data),
+        OP_POSTROOT=> op_post_root(program_id, accounts, data),
+        OP_CLAIM   => op_claim(program_id, accounts, data),
+        OP_SET_YIELD_ORACLE => op_set_yield_oracle(program_id, accounts, data),
+        OP_SET_DONORS => op_set_donors(program_id, accounts, data),
+        OP_SET_BURN_DUST => op_set_burn_dust(program_id, accounts, data),
+        OP_INIT_FROM_TEMPLATE => op_init_from_template(program_id, accounts, data),
+        OP_SET_AUTO_CLAIM => op_set_auto_claim(program_id, accounts, data),
+        OP_CRANK_CLAIM => op_crank_claim(program_id, accounts, data),
+        OP_CLAIM_MANY => op_claim_many(program_id, accounts, data),
+        OP_COMMIT_WEIGHTS => op_commit_weights(program_id, accounts, data),
+        OP_RECONCILE_RENT => op_reconcile_rent(program_id, accounts, data),
+        OP_SET_ROOT_SIGNERS => op_set_root_signers(program_id, accounts, data),
+        OP_SET_PPS_CEILING => op_set_pps_ceiling(program_id, accounts, data),
+        OP_RECORD_UPGRADE_AUTHORITY => op_record_upgrade_authority(program_id, accounts),
+        OP_SETTLE => op_settle(program_id, accounts),
+        OP_PROPOSE_ADMIN => op_propose_admin(program_id, accounts, data),
+        OP_ACCEPT_ADMIN => op_accept_admin(program_id, accounts),
+        OP_SET_PAUSE_FLAGS => op_set_pause_flags(program_id, accounts, data),
+        OP_OPEN_BOOST_ESCROW => op_open_boost_escrow(program_id, accounts, data),
+        OP_SET_GUARDIAN => op_set_guardian(program_id, accounts, data),
+        OP_ENTER_EMERGENCY => op_enter_emergency(program_id, accounts),
+        OP_QUOTE_CLAIM => op_quote_claim(program_id, accounts, data),
+        OP_SET_DEPOSIT_CAP => op_set_deposit_cap(program_id, accounts, data),
+        OP_SET_USER_DEPOSIT_CAP => op_set_user_deposit_cap(program_id, accounts, data),
+        OP_CHECK_SHARE_SUPPLY => op_check_share_supply(program_id, accounts),
+        OP_REPAIR_SHARE_SUPPLY => op_repair_share_supply(program_id, accounts),
+        OP_WITHDRAW_EXACT => op_withdraw_exact(program_id, accounts, data),
+        OP_WITHDRAW_ALL => op_withdraw_all(program_id, accounts, data),
+        OP_DEPOSIT_FOR => op_deposit_for(program_id, accounts, data),
+        OP_CLAIM_COMPOUND => op_claim_compound(program_id, accounts, data),
+        OP_OPEN_CLAIM_PAGE => op_open_claim_page(program_id, accounts, data),
+        OP_CLAIM_EPOCHS => op_claim_epochs(program_id, accounts, data),
+        OP_OPEN_DISTRIBUTOR => op_open_distributor(program_id, accounts, data),
+        OP_CLAWBACK => op_clawback(program_id, accounts, data),
+        OP_FINALIZE_EPOCH => op_finalize_epoch(program_id, accounts, data),
+        OP_OPEN_CLAIM_ESCROW => op_open_claim_escrow(program_id, accounts),
+        OP_REDEEM_ESCROW => op_redeem_escrow(program_id, accounts),
+        OP_SET_SUCCESSOR => op_set_successor(program_id, accounts, data),
+        OP_MIGRATE_POSITION => op_migrate_position(program_id, accounts, data),
+        OP_SET_LIVENESS_WINDOW => op_set_liveness_window(program_id, accounts, data),
+        OP_TRIP_WATCHDOG => op_trip_watchdog(program_id, accounts),
+        OP_RECORD_ARCHIVE => op_record_archive(program_id, accounts, data),
+        OP_REPORT => op_report(program_id, accounts, data),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+// data: [share_decimals:u8?, vault_id:u64?]   (defaults: the usdc_mint's decimals, vault 0)
+fn op_init(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let InitAccounts {
+        vault_state, admin, operator, usdc_mint, share_mint, vault_pda, system_program, token_program, registry, registry_page,
+        vault_usdc_ata, boost_usdc, ata_program,
+    } = InitAccounts::parse(accs)?;
+    check_token_program(token_program, usdc_mint)?;
+    check_mint_supported(usdc_mint)?;
+    let usdc_decimals = mint_decimals(usdc_mint)?;
+    let share_decimals = data.first().copied().unwrap_or(usdc_decimals);
+    let vault_id = data.get(1..9).map_or(0, |v| u64::from_le_bytes(v.try_into().unwrap()));
+    let pps = math::initial_pps(share_decimals, usdc_decimals).ok_or(ProgramError::InvalidInstructionData)?;
+    let (expected_state, state_bump) = derive_vault_state(program_id, usdc_mint.key, admin.key, vault_id);
+    if *vault_state.key != expected_state { return Err(ProgramError::InvalidSeeds) }
+
+    // create vault_state at its PDA; an existing one falls through to init_account, which
+    // refuses it once tagged
+    if vault_state.data_is_empty() {
+        let lamports = Rent::get()?.minimum_balance(VaultState::LEN);
+        let metas = [AccountMeta::writable_signer(admin.key), AccountMeta::writable_signer(vault_state.key)];
+        let data = data_create_account(lamports, VaultState::LEN as u64, program_id);
+        let bump = [state_bump];
+        let id_le = vault_id.to_le_bytes();
+        let seeds = [
+            Seed::from(SEED_STATE), Seed::from(usdc_mint.key.as_ref()), Seed::from(admin.key.as_ref()), Seed::from(id_le.as_ref()),
+            Seed::from(bump.as_ref()),
+        ];
+        cpi::invoke_signed(&ix(system_program, &data, &metas), &[admin,vault_state], &[Signer::from(&seeds)])?;
+    }
+    let st = init_account::<VaultState>(vault_state)?;
+    let clock = Clock::get()?;
+    let (expected_pda, bump) = derive_vault_pda(program_id, usdc_mint.key, admin.key, vault_id);
+    if *vault_pda.key != expected_pda { return Err(ProgramError::InvalidSeeds) }
+    let (auth_pda, auth_bump) = derive_auth_pda(program_id, vault_pda.key);
+    let (expected_mint, share_mint_bump) = derive_share_mint(program_id, vault_state.key);
+    if *share_mint.key != expected_mint { return Err(ProgramError::InvalidSeeds) }
+
+    // create the share mint at its PDA: one canonical, zero-supply mint per vault
+    {
+        let lamports = Rent::get()?.minimum_balance(MINT_LEN);
+        let metas = [AccountMeta::writable_signer(admin.key), AccountMeta::writable_signer(share_mint.key)];
+        let data = data_create_account(lamports, MINT_LEN as u64, token_program.key);
+        let bump = [share_mint_bump];
+        let seeds = [Seed::from(SEED_SHARES), Seed::from(vault_state.key.as_ref()), Seed::from(bump.as_ref())];
+        cpi::invoke_signed(&ix(system_program, &data, &metas), &[admin,share_mint], &[Signer::from(&seeds)])?;
+    }
+    {
+        let metas = [AccountMeta::writable(share_mint.key)];
+        let data = data_initialize_mint2(share_decimals, &auth_pda); // auth_pda is mint authority
+        cpi::invoke(&ix(token_program, &data, &metas), &[share_mint])?;
+    }
+    // read back what the token program wrote: a vault whose mint anyone else could mint,
+    // or that starts with shares outstanding, would be inflatable out of band
+    let mint = unpack_mint(&share_mint.try_borrow_data()?)?;
+    if mint.mint_authority != Some(auth_pda) { return Err(VaultError::ShareMintAuthorityMismatch.into()) }
+    if mint.supply != 0 { return Err(VaultError::ShareSupplyDrift.into()) }
+
+    // the vault's two USDC accounts, both owned by vault_pda: its ATA (the ATA program
+    // refuses any other address) and the shared boost account at its PDA. Every later op
+    // is checked against the addresses stored below.
+    create_ata_idempotent(CreateAtaAccounts { payer: admin, system_program, ata_program }, vault_usdc_ata, vault_pda, usdc_mint, token_program)?;
+    let (expected_boost, boost_bump) = derive_boost_usdc(program_id, vault_pda.key);
+    if *boost_usdc.key != expected_boost { return Err(ProgramError::InvalidSeeds) }
+    {
+        let len = token_account_len(usdc_mint)?;
+        let lamports = Rent::get()?.minimum_balance(len);
+        let metas = [AccountMeta::writable_signer(admin.key), AccountMeta::writable_signer(boost_usdc.key)];
+        let data = data_create_account(lamports, len as u64, token_program.key);
+        let bump = [boost_bump];
+        let seeds = [Seed::from(SEED_BOOST_USDC), Seed::from(vault_pda.key.as_ref()), Seed::from(bump.as_ref())];
+        cpi::invoke_signed(&ix(system_program, &data, &metas), &[admin,boost_usdc], &[Signer::from(&seeds)])?;
+    }
+    {
+        let metas = [AccountMeta::writable(boost_usdc.key), AccountMeta::readonly(usdc_mint.key)];
+        let data = data_initialize_account3(vault_pda.key);
+        cpi::invoke(&ix(token_program, &data, &metas), &[boost_usdc,usdc_mint])?;
+    }
+
+    *st = VaultState {
+        discriminator: VaultState::DISCRIMINATOR,
+        admin: *admin.key,
+        operator: *operator.key,
+        usdc_mint: *usdc_mint.key,
+        share_mint: *share_mint.key,
+        vault_pda: *vault_pda.key,
+        auth_pda,
+        vault_bump: bump,
+        share_mint_bump,
+        auth_bump,
+        share_decimals,
+        state_bump,
+        _pad1: [0; 3],
+        total_shares: 0,
+        pps, // one whole share = one whole USDC
+        pps_ceiling: pps.saturating_mul(DEFAULT_PPS_CEILING_MULT),
+        seed_admin: *admin.key,
+        vault_id,
+        buffered_base: 0,
+        last_settle_slot: clock.slot,
+        last_activity_slot: clock.slot,
+        vault_usdc: *vault_usdc_ata.key,
+        boost_usdc: *boost_usdc.key,
+        ..VaultState::zeroed() // optional config (oracle, donor allow-list) starts disabled
+    };
+
+    register_vault(program_id, admin, registry, registry_page, system_program, vault_state.key)?;
+
+    vlog!("vault initialized, share decimals={}", share_decimals as u64);
+    emit(EVT_INIT, &clock, &InitEvent {
+        vault_state: *vault_state.key,
+        admin: st.admin,
+        operator: st.operator,
+        usdc_mint: st.usdc_mint,
+        share_mint: st.share_mint,
+    });
+    Ok(())
+}
+
+// Appends `vault` to the registry, creating the header on the program's first init and
+// each page on the init that fills its first entry, paid by the admin.
+fn register_vault(
+    program_id: &Pubkey, payer: &AccountInfo, registry: &AccountInfo, registry_page: &AccountInfo, system_program: &AccountInfo,
+    vault: &Pubkey,
+) -> ProgramResult {
+    let (expected, bump) = derive_registry(program_id);
+    if *registry.key != expected { return Err(ProgramError::InvalidSeeds) }
+    if registry.data_is_empty() {
+        let lamports = Rent::get()?.minimum_balance(VaultRegistry::LEN);
+        let metas = [AccountMeta::writable_signer(payer.key), AccountMeta::writable_signer(registry.key)];
+        let data = data_create_account(lamports, VaultRegistry::LEN as u64, program_id);
+        let bump = [bump];
+        let seeds = [Seed::from(SEED_REGISTRY), Seed::from(bump.as_ref())];
+        cpi::invoke_signed(&ix(system_program, &data, &metas), &[payer,registry], &[Signer::from(&seeds)])?;
+        init_account::<VaultRegistry>(registry)?.bump = bump[0];
+    }
+    let reg = load_mut::<VaultRegistry>(program_id, registry)?;
+
+    let page = u32::try_from(reg.count / REGISTRY_PAGE_VAULTS as u64).map_err(|_| VaultError::MathOverflow)?;
+    let (expected, bump) = derive_registry_page(program_id, page);
+    if *registry_page.key != expected { return Err(ProgramError::InvalidSeeds) }
+    if registry_page.data_is_empty() {
+        let lamports = Rent::get()?.minimum_balance(RegistryPage::LEN);
+        let metas = [AccountMeta::writable_signer(payer.key), AccountMeta::writable_signer(registry_page.key)];
+        let data = data_create_account(lamports, RegistryPage::LEN as u64, program_id);
+        let page_le = page.to_le_bytes();
+        let bump = [bump];
+        let seeds = [Seed::from(SEED_REGISTRY), Seed::from(page_le.as_ref()), Seed::from(bump.as_ref())];
+        cpi::invoke_signed(&ix(system_program, &data, &metas), &[payer,registry_page], &[Signer::from(&seeds)])?;
+        let p = init_account::<RegistryPage>(registry_page)?;
+        p.page = page;
+        p.bump = bump[0];
+    }
+    let p = load_mut::<RegistryPage>(program_id, registry_page)?;
+    let slot = (reg.count % REGISTRY_PAGE_VAULTS as u64) as usize;
+    p.vaults[slot] = *vault;
+    p.len = slot as u16 + 1;
+    reg.count += 1;
+    Ok(())
+}
+
+// data: same as op_init; config is copied from the template
+fn op_init_from_template(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    // accounts: op_init's list, then
+    // 13 []  template vault_state (same admin)
+    let InitAccounts { vault_state, admin, .. } = InitAccounts::parse(accs)?;
+    let template = accs.get(13).ok_or(ProgramError::NotEnoughAccountKeys)?;
+    if template.owner != program_id { return Err(ProgramError::IncorrectProgramId) }
+    if template.key == vault_state.key { return Err(ProgramError::InvalidArgument) }
+    let tpl = *load_vault(program_id, template)?;
+    require_admin(&tpl, admin.key)?;
+
+    op_init(program_id, accs, data)?;
+
+    let st = load_vault_mut(program_id, vault_state)?;
+    st.yield_oracle = tpl.yield_oracle;
+    st.oracle_tolerance_bps = tpl.oracle_tolerance_bps;
+    st.donor_allowlist = tpl.donor_allowlist;
+    st.donor_allowlist_len = tpl.donor_allowlist_len;
+    st.restrict_donors = tpl.restrict_donors;
+    st.burn_dust = tpl.burn_dust;
+    st.liveness_slots = tpl.liveness_slots;
+    st.deposit_cap = tpl.deposit_cap;
+    st.user_deposit_cap = tpl.user_deposit_cap;
+    st.root_signers = tpl.root_signers;
+    st.root_signers_len = tpl.root_signers_len;
+    st.root_threshold = tpl.root_threshold;
+    Ok(())
+}
+
+// data: [amount_usdc:u64, usdc_decimals:u8, min_shares_out:u64?]   (min defaults to 0)
+fn op_deposit(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let amount = u64::from_le_bytes(data.get(0..8).ok_or(ProgramError::InvalidInstructionData)?.try_into().unwrap());
+    let usdc_decimals = *data.get(8).ok_or(ProgramError::InvalidInstructionData)?;
+    let min_shares_out = data.get(9..17).map_or(0, |m| u64::from_le_bytes(m.try_into().unwrap()));
+    deposit(program_id, accs, amount, usdc_decimals, min_shares_out, None)
+}
+
+// Deposit paid by `user` whose shares go to `recipient` (a smart wallet, an employee).
+// Same accounts as Deposit, with user_share_ata owned by the recipient rather than
+// the payer; the payer's UserPosition still counts the USDC.
+// data: [amount_usdc:u64, usdc_decimals:u8, recipient:[u8;32], min_shares_out:u64?]
+fn op_deposit_for(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    if data.len() < 41 { return Err(ProgramError::InvalidInstructionData) }
+    let amount = u64::from_le_bytes(data[..8].try_into().unwrap());
+    let recipient: Pubkey = data[9..41].try_into().unwrap();
+    let min_shares_out = data.get(41..49).map_or(0, |m| u64::from_le_bytes(m.try_into().unwrap()));
+    deposit(program_id, accs, amount, data[8], min_shares_out, Some(recipient))
+}
+
+fn deposit(
+    program_id: &Pubkey, accs: &[AccountInfo], amount: u64, usdc_decimals: u8, min_shares_out: u64, recipient: Option<Pubkey>,
+) -> ProgramResult {
+    let DepositAccounts {
+        vault_state, mint_authority, user, user_usdc_ata, vault_usdc_ata, share_mint, user_share_ata, token_program, usdc_mint, position,
+    } = DepositAccounts::parse(accs)?;
+    // the payer names the recipient, so shares can't land in an account it didn't mean
+    if let Some(r) = recipient {
+        if token_account_owner(user_share_ata)? != r { return Err(ProgramError::InvalidArgument) }
+    }
+
+    let st = load_vault_mut(program_id, vault_state)?;
+    check_not_paused(st, PAUSE_DEPOSIT)?;
+    let clock = Clock::get()?;
+    if *mint_authority.key != st.auth_pda { return Err(ProgramError::InvalidSeeds) }
+    check_vault_mint(st, usdc_mint, token_program)?;
+    check_mint_supported(usdc_mint)?;
+    check_share_mint(st, share_mint, token_program)?;
+    check_vault_usdc(st, vault_usdc_ata, token_program)?;
+    check_token_account(user_share_ata, token_program, &st.share_mint, &recipient.unwrap_or(*user.key))?;
+    if position.is_none() && st.user_deposit_cap != 0 { return Err(ProgramError::NotEnoughAccountKeys) }
+    settle(st, &clock)?;
+
+    // 1) pull USDC from user -> vault ATA. Plain invoke forwards the user's signature, so a
+    // PDA user whose program signed this instruction via invoke_signed works unchanged.
+    // A Token-2022 transfer-fee mint withholds its fee from what lands in the vault, so
+    // shares are priced on the vault's balance change rather than on `amount`.
+    let before = token_account_amount(vault_usdc_ata)?;
+    {
+        let metas = metas_transfer_checked(user_usdc_ata.key, usdc_mint.key, vault_usdc_ata.key, user.key);
+        let data = data_transfer_checked(amount, usdc_decimals);
+        cpi::invoke(&ix(token_program, &data, &metas), &[user_usdc_ata,usdc_mint,vault_usdc_ata,user])?;
+    }
+    let received = token_account_amount(vault_usdc_ata)?.checked_sub(before).ok_or(VaultError::MathOverflow)?;
+
+    // 2) mint vault shares to user (or the recipient)
+    let shares = shares_to_issue(st, received, clock.slot)?;
+    let mint_amt: u64 = shares.try_into().map_err(|_| VaultError::MathOverflow)?;
+    // pps can move between the quote and landing; never mint fewer shares than the user accepted
+    if mint_amt < min_shares_out { return Err(VaultError::SlippageExceeded.into()) }
+    st.total_shares = st.total_shares.checked_add(shares).ok_or(VaultError::MathOverflow)?;
+    check_deposit_cap(st)?;
+    if let Some(p) = position {
+        let p = load_position(program_id, vault_state, user, p)?;
+        p.record_deposit(received, mint_amt, &clock)?;
+        check_user_deposit_cap(st, p)?;
+    }
+    {
+        let metas = metas_mint_to_checked(share_mint.key, user_share_ata.key, mint_authority.key);
+        let data = data_mint_to_checked(mint_amt, st.share_decimals);
+        let bump = [st.auth_bump];
+        let seeds = auth_seeds(st, &bump);
+        cpi::invoke_signed(&ix(token_program, &data, &metas), &[share_mint,user_share_ata,mint_authority], &[Signer::from(&seeds)])?;
+    }
+
+    vlog!("deposit amount={} received={} shares={} pps={}", amount, received, mint_amt, logfmt::ray(st.pps).as_str());
+    emit(EVT_DEPOSIT, &clock, &DepositEvent {
+        user: *user.key, amount: received, shares: mint_amt, pps: st.pps, recipient: recipient.unwrap_or(*user.key),
+    });
+    Ok(())
+}
+
+// A payout to a USDC account the user does not own needs its owner named in `recipient`,
+// e.g. a custodian routing redemptions to a settlement wallet.
+// data: [shares:u64, usdc_decimals:u8, recipient:[u8;32]?]   (recipient defaults to user)
+fn op_withdraw(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let shares = u64::from_le_bytes(data.get(0..8).ok_or(ProgramError::InvalidInstructionData)?.try_into().unwrap());
+    let usdc_decimals = *data.get(8).ok_or(ProgramError::InvalidInstructionData)?;
+    let recipient = data.get(9..41).map(|r| Pubkey::try_from(r).unwrap());
+    withdraw(program_id, accs, usdc_decimals, WithdrawSize::Shares(shares), recipient)
+}
+
+// Exact-out: pays exactly `amount` USDC and burns the shares that cost, rounded up so
+// the rounding never favors the withdrawer. max_shares_in guards against pps moving
+// between quote and landing.
+// data: [amount_usdc:u64, usdc_decimals:u8, max_shares_in:u64?]   (max defaults to unlimited)
+fn op_withdraw_exact(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let amount = u64::from_le_bytes(data.get(0..8).ok_or(ProgramError::InvalidInstructionData)?.try_into().unwrap());
+    let usdc_decimals = *data.get(8).ok_or(ProgramError::InvalidInstructionData)?;
+    let max_shares_in = data.get(9..17).map_or(u64::MAX, |m| u64::from_le_bytes(m.try_into().unwrap()));
+    withdraw(program_id, accs, usdc_decimals, WithdrawSize::ExactAssets { amount, max_shares_in }, None)
+}
+
+// Burns the user's whole share balance, read from user_share_ata when the instruction
+// runs, so nothing is left behind by a balance that changed after the client looked.
+// data: [usdc_decimals:u8]
+fn op_withdraw_all(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let usdc_decimals = *data.first().ok_or(ProgramError::InvalidInstructionData)?;
+    let WithdrawAccounts { user_share_ata, .. } = WithdrawAccounts::parse(accs)?;
+    let shares = token_account_amount(user_share_ata)?;
+    if shares == 0 { return Err(ProgramError::InsufficientFunds) }
+    withdraw(program_id, accs, usdc_decimals, WithdrawSize::Shares(shares), None)
+}
+
+enum WithdrawSize {
+    Shares(u64),
+    ExactAssets { amount: u64, max_shares_in: u64 },
+}
+
+// (shares burned, USDC paid) at `pps`
+fn withdraw_amounts(size: WithdrawSize, pps: u128) -> Result<(u64, u64), ProgramError> {
+    match size {
+        WithdrawSize::Shares(shares) => {
+            let out = math::assets_for_shares(shares as u128, pps).ok_or(VaultError::MathOverflow)?;
+            Ok((shares, out.try_into().map_err(|_| VaultError::MathOverflow)?))
+        }
+        WithdrawSize::ExactAssets { amount, max_shares_in } => {
+            let shares = math::shares_for_assets_up(amount, pps).ok_or(VaultError::MathOverflow)?;
+            let shares: u64 = shares.try_into().map_err(|_| VaultError::MathOverflow)?;
+            if shares > max_shares_in { return Err(VaultError::SlippageExceeded.into()) }
+            Ok((shares, amount))
+        }
+    }
+}
+
+// `recipient` is the owner the user authorized for user_usdc_ata; None means the user
+fn withdraw(program_id: &Pubkey, accs: &[AccountInfo], usdc_decimals: u8, size: WithdrawSize, recipient: Option<Pubkey>) -> ProgramResult {
+    let WithdrawAccounts {
+        vault_state, vault_pda, user, user_usdc_ata, vault_usdc_ata, share_mint, user_share_ata, token_program, usdc_mint,
+    } = WithdrawAccounts::parse(accs)?;
+    let recipient = recipient.unwrap_or(*user.key);
+    if token_account_owner(user_usdc_ata)? != recipient { return Err(ProgramError::InvalidArgument) }
+
+    let st = load_vault_mut(program_id, vault_state)?;
+    check_not_paused(st, PAUSE_WITHDRAW)?;
+    let clock = Clock::get()?;
+    if *vault_pda.key != st.vault_pda { return Err(ProgramError::InvalidSeeds) }
+    check_vault_mint(st, usdc_mint, token_program)?;
+    check_share_mint(st, share_mint, token_program)?;
+    check_vault_usdc(st, vault_usdc_ata, token_program)?;
+    check_token_account(user_share_ata, token_program, &st.share_mint, user.key)?;
+    settle(st, &clock)?;
+
+    // USDC owed for shares * pps; supply is updated before any CPI
+    let (shares_burn, amount_out) = withdraw_amounts(size, st.pps)?;
+    check_bootstrap_lock(st, shares_burn, clock.slot)?;
+    let shares_u128 = shares_burn as u128;
+    st.total_shares = st.total_shares.checked_sub(shares_u128).ok_or(VaultError::MathOverflow)?;
+
+    // burn shares from user
+    {
+        let metas = metas_burn_checked(user_share_ata.key, share_mint.key, user.key); // owner is user (wallet or caller's PDA)
+        let data = data_burn_checked(shares_burn, st.share_decimals);
+        cpi::invoke(&ix(token_program, &data, &metas), &[user_share_ata,share_mint,user])?;
+    }
+
+    // transfer vault USDC -> user USDC using vault signer
+    {
+        let metas = metas_transfer_checked(vault_usdc_ata.key, usdc_mint.key, user_usdc_ata.key, vault_pda.key); // owner vault_pda
+        let data = data_transfer_checked(amount_out, usdc_decimals);
+        let bump = [st.vault_bump];
+        let seeds = vault_seeds(st, &bump);
+        cpi::invoke_signed(&ix(token_program, &data, &metas), &[vault_usdc_ata,usdc_mint,user_usdc_ata,vault_pda], &[Signer::from(&seeds)])?;
+    }
+
+    vlog!("withdraw shares={} amount_out={} pps={}", shares_burn, amount_out, logfmt::ray(st.pps).as_str());
+    emit(EVT_WITHDRAW, &clock, &WithdrawEvent { user: *user.key, shares: shares_burn, amount: amount_out, pps: st.pps, recipient });
+
+    // leftover shares that can no longer redeem a single base unit are burned too
+    if st.burn_dust != 0 {
+        let dust = token_account_amount(user_share_ata)?;
+        if dust > 0 && math::assets_for_shares(dust as u128, st.pps) == Some(0) {
+            st.total_shares = st.total_shares.checked_sub(dust as u128).ok_or(VaultError::MathOverflow)?;
+            let metas = metas_burn_checked(user_share_ata.key, share_mint.key, user.key);
+            let data = data_burn_checked(dust, st.share_decimals);
+            cpi::invoke(&ix(token_program, &data, &metas), &[user_share_ata,share_mint,user])?;
+            emit(EVT_DUST_BURNED, &clock, &DustBurnedEvent { user: *user.key, shares: dust });
+        }
+    }
+    Ok(())
+}
+
+// data: [amount_usdc:u64, epoch:u64, boost_bps:u16, usdc_decimals:u8, category:u8?, memo:[u8;32]?]
+//       (category defaults to strategy yield, memo to zeros)
+fn op_donate(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let DonateAccounts {
+        vault_state, vault_pda, donor, donor_usdc_ata, vault_usdc_ata, boost_usdc_ata, token_program, usdc_mint, boost_distributor, extra,
+    } = DonateAccounts::parse(accs)?;
+    if data.len() < 19 { return Err(ProgramError::InvalidInstructionData) }
+    let amount = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let epoch  = u64::from_le_bytes(data[8..16].try_into().unwrap());
+    let boost_bps = u16::from_le_bytes(data[16..18].try_into().unwrap());
+    let usdc_decimals = data[18];
+    if boost_bps as u64 > BPS_DENOM { return Err(VaultError::InvalidBoostBps.into()) }
+    let category = data.get(19).copied().unwrap_or(DONATION_STRATEGY_YIELD);
+    if !matches!(category, DONATION_STRATEGY_YIELD | DONATION_GRANT | DONATION_REBATE | DONATION_PENALTY) {
+        return Err(ProgramError::InvalidInstructionData)
+    }
+    let mut memo = [0u8; 32];
+    if let Some(m) = data.get(20..52) { memo.copy_from_slice(m); }
+
+    let st = load_vault_mut(program_id, vault_state)?;
+    check_not_paused(st, PAUSE_DONATE)?;
+    let clock = Clock::get()?;
+    if *vault_pda.key != st.vault_pda { return Err(ProgramError::InvalidSeeds) }
+    check_vault_mint(st, usdc_mint, token_program)?;
+    check_vault_usdc(st, vault_usdc_ata, token_program)?;
+    if st.restrict_donors != 0 && !is_allowed_donor(st, donor.key) {
+        return Err(VaultError::DonorNotAllowed.into())
+    }
+
+    let mut extra = extra.iter();
+
+    // off-chain yield must be attested before it can move pps
+    if st.yield_oracle != Pubkey::default() {
+        let oracle = extra.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+        check_yield_attestation(st, oracle, epoch, amount)?;
+    }
+
+    // operator_ata -> vault_ata
+    {
+        let metas = metas_transfer_checked(donor_usdc_ata.key, usdc_mint.key, vault_usdc_ata.key, donor.key);
+        let data = data_transfer_checked(amount, usdc_decimals);
+        cpi::invoke(&ix(token_program, &data, &metas), &[donor_usdc_ata,usdc_mint,vault_usdc_ata,donor])?;
+    }
+
+    let (boost, base) = math::split_donation(amount, boost_bps).ok_or(VaultError::InvalidBoostBps)?;
+
+    // into pps, or buffered while there are no shares
+    accrue_base(st, base, &clock)?;
+
+    // Optional: update boost distributor (if provided)
+    let mut credited_epoch = epoch;
+    let mut escrowed = false;
+    if boost_distributor.owner == program_id && boost_distributor.data_len() >= BoostDistributor::LEN {
+        check_distributor(program_id, &st.vault_pda, boost_distributor, epoch)?;
+        let mut bd = load_mut::<BoostDistributor>(program_id, boost_distributor)?;
+        let mut credited_ai = boost_distributor;
+        if bd.epoch == 0 { bd.epoch = epoch; }
+        if bd.epoch != epoch { return Err(ProgramError::InvalidArgument) }
+        if bd.funding_closed() {
+            // late donation: credit the next epoch rather than the settled one
+            let next_ai = extra.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+            credited_epoch = epoch.checked_add(1).ok_or(VaultError::MathOverflow)?;
+            check_distributor(program_id, &st.vault_pda, next_ai, credited_epoch)?;
+            bd = load_mut::<BoostDistributor>(program_id, next_ai)?;
+            credited_ai = next_ai;
+            if bd.epoch == 0 { bd.epoch = credited_epoch; }
+            if bd.epoch != credited_epoch { return Err(ProgramError::InvalidArgument) }
+            if bd.funding_closed() { return Err(VaultError::RolloverDistributorClosed.into()) }
+        }
+        // an escrowed epoch's boost must land in its own escrow
+        if bd.escrow != 0 && *boost_usdc_ata.key != boost_escrow_address(program_id, credited_ai.key, bd)? {
+            return Err(VaultError::BoostEscrowMismatch.into())
+        }
+        escrowed = bd.escrow != 0;
+        bd.boost_total = bd.boost_total.checked_add(boost).ok_or(VaultError::MathOverflow)?;
+    }
+
+    // vault_ata -> boost_ata (boost part) signed by vault
+    if boost > 0 {
+        if !escrowed { check_boost_usdc(st, boost_usdc_ata)?; }
+        let metas = metas_transfer_checked(vault_usdc_ata.key, usdc_mint.key, boost_usdc_ata.key, vault_pda.key);
+        let data = data_transfer_checked(boost, usdc_decimals);
+        let bump = [st.vault_bump];
+        let seeds = vault_seeds(st, &bump);
+        cpi::invoke_signed(&ix(token_program, &data, &metas), &[vault_usdc_ata,usdc_mint,boost_usdc_ata,vault_pda], &[Signer::from(&seeds)])?;
+    }
+
+    vlog!(
+        "donate epoch={} amount={} boost={} base={} pps={} total_shares={}",
+        credited_epoch, amount, boost, base, logfmt::ray(st.pps).as_str(), logfmt::dec(st.total_shares).as_str()
+    );
+    emit(EVT_DONATE, &clock, &DonateEvent {
+        donor: *donor.key, memo, epoch: credited_epoch, amount, boost, base, pps: st.pps, category, _pad: [0; 15],
+    });
+    Ok(())
+}
+
+// Creates the epoch's distributor at [b"boost", vault_pda, epoch_le], tagged with the
+// epoch. Permissionless like OpenClaimPage: the payer only pays rent. Every op that takes
+// a distributor checks it is this account, so it must exist before the epoch's first
+// donation that credits boost, and before CommitWeights.
+// data: [epoch:u64]
+fn op_open_distributor(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let OpenDistributorAccounts { vault_state, payer, boost_distributor, system_program } = OpenDistributorAccounts::parse(accs)?;
+    if data.len() < 8 { return Err(ProgramError::InvalidInstructionData) }
+    let epoch = u64::from_le_bytes(data[0..8].try_into().unwrap());
+
+    let st = load_vault(program_id, vault_state)?;
+    let (expected, bump) = derive_boost_distributor(program_id, &st.vault_pda, epoch);
+    if *boost_distributor.key != expected { return Err(ProgramError::InvalidSeeds) }
+
+    let lamports = Rent::get()?.minimum_balance(BoostDistributor::LEN);
+    let metas = [AccountMeta::writable_signer(payer.key), AccountMeta::writable_signer(boost_distributor.key)];
+    let create = data_create_account(lamports, BoostDistributor::LEN as u64, program_id);
+    let epoch_le = epoch.to_le_bytes();
+    let bump = [bump];
+    let seeds = [Seed::from(SEED_BOOST), Seed::from(st.vault_pda.as_ref()), Seed::from(epoch_le.as_ref()), Seed::from(bump.as_ref())];
+    cpi::invoke_signed(&ix(system_program, &create, &metas), &[payer,boost_distributor], &[Signer::from(&seeds)])?;
+
+    let bd = init_account::<BoostDistributor>(boost_distributor)?;
+    bd.epoch = epoch;
+    bd.bump = bump[0];
+    vlog!("distributor opened epoch={}", epoch);
+    Ok(())
+}
+
+// Creates one claim bitmap page of a posted epoch. Permissionless: the payer only pays
+// rent, and the page can only ever be the zeroed account at its PDA. Page 0 may be opened
+// for any epoch, so distributors posted before paging keep a bitmap.
+// data: [epoch:u64, page:u16]
+fn op_open_claim_page(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let OpenClaimPageAccounts { vault_state, payer, boost_distributor, page, system_program } = OpenClaimPageAccounts::parse(accs)?;
+    if data.len() < 10 { return Err(ProgramError::InvalidInstructionData) }
+    let epoch = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let page_no = u16::from_le_bytes(data[8..10].try_into().unwrap());
+
+    let st = load_vault(program_id, vault_state)?;
+    check_distributor(program_id, &st.vault_pda, boost_distributor, epoch)?;
+    let bd = load::<BoostDistributor>(program_id, boost_distributor)?;
+    if page_no != 0 && page_no >= bd.bitmap_page_count { return Err(VaultError::ClaimIndexOutOfRange.into()) }
+    let (expected, bump) = derive_claims_page(program_id, &st.vault_pda, epoch, page_no);
+    if *page.key != expected { return Err(ProgramError::InvalidSeeds) }
+
+    let lamports = Rent::get()?.minimum_balance(ClaimBitmap256::LEN);
+    let metas = [AccountMeta::writable_signer(payer.key), AccountMeta::writable_signer(page.key)];
+    let data = data_create_account(lamports, ClaimBitmap256::LEN as u64, program_id);
+    let epoch_le = epoch.to_le_bytes();
+    let page_le = page_no.to_le_bytes();
+    let bump = [bump];
+    if page_no == 0 {
+        let seeds = [Seed::from(SEED_CLAIMS), Seed::from(st.vault_pda.as_ref()), Seed::from(epoch_le.as_ref()), Seed::from(bump.as_ref())];
+        cpi::invoke_signed(&ix(system_program, &data, &metas), &[payer,page], &[Signer::from(&seeds)])?;
+    } else {
+        let seeds = [
+            Seed::from(SEED_CLAIMS), Seed::from(st.vault_pda.as_ref()), Seed::from(epoch_le.as_ref()), Seed::from(page_le.as_ref()),
+            Seed::from(bump.as_ref()),
+        ];
+        cpi::invoke_signed(&ix(system_program, &data, &metas), &[payer,page], &[Signer::from(&seeds)])?;
+    }
+    init_account::<ClaimBitmap256>(page)?.bump = bump[0];
+    vlog!("claim page opened epoch={} page={}", epoch, page_no as u64);
+    Ok(())
+}
+
+// Creates the epoch's boost escrow so an over-claim bug in one epoch can never reach
+// another epoch's funds. Must happen before the epoch receives any boost, so each
+// epoch's boost lives in exactly one account.
+// data: [epoch:u64]
+fn op_open_boost_escrow(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let OpenBoostEscrowAccounts {
+        vault_state, operator, boost_distributor, escrow, usdc_mint, system_program, token_program,
+    } = OpenBoostEscrowAccounts::parse(accs)?;
+    let epoch = u64::from_le_bytes(data.get(0..8).ok_or(ProgramError::InvalidInstructionData)?.try_into().unwrap());
+
+    let st = load_vault(program_id, vault_state)?;
+    require_operator(st, operator.key)?;
+    check_vault_mint(st, usdc_mint, token_program)?;
+    check_distributor(program_id, &st.vault_pda, boost_distributor, epoch)?;
+    let bd = load_mut::<BoostDistributor>(program_id, boost_distributor)?;
+    if bd.epoch == 0 { bd.epoch = epoch; }
+    if bd.epoch != epoch { return Err(ProgramError::InvalidArgument) }
+    if bd.escrow != 0 || bd.boost_total != 0 { return Err(VaultError::BoostEscrowMismatch.into()) }
+    let (expected, bump) = derive_boost_escrow(program_id, boost_distributor.key);
+    if *escrow.key != expected { return Err(ProgramError::InvalidSeeds) }
+
+    {
+        let len = token_account_len(usdc_mint)?;
+        let lamports = Rent::get()?.minimum_balance(len);
+        let metas = [AccountMeta::writable_signer(operator.key), AccountMeta::writable_signer(escrow.key)];
+        let data = data_create_account(lamports, len as u64, token_program.key);
+        let bump = [bump];
+        let seeds = [Seed::from(SEED_ESCROW), Seed::from(boost_distributor.key.as_ref()), Seed::from(bump.as_ref())];
+        cpi::invoke_signed(&ix(system_program, &data, &metas), &[operator,escrow], &[Signer::from(&seeds)])?;
+    }
+    {
+        let metas = [AccountMeta::writable(escrow.key), AccountMeta::readonly(usdc_mint.key)];
+        let data = data_initialize_account3(escrow.key);
+        cpi::invoke(&ix(token_program, &data, &metas), &[escrow,usdc_mint])?;
+    }
+    bd.escrow = 1;
+    bd.escrow_bump = bump;
+    vlog!("boost escrow opened epoch={}", epoch);
+    Ok(())
+}
+
+// keccak(b"commit", epoch, total_weight, root, leaf_count): what the operator pre-announces
+fn weight_commitment(epoch: u64, total_weight: u128, root: &[u8; 32], leaf_count: u32) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    keccak256(&[b"commit", &epoch.to_le_bytes(), &total_weight.to_le_bytes(), root, &leaf_count.to_le_bytes()], &mut out);
+    out
+}
+
+// Pins the epoch's weight tree before the epoch ends, so depositors can check that the
+// root posted later is the one announced up front.
+// data: [epoch:u64, commitment: [u8;32]]
+fn op_commit_weights(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let CommitWeightsAccounts { vault_state, operator, boost_distributor } = CommitWeightsAccounts::parse(accs)?;
+    if data.len() < 40 { return Err(ProgramError::InvalidInstructionData) }
+    let epoch = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let mut commitment = [0u8; 32];
+    commitment.copy_from_slice(&data[8..40]);
+    if commitment == [0u8; 32] { return Err(ProgramError::InvalidInstructionData) }
+
+    let st = load_vault(program_id, vault_state)?;
+    require_operator(st, operator.key)?;
+    let clock = Clock::get()?;
+    if clock.epoch > epoch { return Err(VaultError::EpochClosed.into()) }
+
+    check_distributor(program_id, &st.vault_pda, boost_distributor, epoch)?;
+    let bd = load_mut::<BoostDistributor>(program_id, boost_distributor)?;
+    if bd.epoch == 0 { bd.epoch = epoch; }
+    if bd.epoch != epoch { return Err(ProgramError::InvalidArgument) }
+    if bd.funding_closed() { return Err(VaultError::EpochClosed.into()) }
+    bd.weight_commitment = commitment;
+    emit(EVT_WEIGHTS_COMMITTED, &clock, &WeightsCommittedEvent { epoch, commitment });
+    Ok(())
+}
+
+// Number of configured root signers that signed this instruction.
+fn root_signatures(st: &VaultState, operator: &AccountInfo, co_signers: &[AccountInfo]) -> usize {
+    st.root_signers[..st.root_signers_len as usize]
+        .iter()
+        .filter(|k| core::iter::once(operator).chain(co_signers).any(|ai| ai.is_signer && ai.key == *k))
+        .count()
+}
+
+// Weights are plain integers in whatever unit the tree builder snapshots (stake lamports,
+// share-slots); payouts only use weight / total_weight, so the one rule is that
+// total_weight is exactly the sum of the tree's leaf weights. The root cannot be summed
+// here, so claims count the weight they take and fail once it passes total_weight.
+// data: [epoch:u64, total_weight:u128, root: [u8;32], leaf_count:u32]
+fn op_post_root(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let PostRootAccounts { vault_state, operator, boost_distributor, co_signers } = PostRootAccounts::parse(accs)?;
+    if data.len() < 60 { return Err(ProgramError::InvalidInstructionData) }
+    let st = load_vault_mut(program_id, vault_state)?;
+    require_operator(st, operator.key)?;
+    if root_signatures(st, operator, co_signers) < st.root_threshold as usize {
+        return Err(VaultError::RootThresholdNotMet.into())
+    }
+    let clock = Clock::get()?;
+    let epoch = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let total_weight = u128::from_le_bytes(data[8..24].try_into().unwrap());
+    let mut root = [0u8;32];
+    root.copy_from_slice(&data[24..56]);
+    let leaf_count = u32::from_le_bytes(data[56..60].try_into().unwrap());
+    // every leaf must have a claim bit
+    let page_count = leaf_count.div_ceil(CLAIM_PAGE_BITS);
+    if page_count > MAX_CLAIM_PAGES { return Err(ProgramError::InvalidInstructionData) }
+
+    check_distributor(program_id, &st.vault_pda, boost_distributor, epoch)?;
+    let bd = load_mut::<BoostDistributor>(program_id, boost_distributor)?;
+    if bd.weight_commitment == [0u8; 32] { return Err(VaultError::WeightCommitmentMissing.into()) }
+    if bd.epoch != epoch || weight_commitment(epoch, total_weight, &root, leaf_count) != bd.weight_commitment {
+        return Err(VaultError::WeightCommitmentMismatch.into())
+    }
+    if bd.finalized != 0 { return Err(VaultError::EpochFinalized.into()) }
+    bd.total_weight = total_weight;
+    bd.root = root;
+    bd.leaf_count = leaf_count;
+    bd.bitmap_page_size = CLAIM_PAGE_BITS as u16;
+    bd.bitmap_page_count = page_count as u16;
+    bd.expiry_slot = clock.slot.checked_add(CLAIM_WINDOW_SLOTS).ok_or(VaultError::MathOverflow)?;
+    vlog!("post_root epoch={} leaves={}", epoch, leaf_count);
+    emit(EVT_POSTROOT, &clock, &PostRootEvent {
+        epoch, _pad: [0; 8], total_weight, root, leaf_count, _pad1: [0; 12],
+    });
+    Ok(())
+}
+
+// Locks the epoch's posted root so PostRoot can never swap it (or total_weight and
+// leaf_count) once claims are being made against it. One-way; the operator runs it right
+// after PostRoot, and a wrong root can then only be handled by letting the epoch expire.
+// data: [epoch:u64]
+fn op_finalize_epoch(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let CommitWeightsAccounts { vault_state, operator, boost_distributor } = CommitWeightsAccounts::parse(accs)?;
+    if data.len() < 8 { return Err(ProgramError::InvalidInstructionData) }
+    let epoch = u64::from_le_bytes(data[0..8].try_into().unwrap());
+
+    let st = load_vault(program_id, vault_state)?;
+    require_operator(st, operator.key)?;
+    let clock = Clock::get()?;
+    check_distributor(program_id, &st.vault_pda, boost_distributor, epoch)?;
+    let bd = load_mut::<BoostDistributor>(program_id, boost_distributor)?;
+    if bd.epoch != epoch { return Err(ProgramError::InvalidArgument) }
+    if !bd.funding_closed() { return Err(ProgramError::InvalidInstructionData) }
+    if bd.finalized != 0 { return Err(VaultError::EpochFinalized.into()) }
+    bd.finalized = 1;
+    vlog!("finalize_epoch epoch={}", epoch);
+    emit(EVT_EPOCH_FINALIZED, &clock, &EpochFinalizedEvent { root: bd.root, epoch });
+    Ok(())
+}
+
+// Pins the hash of the epoch's exported archive (tree, claim bitmap snapshot, payout
+// ledger; see `interest-cli epoch-archive`) on its distributor, so the data stays
+// auditable against the chain once it lives off-chain. Only after expiry and Clawback,
+// when the record can no longer change, and only once.
+// data: [epoch:u64, archive_hash:[u8;32]]
+fn op_record_archive(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let CommitWeightsAccounts { vault_state, operator, boost_distributor } = CommitWeightsAccounts::parse(accs)?;
+    if data.len() < 40 { return Err(ProgramError::InvalidInstructionData) }
+    let epoch = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let mut archive_hash = [0u8; 32];
+    archive_hash.copy_from_slice(&data[8..40]);
+    if archive_hash == [0u8; 32] { return Err(ProgramError::InvalidInstructionData) }
+
+    let st = load_vault(program_id, vault_state)?;
+    require_operator(st, operator.key)?;
+    let clock = Clock::get()?;
+    check_distributor(program_id, &st.vault_pda, boost_distributor, epoch)?;
+    let bd = load_mut::<BoostDistributor>(program_id, boost_distributor)?;
+    if bd.epoch != epoch { return Err(ProgramError::InvalidArgument) }
+    if !bd.claims_expired(clock.slot) { return Err(VaultError::ClaimWindowOpen.into()) }
+    if !bd.settled(clock.slot) { return Err(VaultError::ClawbackPending.into()) }
+    if bd.archive_hash != [0u8; 32] { return Err(VaultError::ArchiveRecorded.into()) }
+    bd.archive_hash = archive_hash;
+    vlog!("record_archive epoch={}", epoch);
+    emit(EVT_EPOCH_ARCHIVED, &clock, &EpochArchivedEvent { archive_hash, epoch });
+    Ok(())
+}
+
+// Records the operator's figures for a posted epoch in its report PDA, paid by the
+// operator. A second report for the same epoch fails: the account already exists.
+// data: [epoch:u64, gross_yield:u64, fees:u64, strategy_pnl:i64]
+fn op_report(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let ReportAccounts { vault_state, operator, boost_distributor, report, system_program } = ReportAccounts::parse(accs)?;
+    if data.len() < 32 { return Err(ProgramError::InvalidInstructionData) }
+    let epoch = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let gross_yield = u64::from_le_bytes(data[8..16].try_into().unwrap());
+    let fees = u64::from_le_bytes(data[16..24].try_into().unwrap());
+    let strategy_pnl = i64::from_le_bytes(data[24..32].try_into().unwrap());
+    if fees > gross_yield { return Err(ProgramError::InvalidInstructionData) }
+
+    let st = load_vault(program_id, vault_state)?;
+    require_operator(st, operator.key)?;
+    let clock = Clock::get()?;
+    check_distributor(program_id, &st.vault_pda, boost_distributor, epoch)?;
+    let bd = load::<BoostDistributor>(program_id, boost_distributor)?;
+    if bd.epoch != epoch { return Err(ProgramError::InvalidArgument) }
+    // before the root, late donations can still add boost to this epoch
+    if !bd.funding_closed() { return Err(ProgramError::InvalidInstructionData) }
+    let (expected, bump) = derive_epoch_report(program_id, &st.vault_pda, epoch);
+    if *report.key != expected { return Err(ProgramError::InvalidSeeds) }
+    if !report.data_is_empty() { return Err(ProgramError::AccountAlreadyInitialized) }
+
+    {
+        let lamports = Rent::get()?.minimum_balance(EpochReport::LEN);
+        let metas = [AccountMeta::writable_signer(operator.key), AccountMeta::writable_signer(report.key)];
+        let create = data_create_account(lamports, EpochReport::LEN as u64, program_id);
+        let epoch_le = epoch.to_le_bytes();
+        let bump = [bump];
+        let seeds = [Seed::from(SEED_REPORT), Seed::from(st.vault_pda.as_ref()), Seed::from(epoch_le.as_ref()), Seed::from(bump.as_ref())];
+        cpi::invoke_signed(&ix(system_program, &create, &metas), &[operator,report], &[Signer::from(&seeds)])?;
+    }
+    let r = init_account::<EpochReport>(report)?;
+    *r = EpochReport {
+        discriminator: EpochReport::DISCRIMINATOR,
+        epoch,
+        pps: st.pps,
+        gross_yield,
+        fees,
+        boost_allocated: bd.boost_total,
+        strategy_pnl,
+        reported_slot: clock.slot,
+        operator: *operator.key,
+        bump,
+        _pad: [0; 7],
+    };
+    vlog!("report epoch={} gross_yield={} fees={} boost={}", epoch, gross_yield, fees, bd.boost_total);
+    emit(EVT_EPOCH_REPORTED, &clock, &EpochReportedEvent { epoch, gross_yield, fees, boost_allocated: bd.boost_total, strategy_pnl });
+    Ok(())
+}
+
+// Returns an expired epoch's unclaimed boost (boost_total - claimed_total, rounding dust
+// included) from its boost account to the vault as base, raising pps like a donation's
+// base share. Claims fail from expiry_slot on, so the swept amount can no longer be owed.
+// Running it again after a sweep moves nothing.
+// data: [epoch:u64]
+fn op_clawback(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let ClawbackAccounts {
+        vault_state, vault_pda, operator, boost_distributor, boost_usdc_ata, vault_usdc_ata, token_program, usdc_mint,
+    } = ClawbackAccounts::parse(accs)?;
+    if data.len() < 8 { return Err(ProgramError::InvalidInstructionData) }
+    let epoch = u64::from_le_bytes(data[0..8].try_into().unwrap());
+
+    let st = load_vault_mut(program_id, vault_state)?;
+    check_not_paused(st, PAUSE_DONATE)?;
+    let clock = Clock::get()?;
+    require_operator(st, operator.key)?;
+    if *vault_pda.key != st.vault_pda { return Err(ProgramError::InvalidSeeds) }
+    check_vault_mint(st, usdc_mint, token_program)?;
+    check_vault_usdc(st, vault_usdc_ata, token_program)?;
+    check_distributor(program_id, &st.vault_pda, boost_distributor, epoch)?;
+    let bd = load_mut::<BoostDistributor>(program_id, boost_distributor)?;
+    if bd.epoch != epoch { return Err(ProgramError::InvalidArgument) }
+    if !bd.claims_expired(clock.slot) { return Err(VaultError::ClaimWindowOpen.into()) }
+    let source = BoostSource::resolve(program_id, st, bd, boost_distributor, boost_usdc_ata, vault_pda)?;
+
+    let amount = bd.boost_total
+        .checked_sub(bd.claimed_total).and_then(|v| v.checked_sub(bd.clawed_back))
+        .ok_or(VaultError::CorruptedState)?;
+    if amount == 0 { return Ok(()) }
+    bd.clawed_back = bd.clawed_back.checked_add(amount).ok_or(VaultError::MathOverflow)?;
+    accrue_base(st, amount, &clock)?;
+
+    source.pay(st, token_program, usdc_mint, vault_usdc_ata, amount, mint_decimals(usdc_mint)?)?;
+
+    vlog!("clawback epoch={} amount={} pps={}", epoch, amount, logfmt::ray(st.pps).as_str());
+    emit(EVT_CLAWBACK, &clock, &ClawbackEvent { epoch, amount, pps: st.pps });
+    Ok(())
+}
+
+// domain-separated leaf: keccak(b"weight", index, claimer, weight)
+fn claim_leaf(index: u32, claimer: &Pubkey, weight: u128) -> [u8; 32] {
+    let mut leaf = [0u8; 32];
+    keccak256(&[b"weight", &index.to_le_bytes(), claimer.as_ref(), &weight.to_le_bytes()], &mut leaf);
+    leaf
+}
+
+// Where an epoch's boost is paid from: its escrow, a self-owned token account at
+// [b"escrow", distributor] that only this epoch's claims can sign for, or (epochs funded
+// before escrows existed) the shared boost account owned by vault_pda.
+struct BoostSource<'a> {
+    account: &'a AccountInfo,
+    authority: &'a AccountInfo,
+    distributor: &'a Pubkey,
+    bump: [u8; 1],
+    escrow: bool,
+}
+
+impl<'a> BoostSource<'a> {
+    fn resolve(
+        program_id: &Pubkey, st: &VaultState, bd: &BoostDistributor,
+        distributor: &'a AccountInfo, boost_ata: &'a AccountInfo, vault_pda: &'a AccountInfo,
+    ) -> Result<Self, ProgramError> {
+        if bd.escrow == 0 {
+            check_boost_usdc(st, boost_ata)?;
+            return Ok(Self { account: boost_ata, authority: vault_pda, distributor: distributor.key, bump: [st.vault_bump], escrow: false })
+        }
+        if *boost_ata.key != boost_escrow_address(program_id, distributor.key, bd)? { return Err(VaultError::BoostEscrowMismatch.into()) }
+        Ok(Self { account: boost_ata, authority: boost_ata, distributor: distributor.key, bump: [bd.escrow_bump], escrow: true })
+    }
+
+    fn pay(&self, st: &VaultState, token_program: &AccountInfo, mint: &AccountInfo, dest: &AccountInfo, amount: u64, decimals: u8) -> ProgramResult {
+        let metas = metas_transfer_checked(self.account.key, mint.key, dest.key, self.authority.key);
+        let data = data_transfer_checked(amount, decimals);
+        let accounts = [self.account, mint, dest, self.authority];
+        if self.escrow {
+            let seeds = [Seed::from(SEED_ESCROW), Seed::from(self.distributor.as_ref()), Seed::from(self.bump.as_ref())];
+            cpi::invoke_signed(&ix(token_program, &data, &metas), &accounts, &[Signer::from(&seeds)])
+        } else {
+            let seeds = vault_seeds(st, &self.bump);
+            cpi::invoke_signed(&ix(token_program, &data, &metas), &accounts, &[Signer::from(&seeds)])
+        }
+    }
+}
+
+// A leaf whose proof checked out and whose bit is still clear.
+struct VerifiedClaim {
+    epoch: u64,
+    index: u32,
+    weight: u128,
+    amount: u64,
+    byte: usize,
+    mask: u8,
+}
+
+// data: [epoch:u64, index:u32, weight:u128, proof_len:u8, proof_nodes... (32b each)]
+fn verify_claim(bd: &BoostDistributor, bm: &ClaimBitmap256, claimer: &Pubkey, data: &[u8], slot: u64) -> Result<VerifiedClaim, ProgramError> {
+    if data.len() < 29 { return Err(ProgramError::InvalidInstructionData) }
+    let epoch = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let index = u32::from_le_bytes(data[8..12].try_into().unwrap());
+    let weight = u128::from_le_bytes(data[12..28].try_into().unwrap());
+    let proof_len = data[28] as usize;
+    let mut off = 29usize;
+
+    if bd.epoch != epoch { return Err(ProgramError::InvalidArgument) }
+    if bd.total_weight == 0 { return Err(ProgramError::InvalidInstructionData) }
+    if bd.claims_expired(slot) { return Err(VaultError::ClaimWindowClosed.into()) }
+    if index >= bd.leaf_count { return Err(VaultError::ClaimIndexOutOfRange.into()) }
+
+    // bitmap page holding this index
+    let (_, slot) = bd.claim_page(index);
+    let bit = (slot & 7) as u8;
+    let byte = (slot / 8) as usize;
+    if byte >= bm.words.len() { return Err(ProgramError::InvalidInstructionData) }
+    let mask = 1u8 << bit;
+    if (bm.words[byte] & mask) != 0 { return Err(VaultError::AlreadyClaimed.into()) }
+
+    // proof
+    let leaf = claim_leaf(index, claimer, weight);
+
+    // read proof nodes
+    let nodes = proof_len;
+    if data.len() < off + nodes*32 { return Err(ProgramError::InvalidInstructionData) }
+    let mut proof = [[0u8;32]; 16];
+    if nodes > 16 { return Err(ProgramError::InvalidInstructionData) }
+    for i in 0..nodes {
+        proof[i].copy_from_slice(&data[off..off+32]);
+        off += 32;
+    }
+    let ok = verify_merkle(&bd.root, &leaf, &proof[..nodes]);
+    if !ok { return Err(ProgramError::InvalidArgument) }
+
+    // compute claim amount
+    let amount = math::claim_amount(bd.boost_total, weight, bd.total_weight).ok_or(VaultError::MathOverflow)?;
+    Ok(VerifiedClaim { epoch, index, weight, amount, byte, mask })
+}
+
+// Marks the leaf claimed and counts its payout. Ops call this before the transfer CPI.
+fn record_claim(bd: &mut BoostDistributor, bm: &mut ClaimBitmap256, c: &VerifiedClaim) -> ProgramResult {
+    let claimed = bd.claimed_total.checked_add(c.amount).ok_or(VaultError::MathOverflow)?;
+    if claimed > bd.boost_total { return Err(VaultError::MathOverflow.into()) }
+    // leaves sum to total_weight, so a tree that outweighs it stops paying at the first
+    // claim past it instead of draining boost meant for the remaining leaves
+    let weight = bd.claimed_weight.checked_add(c.weight).ok_or(VaultError::MathOverflow)?;
+    if weight > bd.total_weight { return Err(VaultError::WeightSumMismatch.into()) }
+    bd.claimed_total = claimed;
+    bd.claimed_weight = weight;
+    bm.words[c.byte] |= c.mask;
+    Ok(())
+}
+
+// data: [epoch:u64, index:u32, weight:u128, proof_len:u8, proof_nodes... (32b each)]
+fn op_claim(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let ClaimAccounts {
+        vault_state, vault_pda, claimer, boost_distributor, claims_bitmap, boost_usdc_ata, claimer_usdc_ata, token_program, usdc_mint, claim_escrow,
+        create_ata,
+    } = ClaimAccounts::parse(accs)?;
+
+    let st = load_vault_mut(program_id, vault_state)?;
+    check_not_paused(st, PAUSE_CLAIM)?;
+    let clock = Clock::get()?;
+    if *vault_pda.key != st.vault_pda { return Err(ProgramError::InvalidSeeds) }
+    let bd = load_mut::<BoostDistributor>(program_id, boost_distributor)?;
+    let bm = load_mut::<ClaimBitmap256>(program_id, claims_bitmap)?;
+    let c = verify_claim(bd, bm, claimer.key, data, clock.slot)?;
+    check_distributor(program_id, &st.vault_pda, boost_distributor, c.epoch)?;
+    check_claims_page(program_id, &st.vault_pda, bd, claims_bitmap, c.index)?;
+    let source = BoostSource::resolve(program_id, st, bd, boost_distributor, boost_usdc_ata, vault_pda)?;
+    check_vault_mint(st, usdc_mint, token_program)?;
+    if let Some(create) = create_ata {
+        if claimer_usdc_ata.data_is_empty() {
+            create_ata_idempotent(create, claimer_usdc_ata, claimer, usdc_mint, token_program)?;
+        }
+    }
+    let dest = payout_destination(program_id, vault_state, claimer, claimer_usdc_ata, claim_escrow, token_program)?;
+    // effects before the transfer CPI, so a re-entrant token program can't replay the leaf
+    record_claim(bd, bm, &c)?;
 
     // transfer boost -> claimer
+    source.pay(st, token_program, usdc_mint, dest, c.amount, mint_decimals(usdc_mint)?)?;
+
+    vlog!("claim epoch={} index={} amount={} held={}", c.epoch, c.index, c.amount, (dest.key != claimer_usdc_ata.key) as u64);
+    emit(EVT_CLAIM, &clock, &ClaimEvent { weight: c.weight, claimer: *claimer.key, epoch: c.epoch, amount: c.amount, index: c.index, _pad: [0; 12] });
+    Ok(())
+}
+// View for UIs and bots: runs Claim's checks and returns the payout as return data
+// [amount:u64] without moving funds or marking the leaf. Fails exactly where Claim
+// would (bad proof, already claimed, index out of range). The vault takes no fee and
+// does not vest boost, so the amount is what Claim transfers.
+// data: same as Claim
+fn op_quote_claim(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let QuoteClaimAccounts { boost_distributor, claims_bitmap, claimer } = QuoteClaimAccounts::parse(accs)?;
+    if boost_distributor.owner != program_id || claims_bitmap.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId)
+    }
+    let bd = load::<BoostDistributor>(program_id, boost_distributor)?;
+    let bm = load::<ClaimBitmap256>(program_id, claims_bitmap)?;
+    let c = verify_claim(bd, bm, claimer.key, data, Clock::get()?.slot)?;
+    cpi::set_return_data(&c.amount.to_le_bytes());
+    Ok(())
+}
+
+// Settles several leaves of one epoch with a single multiproof. Anyone may send it:
+// each payout goes to a token account whose owner is the leaf's claimer.
+// data: [epoch:u64, n:u8, n * (index:u32, weight:u128), proof_len:u8, proof_nodes (32b each), flags_len:u8, flags (1b each)]
+fn op_claim_many(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let ClaimManyAccounts {
+        vault_state, vault_pda, boost_distributor, claims_bitmap, boost_usdc_ata, token_program, usdc_mint, destinations,
+    } = ClaimManyAccounts::parse(accs)?;
+    if data.len() < 9 { return Err(ProgramError::InvalidInstructionData) }
+    let epoch = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let n = data[8] as usize;
+    if n == 0 || n > MAX_MULTI_LEAVES || destinations.len() < n { return Err(ProgramError::InvalidInstructionData) }
+    let mut off = 9 + n * 20;
+    if data.len() < off + 1 { return Err(ProgramError::InvalidInstructionData) }
+    let proof_len = data[off] as usize * 32;
+    off += 1;
+    if data.len() < off + proof_len + 1 { return Err(ProgramError::InvalidInstructionData) }
+    let proof = &data[off..off + proof_len];
+    off += proof_len;
+    let flags_len = data[off] as usize;
+    off += 1;
+    if data.len() < off + flags_len { return Err(ProgramError::InvalidInstructionData) }
+    let flags = &data[off..off + flags_len];
+
+    let st = load_vault_mut(program_id, vault_state)?;
+    check_not_paused(st, PAUSE_CLAIM)?;
+    let clock = Clock::get()?;
+    if *vault_pda.key != st.vault_pda { return Err(ProgramError::InvalidSeeds) }
+    check_vault_mint(st, usdc_mint, token_program)?;
+    check_distributor(program_id, &st.vault_pda, boost_distributor, epoch)?;
+    let bd = load_mut::<BoostDistributor>(program_id, boost_distributor)?;
+    if bd.epoch != epoch { return Err(ProgramError::InvalidArgument) }
+    if bd.total_weight == 0 { return Err(ProgramError::InvalidInstructionData) }
+    if bd.claims_expired(clock.slot) { return Err(VaultError::ClaimWindowClosed.into()) }
+    let bm = load_mut::<ClaimBitmap256>(program_id, claims_bitmap)?;
+    let usdc_decimals = mint_decimals(usdc_mint)?;
+
+    let mut claimers = [Pubkey::default(); MAX_MULTI_LEAVES];
+    let mut leaves = [[0u8; 32]; MAX_MULTI_LEAVES];
+    // one bitmap page per call: every leaf must sit on the page of the first
+    let first = u32::from_le_bytes(data[9..13].try_into().unwrap());
+    let page = bd.claim_page(first).0;
+    for i in 0..n {
+        let e = 9 + i * 20;
+        let index = u32::from_le_bytes(data[e..e + 4].try_into().unwrap());
+        let weight = u128::from_le_bytes(data[e + 4..e + 20].try_into().unwrap());
+        if index >= bd.leaf_count { return Err(VaultError::ClaimIndexOutOfRange.into()) }
+        if bd.claim_page(index).0 != page { return Err(ProgramError::InvalidInstructionData) }
+        claimers[i] = token_account_owner(&destinations[i])?;
+        leaves[i] = claim_leaf(index, &claimers[i], weight);
+    }
+    if !verify_multiproof(&bd.root, &leaves[..n], proof, flags) { return Err(ProgramError::InvalidArgument) }
+    check_claims_page(program_id, &st.vault_pda, bd, claims_bitmap, first)?;
+
+    let source = BoostSource::resolve(program_id, st, bd, boost_distributor, boost_usdc_ata, vault_pda)?;
+    for (i, dest) in destinations[..n].iter().enumerate() {
+        let e = 9 + i * 20;
+        let index = u32::from_le_bytes(data[e..e + 4].try_into().unwrap());
+        let weight = u128::from_le_bytes(data[e + 4..e + 20].try_into().unwrap());
+        // setting the bit before the next leaf also rejects duplicates within the batch
+        let slot = bd.claim_page(index).1;
+        let (byte, mask) = ((slot / 8) as usize, 1u8 << (slot & 7));
+        if (bm.words[byte] & mask) != 0 { return Err(VaultError::AlreadyClaimed.into()) }
+        let amount = math::claim_amount(bd.boost_total, weight, bd.total_weight).ok_or(VaultError::MathOverflow)?;
+        record_claim(bd, bm, &VerifiedClaim { epoch, index, weight, amount, byte, mask })?;
+        source.pay(st, token_program, usdc_mint, dest, amount, usdc_decimals)?;
+        emit(EVT_CLAIM, &clock, &ClaimEvent { weight, claimer: claimers[i], epoch, amount, index, _pad: [0; 12] });
+    }
+    vlog!("claim_many epoch={} leaves={}", epoch, n as u64);
+    Ok(())
+}
+
+// One claimer's leaves from several epochs. Payouts from the same boost account are
+// summed and sent in one transfer, which is paid out whenever the next entry's boost
+// account differs; so epochs on the shared boost account cost one CPI together, while
+// each escrowed epoch still pays from its own escrow.
+// data: [n:u8, n * (epoch:u64, index:u32, weight:u128, proof_len:u8, proof_nodes (32b each))]
+fn op_claim_epochs(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let ClaimEpochsAccounts { vault_state, vault_pda, claimer, claimer_usdc_ata, token_program, usdc_mint, entries } = ClaimEpochsAccounts::parse(accs)?;
+    if data.is_empty() { return Err(ProgramError::InvalidInstructionData) }
+    let n = data[0] as usize;
+    if n == 0 || n > MAX_CLAIM_EPOCHS { return Err(ProgramError::InvalidInstructionData) }
+    if entries.len() < n * 3 { return Err(ProgramError::NotEnoughAccountKeys) }
+
+    let st = load_vault_mut(program_id, vault_state)?;
+    check_not_paused(st, PAUSE_CLAIM)?;
+    let clock = Clock::get()?;
+    if *vault_pda.key != st.vault_pda { return Err(ProgramError::InvalidSeeds) }
+    check_vault_mint(st, usdc_mint, token_program)?;
+    let usdc_decimals = mint_decimals(usdc_mint)?;
+    let dest = payout_destination(program_id, vault_state, claimer, claimer_usdc_ata, entries.get(n * 3), token_program)?;
+
+    let mut off = 1usize;
+    let mut pending: Option<(BoostSource, u64)> = None;
+    let mut total = 0u64;
+    for group in entries[..n * 3].chunks_exact(3) {
+        let (boost_distributor, claims_bitmap, boost_usdc_ata) = (&group[0], &group[1], &group[2]);
+        if data.len() < off + 29 { return Err(ProgramError::InvalidInstructionData) }
+        let len = 29 + data[off + 28] as usize * 32;
+        if data.len() < off + len { return Err(ProgramError::InvalidInstructionData) }
+        let entry = &data[off..off + len];
+        off += len;
+
+        let epoch = u64::from_le_bytes(entry[0..8].try_into().unwrap());
+        check_distributor(program_id, &st.vault_pda, boost_distributor, epoch)?;
+        let bd = load_mut::<BoostDistributor>(program_id, boost_distributor)?;
+        let bm = load_mut::<ClaimBitmap256>(program_id, claims_bitmap)?;
+        let c = verify_claim(bd, bm, claimer.key, entry, clock.slot)?;
+        check_claims_page(program_id, &st.vault_pda, bd, claims_bitmap, c.index)?;
+        let source = BoostSource::resolve(program_id, st, bd, boost_distributor, boost_usdc_ata, vault_pda)?;
+        // marking the leaf before the next entry also rejects a repeated entry
+        record_claim(bd, bm, &c)?;
+        total = total.checked_add(c.amount).ok_or(VaultError::MathOverflow)?;
+        emit(EVT_CLAIM, &clock, &ClaimEvent { weight: c.weight, claimer: *claimer.key, epoch: c.epoch, amount: c.amount, index: c.index, _pad: [0; 12] });
+
+        pending = match pending {
+            Some((prev, sum)) if *prev.account.key == *source.account.key => Some((prev, sum + c.amount)),
+            Some((prev, sum)) => {
+                prev.pay(st, token_program, usdc_mint, dest, sum, usdc_decimals)?;
+                Some((source, c.amount))
+            }
+            None => Some((source, c.amount)),
+        };
+    }
+    if let Some((source, sum)) = pending {
+        source.pay(st, token_program, usdc_mint, dest, sum, usdc_decimals)?;
+    }
+    vlog!("claim_epochs epochs={} amount={}", n as u64, total);
+    Ok(())
+}
+
+// A frozen (e.g. compliance-frozen) or closed USDC account would fail the claim on every
+// retry. In that case the payout goes to the claimer's claim escrow, passed after the
+// claim's other accounts, and waits there for RedeemEscrow.
+fn payout_destination<'a>(
+    program_id: &Pubkey, vault_state: &AccountInfo, claimer: &AccountInfo, claimer_usdc_ata: &'a AccountInfo,
+    claim_escrow: Option<&'a AccountInfo>, token_program: &AccountInfo,
+) -> Result<&'a AccountInfo, ProgramError> {
+    if token_account_open(claimer_usdc_ata, token_program.key)? { return Ok(claimer_usdc_ata) }
+    let claim_escrow = claim_escrow.ok_or(VaultError::PayoutAccountUnusable)?;
+    let (expected, _) = derive_claim_escrow(program_id, vault_state.key, claimer.key);
+    if *claim_escrow.key != expected { return Err(ProgramError::InvalidSeeds) }
+    Ok(claim_escrow)
+}
+
+// The ATA program derives the address from (owner, token_program, mint) and fails if
+// `ata` is not it, so nothing but owner's canonical account can be created here.
+fn create_ata_idempotent(
+    accs: CreateAtaAccounts, ata: &AccountInfo, owner: &AccountInfo, mint: &AccountInfo, token_program: &AccountInfo,
+) -> ProgramResult {
+    let CreateAtaAccounts { payer, system_program, ata_program } = accs;
+    let metas = [
+        AccountMeta::writable_signer(payer.key), AccountMeta::writable(ata.key), AccountMeta::readonly(owner.key),
+        AccountMeta::readonly(mint.key), AccountMeta::readonly(system_program.key), AccountMeta::readonly(token_program.key),
+    ];
+    cpi::invoke(&ix(ata_program, &[ATA_IX_CREATE_IDEMPOTENT], &metas), &[payer, ata, owner, mint, system_program, token_program])
+}
+
+// Creates the claimer's claim escrow: a USDC account at its PDA that is its own
+// authority, so only RedeemEscrow (signed by the claimer) can move what lands there.
+// data: []
+fn op_open_claim_escrow(program_id: &Pubkey, accs: &[AccountInfo]) -> ProgramResult {
+    let OpenClaimEscrowAccounts {
+        vault_state, payer, claimer, claim_escrow, usdc_mint, system_program, token_program,
+    } = OpenClaimEscrowAccounts::parse(accs)?;
+    let st = load_vault(program_id, vault_state)?;
+    check_vault_mint(st, usdc_mint, token_program)?;
+    let (expected, bump) = derive_claim_escrow(program_id, vault_state.key, claimer.key);
+    if *claim_escrow.key != expected { return Err(ProgramError::InvalidSeeds) }
+    if !claim_escrow.data_is_empty() { return Err(ProgramError::AccountAlreadyInitialized) }
+
+    {
+        let len = token_account_len(usdc_mint)?;
+        let lamports = Rent::get()?.minimum_balance(len);
+        let metas = [AccountMeta::writable_signer(payer.key), AccountMeta::writable_signer(claim_escrow.key)];
+        let data = data_create_account(lamports, len as u64, token_program.key);
+        let bump = [bump];
+        let seeds = [Seed::from(SEED_CLAIM_ESCROW), Seed::from(vault_state.key.as_ref()), Seed::from(claimer.key.as_ref()), Seed::from(bump.as_ref())];
+        cpi::invoke_signed(&ix(system_program, &data, &metas), &[payer,claim_escrow], &[Signer::from(&seeds)])?;
+    }
+    {
+        let metas = [AccountMeta::writable(claim_escrow.key), AccountMeta::readonly(usdc_mint.key)];
+        let data = data_initialize_account3(claim_escrow.key);
+        cpi::invoke(&ix(token_program, &data, &metas), &[claim_escrow,usdc_mint])?;
+    }
+    vlog!("claim escrow opened");
+    Ok(())
+}
+
+// Moves everything held in the claimer's claim escrow to a USDC account of theirs,
+// e.g. once a frozen account is thawed. Gated by PAUSE_CLAIM like the claims that fill it.
+// data: []
+fn op_redeem_escrow(program_id: &Pubkey, accs: &[AccountInfo]) -> ProgramResult {
+    let RedeemEscrowAccounts { vault_state, claimer, claim_escrow, claimer_usdc_ata, token_program, usdc_mint } = RedeemEscrowAccounts::parse(accs)?;
+    let st = load_vault(program_id, vault_state)?;
+    check_not_paused(st, PAUSE_CLAIM)?;
+    check_vault_mint(st, usdc_mint, token_program)?;
+    let (expected, bump) = derive_claim_escrow(program_id, vault_state.key, claimer.key);
+    if *claim_escrow.key != expected { return Err(ProgramError::InvalidSeeds) }
+    let amount = token_account_amount(claim_escrow)?;
+    if amount == 0 { return Err(ProgramError::InsufficientFunds) }
+
+    {
+        let metas = metas_transfer_checked(claim_escrow.key, usdc_mint.key, claimer_usdc_ata.key, claim_escrow.key);
+        let data = data_transfer_checked(amount, mint_decimals(usdc_mint)?);
+        let bump = [bump];
+        let seeds = [Seed::from(SEED_CLAIM_ESCROW), Seed::from(vault_state.key.as_ref()), Seed::from(claimer.key.as_ref()), Seed::from(bump.as_ref())];
+        cpi::invoke_signed(&ix(token_program, &data, &metas), &[claim_escrow, usdc_mint, claimer_usdc_ata, claim_escrow], &[Signer::from(&seeds)])?;
+    }
+    let clock = Clock::get()?;
+    vlog!("escrow redeemed amount={}", amount);
+    emit(EVT_ESCROW_REDEEMED, &clock, &EscrowRedeemedEvent { claimer: *claimer.key, amount });
+    Ok(())
+}
+
+// data: [auto_claim:u8, compound:u8]
+fn op_set_auto_claim(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let AutoClaimAccounts { vault_state, user, prefs, system_program } = AutoClaimAccounts::parse(accs)?;
+    if data.len() < 2 || data[0] > 1 || data[1] > 1 { return Err(ProgramError::InvalidInstructionData) }
+    let _st = load_vault(program_id, vault_state)?;
+    let (expected, bump) = derive_user_prefs(program_id, vault_state.key, user.key);
+    if *prefs.key != expected { return Err(ProgramError::InvalidSeeds) }
+
+    // first opt-in creates the user's prefs account, paid by the user
+    if prefs.data_is_empty() {
+        let lamports = Rent::get()?.minimum_balance(UserPrefs::LEN);
+        let metas = [AccountMeta::writable_signer(user.key), AccountMeta::writable_signer(prefs.key)];
+        let data = data_create_account(lamports, UserPrefs::LEN as u64, program_id);
+        let bump = [bump];
+        let seeds = [Seed::from(SEED_PREFS), Seed::from(vault_state.key.as_ref()), Seed::from(user.key.as_ref()), Seed::from(bump.as_ref())];
+        cpi::invoke_signed(&ix(system_program, &data, &metas), &[user,prefs], &[Signer::from(&seeds)])?;
+        init_account::<UserPrefs>(prefs)?;
+    }
+
+    let p = load_mut::<UserPrefs>(program_id, prefs)?;
+    p.vault_state = *vault_state.key;
+    p.user = *user.key;
+    p.auto_claim = data[0];
+    p.compound = data[1];
+    p.bump = bump;
+    Ok(())
+}
+
+// Operator claims for a user who opted in. The payout goes where the user's prefs say:
+// their USDC account, or back into the vault as freshly minted shares.
+// data: same as op_claim
+fn op_crank_claim(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let CrankClaimAccounts {
+        vault_state, vault_pda, operator, claimer, prefs, boost_distributor, claims_bitmap, boost_usdc_ata, destination,
+        token_program, usdc_mint, compound,
+    } = CrankClaimAccounts::parse(accs)?;
+
+    let st = load_vault_mut(program_id, vault_state)?;
+    check_not_paused(st, PAUSE_CLAIM)?;
+    let clock = Clock::get()?;
+    require_operator(st, operator.key)?;
+    if *vault_pda.key != st.vault_pda { return Err(ProgramError::InvalidSeeds) }
+    check_vault_mint(st, usdc_mint, token_program)?;
+    let (expected, _) = derive_user_prefs(program_id, vault_state.key, claimer.key);
+    if *prefs.key != expected { return Err(ProgramError::InvalidSeeds) }
+    let p = load::<UserPrefs>(program_id, prefs)?;
+    if p.auto_claim == 0 { return Err(VaultError::AutoClaimNotEnabled.into()) }
+    // compounding mints shares, so it is a deposit too
+    if p.compound != 0 { check_not_paused(st, PAUSE_DEPOSIT)?; }
+    // the operator picks the accounts, so the destination is pinned by the user's choice:
+    // the vault USDC account when compounding, but never the boost account it pays from
+    if p.compound != 0 {
+        if destination.key == boost_usdc_ata.key { return Err(VaultError::PayoutAccountMismatch.into()) }
+        check_vault_usdc(st, destination, token_program)?;
+    } else if token_account_owner(destination)? != *claimer.key {
+        return Err(VaultError::PayoutAccountMismatch.into())
+    }
+    settle(st, &clock)?;
+
+    let bd = load_mut::<BoostDistributor>(program_id, boost_distributor)?;
+    let bm = load_mut::<ClaimBitmap256>(program_id, claims_bitmap)?;
+    let c = verify_claim(bd, bm, claimer.key, data, clock.slot)?;
+    check_distributor(program_id, &st.vault_pda, boost_distributor, c.epoch)?;
+    check_claims_page(program_id, &st.vault_pda, bd, claims_bitmap, c.index)?;
+    let source = BoostSource::resolve(program_id, st, bd, boost_distributor, boost_usdc_ata, vault_pda)?;
+    record_claim(bd, bm, &c)?;
+    let usdc_decimals = mint_decimals(usdc_mint)?;
+    source.pay(st, token_program, usdc_mint, destination, c.amount, usdc_decimals)?;
+
+    if p.compound != 0 {
+        let compound = compound.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        mint_compounded(st, &clock, claimer, compound, token_program, c.amount)?;
+    }
+
+    vlog!("crank_claim epoch={} index={} amount={}", c.epoch, c.index, c.amount);
+    emit(EVT_CLAIM, &clock, &ClaimEvent { weight: c.weight, claimer: *claimer.key, epoch: c.epoch, amount: c.amount, index: c.index, _pad: [0; 12] });
+    Ok(())
+}
+
+// Claim whose payout is deposited straight back into the vault as shares for the claimer,
+// in one instruction and without a USDC account for the claimer. Verification and the
+// claim bit are exactly Claim's.
+// data: same as op_claim
+fn op_claim_compound(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let ClaimCompoundAccounts {
+        vault_state, vault_pda, claimer, boost_distributor, claims_bitmap, boost_usdc_ata, vault_usdc_ata, token_program, usdc_mint,
+        compound,
+    } = ClaimCompoundAccounts::parse(accs)?;
+
+    let st = load_vault_mut(program_id, vault_state)?;
+    check_not_paused(st, PAUSE_CLAIM)?;
+    check_not_paused(st, PAUSE_DEPOSIT)?;
+    let clock = Clock::get()?;
+    if *vault_pda.key != st.vault_pda { return Err(ProgramError::InvalidSeeds) }
+    check_vault_mint(st, usdc_mint, token_program)?;
+    check_vault_usdc(st, vault_usdc_ata, token_program)?;
+    settle(st, &clock)?;
+    let bd = load_mut::<BoostDistributor>(program_id, boost_distributor)?;
+    let bm = load_mut::<ClaimBitmap256>(program_id, claims_bitmap)?;
+    let c = verify_claim(bd, bm, claimer.key, data, clock.slot)?;
+    check_distributor(program_id, &st.vault_pda, boost_distributor, c.epoch)?;
+    check_claims_page(program_id, &st.vault_pda, bd, claims_bitmap, c.index)?;
+    let source = BoostSource::resolve(program_id, st, bd, boost_distributor, boost_usdc_ata, vault_pda)?;
+    record_claim(bd, bm, &c)?;
+
+    // boost -> vault, then shares for it at the current pps
+    source.pay(st, token_program, usdc_mint, vault_usdc_ata, c.amount, mint_decimals(usdc_mint)?)?;
+    mint_compounded(st, &clock, claimer, compound, token_program, c.amount)?;
+
+    vlog!("claim_compound epoch={} index={} amount={}", c.epoch, c.index, c.amount);
+    emit(EVT_CLAIM, &clock, &ClaimEvent { weight: c.weight, claimer: *claimer.key, epoch: c.epoch, amount: c.amount, index: c.index, _pad: [0; 12] });
+    Ok(())
+}
+
+// Mints shares for `amount` USDC that a claim has already moved into the vault.
+fn mint_compounded(
+    st: &mut VaultState, clock: &Clock, claimer: &AccountInfo, accs: CompoundAccounts, token_program: &AccountInfo, amount: u64,
+) -> ProgramResult {
+    let CompoundAccounts { mint_authority, share_mint, claimer_share_ata } = accs;
+    if *mint_authority.key != st.auth_pda { return Err(ProgramError::InvalidSeeds) }
+    check_share_mint(st, share_mint, token_program)?;
+    if token_account_owner(claimer_share_ata)? != *claimer.key { return Err(VaultError::PayoutAccountMismatch.into()) }
+
+    let shares = shares_to_issue(st, amount, clock.slot)?;
+    let mint_amt: u64 = shares.try_into().map_err(|_| VaultError::MathOverflow)?;
+    st.total_shares = st.total_shares.checked_add(shares).ok_or(VaultError::MathOverflow)?;
+    let metas = metas_mint_to_checked(share_mint.key, claimer_share_ata.key, mint_authority.key);
+    let data = data_mint_to_checked(mint_amt, st.share_decimals);
+    let bump = [st.auth_bump];
+    let seeds = auth_seeds(st, &bump);
+    cpi::invoke_signed(&ix(token_program, &data, &metas), &[share_mint,claimer_share_ata,mint_authority], &[Signer::from(&seeds)])?;
+    emit(EVT_DEPOSIT, clock, &DepositEvent { user: *claimer.key, amount, shares: mint_amt, pps: st.pps, recipient: *claimer.key });
+    Ok(())
+}
+
+// The only writer of pps: folds buffered base into pps once there are shares to spread
+// it over and returns the base folded. Every op that prices shares or adds base calls it
+// before doing so, so they all see the same pps. A no-op in emergency mode, where pps
+// stays frozen so every exit gets the same price.
+fn settle(st: &mut VaultState, clock: &Clock) -> Result<u64, ProgramError> {
+    if st.flags & PAUSE_EMERGENCY != 0 { return Ok(0) }
+    if st.buffered_base == 0 || st.total_shares == 0 { return Ok(0) }
+    let base = st.buffered_base;
+    let delta = math::pps_delta(base, st.total_shares).ok_or(VaultError::MathOverflow)?;
+    st.pps = st.pps.checked_add(delta).ok_or(VaultError::MathOverflow)?;
+    st.buffered_base = 0;
+    st.last_settle_slot = clock.slot;
+    st.last_activity_slot = clock.slot;
+    Ok(base)
+}
+
+// Base USDC handed to holders (a donation's base share, clawed-back boost): buffered,
+// then settled, so it reaches pps through the same path as every other fold.
+fn accrue_base(st: &mut VaultState, amount: u64, clock: &Clock) -> ProgramResult {
+    st.buffered_base = st.buffered_base.checked_add(amount).ok_or(VaultError::MathOverflow)?;
+    st.last_activity_slot = clock.slot;
+    settle(st, clock)?;
+    Ok(())
+}
+
+// Price `amount` in shares at the settled pps. The first deposit into an empty vault goes through bootstrap.
+fn shares_to_issue(st: &mut VaultState, amount: u64, slot: u64) -> Result<u128, ProgramError> {
+    let shares = math::shares_for_assets(amount, st.pps).ok_or(VaultError::MathOverflow)?;
+    if st.total_shares == 0 {
+        bootstrap(st, amount, shares, slot)?;
+    }
+    Ok(shares)
+}
+
+// First deposit into an empty vault: enforce a minimum size and pin part of the
+// minted shares in supply for BOOTSTRAP_LOCK_SLOTS, so the vault cannot be drained
+// back to a dust share count where pps is cheap to manipulate.
+fn bootstrap(st: &mut VaultState, amount: u64, shares: u128, slot: u64) -> ProgramResult {
+    if amount < BOOTSTRAP_MIN_DEPOSIT { return Err(VaultError::BootstrapDepositTooSmall.into()) }
+    st.bootstrap_locked_shares = shares.min(BOOTSTRAP_LOCKED_SHARES as u128) as u64;
+    st.bootstrap_unlock_slot = slot.checked_add(BOOTSTRAP_LOCK_SLOTS).ok_or(VaultError::MathOverflow)?;
+    Ok(())
+}
+
+fn check_bootstrap_lock(st: &VaultState, shares_burn: u64, slot: u64) -> ProgramResult {
+    if slot >= st.bootstrap_unlock_slot { return Ok(()) }
+    let remaining = st.total_shares.checked_sub(shares_burn as u128).ok_or(VaultError::MathOverflow)?;
+    if remaining < st.bootstrap_locked_shares as u128 { return Err(VaultError::BootstrapSharesLocked.into()) }
+    Ok(())
+}
+
+fn check_yield_attestation(st: &VaultState, oracle: &AccountInfo, epoch: u64, amount: u64) -> ProgramResult {
+    if *oracle.key != st.yield_oracle { return Err(VaultError::OracleAccountMismatch.into()) }
+    let att = cast::<YieldAttestation>(oracle)?;
+    if att.epoch != epoch { return Err(VaultError::OracleEpochMismatch.into()) }
+
+    let reported = att.reported_yield as u128;
+    let diff = (amount as u128).abs_diff(reported);
+    let tolerance = reported * st.oracle_tolerance_bps as u128 / BPS_DENOM as u128;
+    if diff > tolerance { return Err(VaultError::OracleYieldOutOfTolerance.into()) }
+    Ok(())
+}
+
+// data: [yield_oracle: Pubkey, tolerance_bps:u16]   (yield_oracle = default disables the check)
+fn op_set_yield_oracle(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let AdminAccounts { vault_state, admin } = AdminAccounts::parse(accs)?;
+    if data.len() < 34 { return Err(ProgramError::InvalidInstructionData) }
+    let mut oracle = [0u8; 32];
+    oracle.copy_from_slice(&data[0..32]);
+    let tolerance_bps = u16::from_le_bytes(data[32..34].try_into().unwrap());
+    if tolerance_bps as u64 > BPS_DENOM { return Err(ProgramError::InvalidInstructionData) }
+
+    let st = load_vault_mut(program_id, vault_state)?;
+    require_admin(st, admin.key)?;
+    st.yield_oracle = Pubkey::from(oracle);
+    st.oracle_tolerance_bps = tolerance_bps;
+    Ok(())
+}
+
+const RENT_TARGET_VAULT_STATE: u8 = 0;
+const RENT_TARGET_DISTRIBUTOR: u8 = 1;
+const RENT_TARGET_CLAIMS_BITMAP: u8 = 2;
+const RENT_TARGET_USER_PREFS: u8 = 3;
+
+// Brings one of this vault's program-owned accounts back to exactly rent-exempt: tops
+// it up from the admin after a realloc, or sweeps lamports above the minimum to the
+// admin. The target is re-derived from `kind`, so other vaults' accounts can't be swept.
+// data: [kind:u8, epoch:u64 (distributor/bitmap) | user: Pubkey (prefs), page:u16? (bitmap; default 0)]
+fn op_reconcile_rent(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let ReconcileRentAccounts { vault_state, admin, target, system_program } = ReconcileRentAccounts::parse(accs)?;
+    let st = load_vault(program_id, vault_state)?;
+    require_admin(st, admin.key)?;
+
+    let kind = *data.first().ok_or(ProgramError::InvalidInstructionData)?;
+    let epoch = || data.get(1..9).map(|b| u64::from_le_bytes(b.try_into().unwrap())).ok_or(ProgramError::InvalidInstructionData);
+    let expected = match kind {
+        RENT_TARGET_VAULT_STATE => *vault_state.key,
+        RENT_TARGET_DISTRIBUTOR => derive_boost_distributor(program_id, &st.vault_pda, epoch()?).0,
+        RENT_TARGET_CLAIMS_BITMAP => {
+            let page = data.get(9..11).map_or(0, |p| u16::from_le_bytes(p.try_into().unwrap()));
+            derive_claims_page(program_id, &st.vault_pda, epoch()?, page).0
+        }
+        RENT_TARGET_USER_PREFS => {
+            let user: Pubkey = data.get(1..33).ok_or(ProgramError::InvalidInstructionData)?.try_into().unwrap();
+            derive_user_prefs(program_id, vault_state.key, &user).0
+        }
+        _ => return Err(ProgramError::InvalidInstructionData),
+    };
+    if *target.key != expected { return Err(ProgramError::InvalidSeeds) }
+    if target.owner != program_id { return Err(ProgramError::IncorrectProgramId) }
+
+    let clock = Clock::get()?;
+    let min = Rent::get()?.minimum_balance(target.data_len());
+    let before = target.lamports();
+    if before < min {
+        let metas = [AccountMeta::writable_signer(admin.key), AccountMeta::writable(target.key)];
+        let data = data_system_transfer(min - before);
+        cpi::invoke(&ix(system_program, &data, &metas), &[admin, target])?;
+    } else if before > min {
+        // program-owned, so lamports can be moved directly
+        *target.try_borrow_mut_lamports()? = min;
+        let mut to = admin.try_borrow_mut_lamports()?;
+        *to = to.checked_add(before - min).ok_or(VaultError::MathOverflow)?;
+    }
+    emit(EVT_RENT_RECONCILED, &clock, &RentReconciledEvent { account: *target.key, lamports_before: before, lamports_after: min });
+    Ok(())
+}
+
+fn is_allowed_donor(st: &VaultState, donor: &Pubkey) -> bool {
+    *donor == st.operator
+        || st.donor_allowlist[..st.donor_allowlist_len as usize].iter().any(|d| d == donor)
+}
+
+// data: [restrict:u8, count:u8, donors: [Pubkey; count]]   (count <= MAX_DONORS)
+fn op_set_donors(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let AdminAccounts { vault_state, admin } = AdminAccounts::parse(accs)?;
+    if data.len() < 2 { return Err(ProgramError::InvalidInstructionData) }
+    let restrict = data[0];
+    let count = data[1] as usize;
+    if restrict > 1 || count > MAX_DONORS || data.len() < 2 + count * 32 {
+        return Err(ProgramError::InvalidInstructionData)
+    }
+
+    let st = load_vault_mut(program_id, vault_state)?;
+    require_admin(st, admin.key)?;
+    let mut list = [Pubkey::default(); MAX_DONORS];
+    for (i, d) in list.iter_mut().take(count).enumerate() {
+        let off = 2 + i * 32;
+        d.copy_from_slice(&data[off..off + 32]);
+    }
+    st.donor_allowlist = list;
+    st.donor_allowlist_len = count as u8;
+    st.restrict_donors = restrict;
+    Ok(())
+}
+
+// Admin may set any PAUSE_* bit; the operator may only flip OPERATOR_PAUSE_BITS.
+// data: [flags:u32]   (full new value)
+fn op_set_pause_flags(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let AdminAccounts { vault_state, admin: authority } = AdminAccounts::parse(accs)?;
+    let flags = u32::from_le_bytes(data.get(0..4).ok_or(ProgramError::InvalidInstructionData)?.try_into().unwrap());
+    if flags & !PAUSE_ALL != 0 { return Err(ProgramError::InvalidInstructionData) }
+
+    let st = load_vault_mut(program_id, vault_state)?;
+    let allowed = if *authority.key == st.admin {
+        PAUSE_ALL
+    } else if *authority.key == st.operator {
+        OPERATOR_PAUSE_BITS
+    } else {
+        return Err(VaultError::Unauthorized.into())
+    };
+    if (st.flags ^ flags) & !allowed != 0 { return Err(VaultError::PauseBitsNotAllowed.into()) }
+    let clock = Clock::get()?;
+    emit(EVT_PAUSE_FLAGS_SET, &clock, &PauseFlagsSetEvent { authority: *authority.key, old_flags: st.flags, new_flags: flags });
+    st.flags = flags;
+    Ok(())
+}
+
+// The guardian is an incident-response key that can only stop the vault (EnterEmergency),
+// never reconfigure or restart it. The default pubkey removes it.
+// data: [guardian: Pubkey]
+fn op_set_guardian(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let AdminAccounts { vault_state, admin } = AdminAccounts::parse(accs)?;
+    let guardian: Pubkey = data.get(0..32).ok_or(ProgramError::InvalidInstructionData)?.try_into().unwrap();
+
+    let st = load_vault_mut(program_id, vault_state)?;
+    require_admin(st, admin.key)?;
+    st.guardian = guardian;
+    Ok(())
+}
+
+// Registers the vault depositors may move to with MigratePosition, e.g. a v2 deployment
+// whose state this program cannot read. The successor must accept this program's
+// Deposit instruction. A default successor turns migration off.
+// data: [successor_program:[u8;32], successor:[u8;32]]
+fn op_set_successor(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let AdminAccounts { vault_state, admin } = AdminAccounts::parse(accs)?;
+    if data.len() < 64 { return Err(ProgramError::InvalidInstructionData) }
+    let st = load_vault_mut(program_id, vault_state)?;
+    require_admin(st, admin.key)?;
+    let successor: Pubkey = data[32..64].try_into().unwrap();
+    if successor == *vault_state.key { return Err(ProgramError::InvalidArgument) }
+    st.successor_program = data[0..32].try_into().unwrap();
+    st.successor = successor;
+    Ok(())
+}
+
+// Opt-in move to the registered successor in one instruction: burns the user's shares
+// as Withdraw does (same pause bit, bootstrap lock and rounding), then deposits exactly
+// the USDC that paid into the successor through its Deposit, with the user's signature.
+// The user names the successor program and vault_state they expect, so a successor
+// changed after they signed fails with SuccessorMismatch instead of receiving their funds.
+// data: [shares:u64 (0 = whole balance), usdc_decimals:u8, successor_program:[u8;32], successor:[u8;32], min_shares_out:u64?]
+fn op_migrate_position(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let WithdrawAccounts { vault_state, user, user_usdc_ata, user_share_ata, token_program, usdc_mint, .. } = WithdrawAccounts::parse(accs)?;
+    let MigrateAccounts {
+        successor_program, successor_state, successor_mint_authority, successor_usdc_ata, successor_share_mint, user_successor_share_ata,
+    } = MigrateAccounts::parse(accs)?;
+    if data.len() < 73 { return Err(ProgramError::InvalidInstructionData) }
+    let shares = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let usdc_decimals = data[8];
+    let expected_program: Pubkey = data[9..41].try_into().unwrap();
+    let expected: Pubkey = data[41..73].try_into().unwrap();
+    let min_shares_out = data.get(73..81).map_or(0, |m| u64::from_le_bytes(m.try_into().unwrap()));
+
+    let st = load_vault(program_id, vault_state)?;
+    if st.successor == Pubkey::default() { return Err(VaultError::NoSuccessor.into()) }
+    if st.successor != expected || st.successor_program != expected_program { return Err(VaultError::SuccessorMismatch.into()) }
+    if *successor_state.key != st.successor || *successor_program.key != st.successor_program {
+        return Err(VaultError::NoSuccessor.into())
+    }
+    let shares = if shares == 0 { token_account_amount(user_share_ata)? } else { shares };
+    if shares == 0 { return Err(ProgramError::InsufficientFunds) }
+
+    let before = token_account_amount(user_usdc_ata)?;
+    withdraw(program_id, accs, usdc_decimals, WithdrawSize::Shares(shares), None)?;
+    let amount = token_account_amount(user_usdc_ata)?.checked_sub(before).ok_or(VaultError::MathOverflow)?;
+
     {
-        let metas = vec![
-            AccountMeta::new(*a5.key, false),
-            AccountMeta::new_readonly(*a8.key, false),
-            AccountMeta::new(*a6.key, false),
-            AccountMeta::new_readonly(*a1.key, false),
+        let metas = [
+            AccountMeta::writable(successor_state.key), AccountMeta::readonly(successor_mint_authority.key), AccountMeta::readonly_signer(user.key),
+            AccountMeta::writable(user_usdc_ata.key), AccountMeta::writable(successor_usdc_ata.key), AccountMeta::writable(successor_share_mint.key),
+            AccountMeta::writable(user_successor_share_ata.key), AccountMeta::readonly(token_program.key), AccountMeta::readonly(usdc_mint.key),
         ];
-        let ix = ix(a7, data_transfer_checked(claim, 6).to_vec(), metas);
-        let signer = vault_signer(st);
-        cpi::invoke_signed(&ix, &[a7,a5,a8,a6,a1], &[&signer])?;
+        let mut data = [0u8; 18];
+        data[0] = OP_DEPOSIT;
+        data[1..9].copy_from_slice(&amount.to_le_bytes());
+        data[9] = usdc_decimals;
+        data[10..18].copy_from_slice(&min_shares_out.to_le_bytes());
+        cpi::invoke(&ix(successor_program, &data, &metas), &[
+            successor_state, successor_mint_authority, user, user_usdc_ata, successor_usdc_ata, successor_share_mint,
+            user_successor_share_ata, token_program, usdc_mint,
+        ])?;
+    }
+    let clock = Clock::get()?;
+    vlog!("position migrated shares={} amount={}", shares, amount);
+    emit(EVT_POSITION_MIGRATED, &clock, &PositionMigratedEvent { user: *user.key, successor: expected, amount });
+    Ok(())
+}
+
+// data: [liveness_slots:u64]   (0 turns the watchdog off)
+fn op_set_liveness_window(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let AdminAccounts { vault_state, admin } = AdminAccounts::parse(accs)?;
+    let slots = u64::from_le_bytes(data.get(0..8).ok_or(ProgramError::InvalidInstructionData)?.try_into().unwrap());
+
+    let st = load_vault_mut(program_id, vault_state)?;
+    require_admin(st, admin.key)?;
+    // the window runs from now, so turning it on never trips a vault straight away
+    st.last_activity_slot = st.last_activity_slot.max(Clock::get()?.slot);
+    st.liveness_slots = slots;
+    Ok(())
+}
+
+// Anyone may call once the operator has been silent past the liveness window: deposits
+// pause and the withdraw bit clears, so depositors of an abandoned vault can always
+// leave. Only the admin's SetPauseFlags undoes it.
+// data: []
+fn op_trip_watchdog(program_id: &Pubkey, accs: &[AccountInfo]) -> ProgramResult {
+    let AdminAccounts { vault_state, admin: caller } = AdminAccounts::parse(accs)?;
+    let st = load_vault_mut(program_id, vault_state)?;
+    let clock = Clock::get()?;
+    if !st.operator_silent(clock.slot) { return Err(VaultError::OperatorActive.into()) }
+    let flags = (st.flags | PAUSE_DEPOSIT) & !PAUSE_WITHDRAW;
+    vlog!("watchdog tripped, last activity slot={}", st.last_activity_slot);
+    emit(EVT_PAUSE_FLAGS_SET, &clock, &PauseFlagsSetEvent { authority: *caller.key, old_flags: st.flags, new_flags: flags });
+    st.flags = flags;
+    Ok(())
+}
+
+// Withdraw-only mode: deposits, donations, claims and settles stop, withdrawals continue
+// at the current pps even if PAUSE_WITHDRAW is set. Leaving it takes the admin's
+// SetPauseFlags.
+// data: []
+fn op_enter_emergency(program_id: &Pubkey, accs: &[AccountInfo]) -> ProgramResult {
+    let AdminAccounts { vault_state, admin: authority } = AdminAccounts::parse(accs)?;
+    let st = load_vault_mut(program_id, vault_state)?;
+    let is_guardian = st.guardian != Pubkey::default() && *authority.key == st.guardian;
+    if !is_guardian && *authority.key != st.admin { return Err(VaultError::NotGuardian.into()) }
+    let clock = Clock::get()?;
+    let flags = st.flags | PAUSE_EMERGENCY;
+    emit(EVT_PAUSE_FLAGS_SET, &clock, &PauseFlagsSetEvent { authority: *authority.key, old_flags: st.flags, new_flags: flags });
+    st.flags = flags;
+    vlog!("emergency withdraw-only mode on");
+    Ok(())
+}
+
+// First half of an admin rotation; authority does not move until the new key accepts.
+// Proposing the default pubkey cancels a pending proposal.
+// data: [new_admin: Pubkey]
+fn op_propose_admin(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let AdminAccounts { vault_state, admin } = AdminAccounts::parse(accs)?;
+    let new_admin: Pubkey = data.get(0..32).ok_or(ProgramError::InvalidInstructionData)?.try_into().unwrap();
+
+    let st = load_vault_mut(program_id, vault_state)?;
+    require_admin(st, admin.key)?;
+    st.pending_admin = new_admin;
+    Ok(())
+}
+
+// data: []
+fn op_accept_admin(program_id: &Pubkey, accs: &[AccountInfo]) -> ProgramResult {
+    let AcceptAdminAccounts { vault_state, new_admin } = AcceptAdminAccounts::parse(accs)?;
+    let st = load_vault_mut(program_id, vault_state)?;
+    if st.pending_admin == Pubkey::default() || *new_admin.key != st.pending_admin {
+        return Err(VaultError::NotPendingAdmin.into())
+    }
+    let clock = Clock::get()?;
+    // pin the PDA seed before the admin it was derived from goes away
+    st.seed_admin = *vault_seed_admin(st);
+    let old_admin = st.admin;
+    st.admin = *new_admin.key;
+    st.pending_admin = Pubkey::default();
+    emit(EVT_ADMIN_TRANSFERRED, &clock, &AdminTransferredEvent { old_admin, new_admin: st.admin });
+    Ok(())
+}
+
+// data: [threshold:u8, count:u8, signers: [Pubkey; count]]   (count <= MAX_ROOT_SIGNERS, threshold <= count)
+fn op_set_root_signers(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let AdminAccounts { vault_state, admin } = AdminAccounts::parse(accs)?;
+    if data.len() < 2 { return Err(ProgramError::InvalidInstructionData) }
+    let threshold = data[0];
+    let count = data[1] as usize;
+    if count > MAX_ROOT_SIGNERS || threshold as usize > count || data.len() < 2 + count * 32 {
+        return Err(ProgramError::InvalidInstructionData)
+    }
+
+    let st = load_vault_mut(program_id, vault_state)?;
+    require_admin(st, admin.key)?;
+    let mut list = [Pubkey::default(); MAX_ROOT_SIGNERS];
+    for i in 0..count {
+        let off = 2 + i * 32;
+        list[i].copy_from_slice(&data[off..off + 32]);
+        // duplicates would let one key count twice toward the threshold
+        if list[..i].contains(&list[i]) { return Err(ProgramError::InvalidInstructionData) }
+    }
+    st.root_signers = list;
+    st.root_signers_len = count as u8;
+    st.root_threshold = threshold;
+    Ok(())
+}
+
+// data: [pps_ceiling:u128]   (must be >= current pps)
+fn op_set_pps_ceiling(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let AdminAccounts { vault_state, admin } = AdminAccounts::parse(accs)?;
+    if data.len() < 16 { return Err(ProgramError::InvalidInstructionData) }
+    let ceiling = u128::from_le_bytes(data[0..16].try_into().unwrap());
+
+    // plain load: this is how an admin un-halts a vault whose pps outgrew the old ceiling
+    let st = load_mut::<VaultState>(program_id, vault_state)?;
+    check_vault_state_key(program_id, vault_state, st)?;
+    require_admin(st, admin.key)?;
+    if ceiling < st.pps || st.pps < math::PPS_FLOOR { return Err(ProgramError::InvalidInstructionData) }
+    st.pps_ceiling = ceiling;
+    Ok(())
+}
+
+// data: [deposit_cap:u64]   (0 removes the cap; lowering it below current assets only blocks new deposits)
+fn op_set_deposit_cap(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let AdminAccounts { vault_state, admin } = AdminAccounts::parse(accs)?;
+    let cap = u64::from_le_bytes(data.get(0..8).ok_or(ProgramError::InvalidInstructionData)?.try_into().unwrap());
+
+    let st = load_vault_mut(program_id, vault_state)?;
+    require_admin(st, admin.key)?;
+    st.deposit_cap = cap;
+    Ok(())
+}
+
+// data: [user_deposit_cap:u64]   (0 removes it; Deposit then no longer needs the position accounts)
+fn op_set_user_deposit_cap(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let AdminAccounts { vault_state, admin } = AdminAccounts::parse(accs)?;
+    let cap = u64::from_le_bytes(data.get(0..8).ok_or(ProgramError::InvalidInstructionData)?.try_into().unwrap());
+
+    let st = load_vault_mut(program_id, vault_state)?;
+    require_admin(st, admin.key)?;
+    st.user_deposit_cap = cap;
+    Ok(())
+}
+
+// total_shares drives pps and withdraw math while the mint's supply is what holders
+// actually own; the two are updated by separate code paths (state write, then a
+// MintTo/Burn CPI), so a bug in either shows up as drift.
+fn check_share_supply(st: &VaultState, supply: u64) -> ProgramResult {
+    if st.total_shares != supply as u128 { return Err(VaultError::ShareSupplyDrift.into()) }
+    Ok(())
+}
+
+// Permissionless crank/monitor: fails with ShareSupplyDrift unless the share mint's
+// supply equals total_shares. Simulate it to alert without paying fees.
+// data: []
+fn op_check_share_supply(program_id: &Pubkey, accs: &[AccountInfo]) -> ProgramResult {
+    let ShareSupplyAccounts { vault_state, share_mint } = ShareSupplyAccounts::parse(accs)?;
+    let st = load_vault(program_id, vault_state)?;
+    if *share_mint.key != st.share_mint { return Err(ProgramError::InvalidArgument) }
+    check_share_supply(st, mint_supply(share_mint)?)
+}
+
+// Admin repair after drift: the minted tokens are what holders can redeem, so
+// total_shares is reset to the mint supply (ShareSupplyRepaired event).
+// data: []
+fn op_repair_share_supply(program_id: &Pubkey, accs: &[AccountInfo]) -> ProgramResult {
+    let RepairShareSupplyAccounts { vault_state, admin, share_mint } = RepairShareSupplyAccounts::parse(accs)?;
+    let st = load_vault_mut(program_id, vault_state)?;
+    require_admin(st, admin.key)?;
+    if *share_mint.key != st.share_mint { return Err(ProgramError::InvalidArgument) }
+    let supply = mint_supply(share_mint)? as u128;
+    if st.total_shares == supply { return Ok(()) }
+    let clock = Clock::get()?;
+    emit(EVT_SHARE_SUPPLY_REPAIRED, &clock, &ShareSupplyRepairedEvent { old_total_shares: st.total_shares, new_total_shares: supply });
+    vlog!("total_shares repaired {} -> {}", logfmt::dec(st.total_shares).as_str(), supply);
+    st.total_shares = supply;
+    Ok(())
+}
+
+// Anyone may fold buffered base into pps instead of waiting for the next deposit. With
+// the tip accounts the caller is paid SETTLE_TIP_BPS of the folded base (capped at
+// SETTLE_TIP_MAX) out of that base, so the tip never touches existing holders' value.
+// data: []
+fn op_settle(program_id: &Pubkey, accs: &[AccountInfo]) -> ProgramResult {
+    let SettleAccounts { vault_state, tip } = SettleAccounts::parse(accs)?;
+    let st = load_vault_mut(program_id, vault_state)?;
+    // pps stays frozen in emergency mode so every exit gets the same price
+    check_not_paused(st, PAUSE_EMERGENCY)?;
+    let clock = Clock::get()?;
+    if st.buffered_base == 0 || st.total_shares == 0 { return Err(VaultError::NothingToSettle.into()) }
+
+    let tip_amount = match &tip {
+        Some(_) => (st.buffered_base as u128 * SETTLE_TIP_BPS as u128 / BPS_DENOM as u128).min(SETTLE_TIP_MAX as u128) as u64,
+        None => 0,
+    };
+    st.buffered_base -= tip_amount;
+    let folded = settle(st, &clock)?;
+
+    let mut caller_usdc_ata = Pubkey::default();
+    if let Some(SettleTipAccounts { vault_pda, vault_usdc_ata, caller_usdc_ata: dest, token_program, usdc_mint }) = tip {
+        if *vault_pda.key != st.vault_pda { return Err(ProgramError::InvalidSeeds) }
+        check_vault_mint(st, usdc_mint, token_program)?;
+        check_vault_usdc(st, vault_usdc_ata, token_program)?;
+        caller_usdc_ata = *dest.key;
+        if tip_amount > 0 {
+            let metas = metas_transfer_checked(vault_usdc_ata.key, usdc_mint.key, dest.key, vault_pda.key);
+            let data = data_transfer_checked(tip_amount, mint_decimals(usdc_mint)?);
+            let bump = [st.vault_bump];
+            let seeds = vault_seeds(st, &bump);
+            cpi::invoke_signed(&ix(token_program, &data, &metas), &[vault_usdc_ata,usdc_mint,dest,vault_pda], &[Signer::from(&seeds)])?;
+        }
     }
 
-    // mark claimed
-    bm.words[byte] |= mask;
+    vlog!("settle folded={} tip={} pps={}", folded, tip_amount, logfmt::ray(st.pps).as_str());
+    emit(EVT_SETTLED, &clock, &SettledEvent { caller_usdc_ata, folded_base: folded, tip: tip_amount, pps: st.pps });
+    Ok(())
+}
+
+// Copies the program's current upgrade authority out of its ProgramData account so
+// integrators can read rug risk from VaultState alone.
+// data: []
+fn op_record_upgrade_authority(program_id: &Pubkey, accs: &[AccountInfo]) -> ProgramResult {
+    let RecordUpgradeAuthorityAccounts { vault_state, program_data } = RecordUpgradeAuthorityAccounts::parse(accs)?;
+    let (expected, _) = find_program_address(&[program_id.as_ref()], &BPF_LOADER_UPGRADEABLE_ID);
+    if *program_data.key != expected { return Err(ProgramError::InvalidSeeds) }
+    if *program_data.owner != BPF_LOADER_UPGRADEABLE_ID { return Err(ProgramError::IncorrectProgramId) }
+
+    let (status, authority) = {
+        let d = program_data.try_borrow_data()?;
+        if d.len() < PROGRAM_DATA_AUTHORITY_OFFSET + 1
+            || u32::from_le_bytes(d[0..4].try_into().unwrap()) != PROGRAM_DATA_TAG
+        {
+            return Err(ProgramError::InvalidAccountData)
+        }
+        match d[PROGRAM_DATA_AUTHORITY_OFFSET] {
+            0 => (UPGRADE_AUTHORITY_IMMUTABLE, Pubkey::default()),
+            1 => {
+                let a = d.get(PROGRAM_DATA_AUTHORITY_OFFSET + 1..PROGRAM_DATA_AUTHORITY_OFFSET + 33)
+                    .ok_or(ProgramError::InvalidAccountData)?;
+                (UPGRADE_AUTHORITY_HELD, a.try_into().unwrap())
+            }
+            _ => return Err(ProgramError::InvalidAccountData),
+        }
+    };
+
+    let st = load_vault_mut(program_id, vault_state)?;
+    let clock = Clock::get()?;
+    st.upgrade_authority = authority;
+    st.upgrade_authority_slot = clock.slot;
+    st.upgrade_authority_status = status;
+    emit(EVT_UPGRADE_AUTHORITY_RECORDED, &clock, &UpgradeAuthorityRecordedEvent { authority, status, _pad: [0; 7] });
+    Ok(())
+}
+
+// data: [burn_dust:u8]
+fn op_set_burn_dust(program_id: &Pubkey, accs: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let AdminAccounts { vault_state, admin } = AdminAccounts::parse(accs)?;
+    let burn_dust = *data.first().ok_or(ProgramError::InvalidInstructionData)?;
+    if burn_dust > 1 { return Err(ProgramError::InvalidInstructionData) }
+
+    let st = load_vault_mut(program_id, vault_state)?;
+    require_admin(st, admin.key)?;
+    st.burn_dust = burn_dust;
     Ok(())
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use math::RAY;
+
+    fn vault() -> VaultState {
+        VaultState { pps: RAY, ..VaultState::zeroed() }
+    }
+
+    #[test]
+    fn check_pps_bounds() {
+        let mut st = VaultState { pps_ceiling: 2 * RAY, ..vault() };
+        assert_eq!(check_pps(&st), Ok(()));
+        st.pps = 2 * RAY;
+        assert_eq!(check_pps(&st), Ok(()));
+        st.pps = 2 * RAY + 1;
+        assert_eq!(check_pps(&st), Err(VaultError::CorruptedState.into()));
+        st.pps = math::PPS_FLOOR - 1;
+        assert_eq!(check_pps(&st), Err(VaultError::CorruptedState.into()));
+    }
+
+    #[test]
+    fn bootstrap_requires_minimum_deposit() {
+        let mut st = vault();
+        assert_eq!(
+            bootstrap(&mut st, BOOTSTRAP_MIN_DEPOSIT - 1, 1, 0),
+            Err(VaultError::BootstrapDepositTooSmall.into())
+        );
+        assert_eq!(bootstrap(&mut st, BOOTSTRAP_MIN_DEPOSIT, BOOTSTRAP_MIN_DEPOSIT as u128, 10), Ok(()));
+        assert_eq!(st.bootstrap_locked_shares, BOOTSTRAP_LOCKED_SHARES.min(BOOTSTRAP_MIN_DEPOSIT));
+        assert_eq!(st.bootstrap_unlock_slot, 10 + BOOTSTRAP_LOCK_SLOTS);
+    }
+
+    #[test]
+    fn bootstrap_lock_holds_until_unlock_slot() {
+        let mut st = vault();
+        let minted = 5 * BOOTSTRAP_LOCKED_SHARES;
+        bootstrap(&mut st, minted, minted as u128, 100).unwrap();
+        st.total_shares = minted as u128;
+
+        let free = minted - BOOTSTRAP_LOCKED_SHARES;
+        assert_eq!(check_bootstrap_lock(&st, free, 100), Ok(()));
+        assert_eq!(check_bootstrap_lock(&st, free + 1, 100), Err(VaultError::BootstrapSharesLocked.into()));
+        assert_eq!(check_bootstrap_lock(&st, minted, st.bootstrap_unlock_slot), Ok(()));
+    }
+
+    #[test]
+    fn roles_fail_with_their_own_errors() {
+        let (admin, operator) = ([1u8; 32], [2u8; 32]);
+        let st = VaultState { admin, operator, ..vault() };
+        assert_eq!(require_admin(&st, &admin), Ok(()));
+        assert_eq!(require_operator(&st, &operator), Ok(()));
+        // the two roles do not stand in for each other
+        assert_eq!(require_admin(&st, &operator), Err(VaultError::NotAdmin.into()));
+        assert_eq!(require_operator(&st, &admin), Err(VaultError::NotOperator.into()));
+    }
+
+    #[test]
+    fn pause_bits_are_independent() {
+        let st = VaultState { flags: PAUSE_DEPOSIT | PAUSE_DONATE, ..vault() };
+        assert_eq!(check_not_paused(&st, PAUSE_DEPOSIT), Err(VaultError::Paused.into()));
+        assert_eq!(check_not_paused(&st, PAUSE_DONATE), Err(VaultError::Paused.into()));
+        assert_eq!(check_not_paused(&st, PAUSE_WITHDRAW), Ok(()));
+        assert_eq!(check_not_paused(&st, PAUSE_CLAIM), Ok(()));
+    }
+
+    #[test]
+    fn emergency_mode_is_withdraw_only() {
+        let st = VaultState { flags: PAUSE_EMERGENCY | PAUSE_WITHDRAW, ..vault() };
+        assert_eq!(check_not_paused(&st, PAUSE_WITHDRAW), Ok(()));
+        for bit in [PAUSE_DEPOSIT, PAUSE_DONATE, PAUSE_CLAIM, PAUSE_EMERGENCY] {
+            assert_eq!(check_not_paused(&st, bit), Err(VaultError::Paused.into()));
+        }
+        assert_eq!(check_not_paused(&vault(), PAUSE_EMERGENCY), Ok(()));
+    }
+
+    #[test]
+    fn deposit_cap_bounds_total_assets() {
+        let mut st = VaultState { total_shares: 1_000, buffered_base: 500, ..vault() };
+        assert_eq!(check_deposit_cap(&st), Ok(()));
+        st.deposit_cap = 1_500;
+        assert_eq!(check_deposit_cap(&st), Ok(()));
+        st.total_shares += 1;
+        assert_eq!(check_deposit_cap(&st), Err(VaultError::DepositCapExceeded.into()));
+    }
+
+    #[test]
+    fn user_deposit_cap_is_cumulative() {
+        let mut st = vault();
+        let mut p = UserPosition { deposited: 10_000, ..UserPosition::zeroed() };
+        assert_eq!(check_user_deposit_cap(&st, &p), Ok(()));
+        st.user_deposit_cap = 10_000;
+        assert_eq!(check_user_deposit_cap(&st, &p), Ok(()));
+        p.deposited += 1;
+        assert_eq!(check_user_deposit_cap(&st, &p), Err(VaultError::UserDepositCapExceeded.into()));
+    }
+
+    #[test]
+    fn position_epoch_weight_resets_each_epoch() {
+        let clock = |epoch, slot| Clock { slot, epoch_start_timestamp: 0, epoch, leader_schedule_epoch: epoch, unix_timestamp: 0 };
+        let mut p = UserPosition::zeroed();
+        p.record_deposit(1_000, 100, &clock(5, 1_000)).unwrap();
+        p.record_deposit(500, 50, &clock(5, 1_200)).unwrap();
+        assert_eq!((p.epoch, p.epoch_shares, p.epoch_share_slots, p.last_deposit_slot), (5, 150, 160_000, 1_200));
+        // epoch ends at slot 2_000: 100 * 1_000 + 50 * 800
+        assert_eq!(p.epoch_shares as u128 * 2_000 - p.epoch_share_slots, 140_000);
+        p.record_deposit(100, 10, &clock(6, 2_100)).unwrap();
+        assert_eq!((p.epoch, p.epoch_shares, p.epoch_share_slots), (6, 10, 21_000));
+        assert_eq!(p.deposited, 1_600);
+    }
+
+    #[test]
+    fn share_supply_must_match_total_shares() {
+        let st = VaultState { total_shares: 1_000, ..vault() };
+        assert_eq!(check_share_supply(&st, 1_000), Ok(()));
+        assert_eq!(check_share_supply(&st, 999), Err(VaultError::ShareSupplyDrift.into()));
+        assert_eq!(check_share_supply(&VaultState { total_shares: u64::MAX as u128 + 1, ..vault() }, 0), Err(VaultError::ShareSupplyDrift.into()));
+    }
+
+    #[test]
+    fn exact_out_withdraw_rounds_shares_up() {
+        let pps = RAY + RAY / 4; // 1.25 USDC per share
+        let exact = |amount, max_shares_in| withdraw_amounts(WithdrawSize::ExactAssets { amount, max_shares_in }, pps);
+        assert_eq!(exact(100, u64::MAX), Ok((80, 100)));
+        // 80.8 shares round up to 81, which is worth 101.25, never less than what is paid out
+        assert_eq!(exact(101, u64::MAX), Ok((81, 101)));
+        assert!(math::assets_for_shares(81, pps).unwrap() >= 101);
+        assert_eq!(exact(101, 80), Err(VaultError::SlippageExceeded.into()));
+        assert_eq!(withdraw_amounts(WithdrawSize::Shares(81), pps), Ok((81, 101)));
+    }
+
+    #[test]
+    fn discriminators_lead_every_layout() {
+        use core::mem::offset_of;
+        assert_eq!(
+            [offset_of!(VaultState, discriminator), offset_of!(BoostDistributor, discriminator), offset_of!(ClaimBitmap256, discriminator),
+             offset_of!(UserPrefs, discriminator), offset_of!(UserPosition, discriminator), offset_of!(VaultRegistry, discriminator),
+             offset_of!(RegistryPage, discriminator), offset_of!(EpochReport, discriminator)],
+            [0; 8],
+        );
+        let tags = [
+            VaultState::DISCRIMINATOR, BoostDistributor::DISCRIMINATOR, ClaimBitmap256::DISCRIMINATOR,
+            UserPrefs::DISCRIMINATOR, UserPosition::DISCRIMINATOR, VaultRegistry::DISCRIMINATOR, RegistryPage::DISCRIMINATOR,
+            EpochReport::DISCRIMINATOR,
+        ];
+        for (i, t) in tags.iter().enumerate() {
+            assert!(tags[i + 1..].iter().all(|u| u != t));
+        }
+    }
+
+    // sdk/js/src/accounts.ts copies these into ACCOUNT_LEN; update both when a layout changes
+    #[test]
+    fn account_lens_are_pinned() {
+        assert_eq!(
+            [
+                VaultState::LEN, BoostDistributor::LEN, ClaimBitmap256::LEN, UserPrefs::LEN, UserPosition::LEN, VaultRegistry::LEN,
+                RegistryPage::LEN, EpochReport::LEN,
+            ],
+            [896, 208, 41, 80, 144, 24, 2064, 112],
+        );
+    }
+
+    #[test]
+    fn claim_page_splits_index_by_page_size() {
+        // distributors posted before paging have no page size and use 256-bit pages
+        let legacy = BoostDistributor::zeroed();
+        assert_eq!(legacy.claim_page(255), (0, 255));
+        assert_eq!(legacy.claim_page(256), (1, 0));
+        let bd = BoostDistributor { bitmap_page_size: CLAIM_PAGE_BITS as u16, bitmap_page_count: 3, ..BoostDistributor::zeroed() };
+        assert_eq!(bd.claim_page(0), (0, 0));
+        assert_eq!(bd.claim_page(513), (2, 1));
+        assert_eq!(CLAIM_PAGE_BITS, 256);
+    }
+
+    #[test]
+    fn claim_pages_cover_largest_tree() {
+        // PostRoot accepts up to MAX_CLAIM_PAGES pages: 65_536 leaves per epoch
+        let leaves = MAX_CLAIM_PAGES * CLAIM_PAGE_BITS;
+        assert_eq!(leaves, 65_536);
+        assert_eq!(leaves.div_ceil(CLAIM_PAGE_BITS), MAX_CLAIM_PAGES);
+        let bd = BoostDistributor {
+            leaf_count: leaves, bitmap_page_size: CLAIM_PAGE_BITS as u16, bitmap_page_count: MAX_CLAIM_PAGES as u16,
+            ..BoostDistributor::zeroed()
+        };
+        // the last leaf is the last bit of the last page, and fits the u16 page number
+        assert_eq!(bd.claim_page(leaves - 1), ((MAX_CLAIM_PAGES - 1) as u16, CLAIM_PAGE_BITS - 1));
+    }
+
+    #[test]
+    fn claims_close_at_expiry_slot() {
+        let bd = BoostDistributor { epoch: 3, total_weight: 1, leaf_count: 1, expiry_slot: 100, ..BoostDistributor::zeroed() };
+        assert!(!bd.claims_expired(99));
+        assert!(bd.claims_expired(100));
+        // roots posted before expiry existed stay claimable
+        assert!(!BoostDistributor { expiry_slot: 0, ..bd }.claims_expired(u64::MAX));
+
+        let mut data = [0u8; 29];
+        data[0..8].copy_from_slice(&3u64.to_le_bytes());
+        let err = verify_claim(&bd, &ClaimBitmap256::zeroed(), &Pubkey::default(), &data, 100).err();
+        assert_eq!(err, Some(VaultError::ClaimWindowClosed.into()));
+    }
+
+    #[test]
+    fn claims_stop_at_total_weight() {
+        let mut bd = BoostDistributor { total_weight: 10, boost_total: 1_000, ..BoostDistributor::zeroed() };
+        let mut bm = ClaimBitmap256::zeroed();
+        let claim = |weight, byte| VerifiedClaim { epoch: 0, index: 0, weight, amount: 0, byte, mask: 1 };
+        assert!(record_claim(&mut bd, &mut bm, &claim(6, 0)).is_ok());
+        // the tree's leaves add up to more than total_weight
+        assert_eq!(record_claim(&mut bd, &mut bm, &claim(5, 1)).err(), Some(VaultError::WeightSumMismatch.into()));
+        assert_eq!((bd.claimed_weight, bm.words[1]), (6, 0));
+        assert!(record_claim(&mut bd, &mut bm, &claim(4, 1)).is_ok());
+        assert_eq!(bd.claimed_weight, 10);
+    }
+
+    #[test]
+    fn epoch_settles_once_clawed_back() {
+        let bd = BoostDistributor { boost_total: 1_000, claimed_total: 600, expiry_slot: 100, ..BoostDistributor::zeroed() };
+        assert!(!bd.settled(99));
+        assert!(!bd.settled(100));
+        assert!(BoostDistributor { clawed_back: 400, ..bd }.settled(100));
+        // fully claimed epochs have nothing to claw back but still wait for expiry
+        let bd = BoostDistributor { claimed_total: 1_000, ..bd };
+        assert!(!bd.settled(99));
+        assert!(bd.settled(100));
+        assert!(!BoostDistributor { expiry_slot: 0, ..bd }.settled(u64::MAX));
+    }
+
+    fn at(slot: u64) -> Clock {
+        Clock { slot, epoch_start_timestamp: 0, epoch: 0, leader_schedule_epoch: 0, unix_timestamp: 0 }
+    }
+
+    #[test]
+    fn settle_folds_buffer_only_with_shares() {
+        let mut st = VaultState { buffered_base: 500, ..vault() };
+        assert_eq!(settle(&mut st, &at(7)), Ok(0));
+        assert_eq!((st.pps, st.buffered_base), (RAY, 500));
+
+        st.total_shares = 1_000;
+        assert_eq!(settle(&mut st, &at(7)), Ok(500));
+        assert_eq!((st.pps, st.buffered_base, st.last_settle_slot), (RAY + RAY / 2, 0, 7));
+        assert_eq!(settle(&mut st, &at(8)), Ok(0));
+        assert_eq!(st.last_settle_slot, 7);
+    }
+
+    #[test]
+    fn settle_is_frozen_in_emergency() {
+        let mut st = VaultState { buffered_base: 500, total_shares: 1_000, flags: PAUSE_EMERGENCY, ..vault() };
+        assert_eq!(settle(&mut st, &at(7)), Ok(0));
+        assert_eq!((st.pps, st.buffered_base, st.last_settle_slot), (RAY, 500, 0));
+        accrue_base(&mut st, 100, &at(8)).unwrap();
+        assert_eq!((st.pps, st.buffered_base), (RAY, 600));
+
+        st.flags = 0;
+        assert_eq!(settle(&mut st, &at(9)), Ok(600));
+        assert_eq!(st.pps, RAY + RAY * 6 / 10);
+    }
+
+    #[test]
+    fn accrue_base_buffers_until_there_are_shares() {
+        let mut st = vault();
+        accrue_base(&mut st, 300, &at(5)).unwrap();
+        assert_eq!((st.pps, st.buffered_base, st.last_activity_slot, st.last_settle_slot), (RAY, 300, 5, 0));
+
+        // the next accrual folds what was buffered along with it
+        st.total_shares = 1_000;
+        accrue_base(&mut st, 200, &at(6)).unwrap();
+        assert_eq!((st.pps, st.buffered_base, st.last_settle_slot), (RAY + RAY / 2, 0, 6));
+    }
+
+    #[test]
+    fn operator_silent_after_liveness_window() {
+        let st = VaultState { last_activity_slot: 100, ..vault() };
+        assert!(!st.operator_silent(u64::MAX));
+        let st = VaultState { liveness_slots: 50, ..st };
+        assert!(!st.operator_silent(150));
+        assert!(st.operator_silent(151));
+        // activity recorded ahead of the clock never counts as silence
+        assert!(!st.operator_silent(0));
+
+        let mut st = VaultState { buffered_base: 10, total_shares: 1_000, ..st };
+        settle(&mut st, &at(200)).unwrap();
+        assert!(!st.operator_silent(250));
+    }
+
+    #[test]
+    fn unpacks_token_accounts_and_mints() {
+        let (mint, owner) = ([1u8; 32], [2u8; 32]);
+        let mut d = [0u8; TOKEN_ACCOUNT_LEN + 1 + T22_TLV_HEADER];
+        d[0..32].copy_from_slice(&mint);
+        d[32..64].copy_from_slice(&owner);
+        d[64..72].copy_from_slice(&7u64.to_le_bytes());
+        // never initialized
+        assert_eq!(unpack_token_account(&d), Err(ProgramError::InvalidAccountData));
+        d[TOKEN_ACCOUNT_STATE_OFFSET] = TOKEN_ACCOUNT_INITIALIZED;
+        assert_eq!(unpack_token_account(&d), Ok(TokenAccount { mint, owner, amount: 7 }));
+        // Token-2022 extensions after the base layout don't change it
+        assert_eq!(unpack_token_account(&d[..TOKEN_ACCOUNT_LEN]), unpack_token_account(&d));
+        assert!(unpack_token_account(&d[..TOKEN_ACCOUNT_LEN - 1]).is_err());
+
+        let mut m = [0u8; MINT_LEN];
+        m[MINT_SUPPLY_OFFSET..MINT_SUPPLY_OFFSET + 8].copy_from_slice(&500u64.to_le_bytes());
+        m[MINT_DECIMALS_OFFSET] = 6;
+        assert!(unpack_mint(&m).is_err());
+        m[MINT_INITIALIZED_OFFSET] = 1;
+        assert_eq!(unpack_mint(&m), Ok(Mint { mint_authority: None, supply: 500, decimals: 6 }));
+        m[0] = 1;
+        m[4..36].copy_from_slice(&owner);
+        assert_eq!(unpack_mint(&m).unwrap().mint_authority, Some(owner));
+        m[0] = 2;
+        assert!(unpack_mint(&m).is_err());
+    }
 
+    // a Token-2022 mint with the given (type, value length) extensions; the zeroed
+    // remainder of the buffer reads as the uninitialized TLV tail
+    fn t22_mint(exts: &[(u16, usize)]) -> [u8; 512] {
+        let mut d = [0u8; 512];
+        d[T22_ACCOUNT_TYPE_OFFSET] = T22_ACCOUNT_TYPE_MINT;
+        let mut off = T22_ACCOUNT_TYPE_OFFSET + 1;
+        for &(ty, len) in exts {
+            d[off..off + 2].copy_from_slice(&ty.to_le_bytes());
+            d[off + 2..off + 4].copy_from_slice(&(len as u16).to_le_bytes());
+            off += T22_TLV_HEADER + len;
+        }
+        d
+    }
+
+    #[test]
+    fn token_account_len_follows_mint_extensions() {
+        const METADATA_POINTER: u16 = 18;
+        assert_eq!(account_len_for_mint(&[0u8; MINT_LEN]), TOKEN_ACCOUNT_LEN);
+        // mint-only extensions leave the account at the base size
+        assert_eq!(account_len_for_mint(&t22_mint(&[(METADATA_POINTER, 64)])), TOKEN_ACCOUNT_LEN);
+        // account type byte plus TransferFeeAmount
+        let fee = t22_mint(&[(METADATA_POINTER, 64), (EXT_TRANSFER_FEE_CONFIG, 108)]);
+        assert!(mint_has_extension(&fee, EXT_TRANSFER_FEE_CONFIG));
+        assert_eq!(account_len_for_mint(&fee), TOKEN_ACCOUNT_LEN + 1 + T22_TLV_HEADER + 8);
+        let fee_hook_pause = t22_mint(&[(EXT_TRANSFER_FEE_CONFIG, 108), (EXT_TRANSFER_HOOK, 64), (EXT_PAUSABLE, 33)]);
+        assert_eq!(account_len_for_mint(&fee_hook_pause), TOKEN_ACCOUNT_LEN + 1 + 3 * T22_TLV_HEADER + 8 + 1);
+        // an entry running past the data is not read
+        assert!(!mint_has_extension(&t22_mint(&[(EXT_TRANSFER_HOOK, 400)]), EXT_TRANSFER_HOOK));
+    }
+}
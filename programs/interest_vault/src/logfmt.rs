@@ -0,0 +1,65 @@
+// Stack formatting of u128 values for the verbose-logs profile. msg! has no u128
+// support, so pps and share counts would otherwise be logged as truncated u64 casts.
+// Nothing here allocates; the text lives in the returned buffer.
+
+use crate::math::RAY;
+
+const RAY_DIGITS: usize = 12;
+
+// u128::MAX has 39 digits; a RAY value adds the point
+pub struct Num {
+    buf: [u8; 40],
+    start: usize,
+}
+
+impl Num {
+    pub fn as_str(&self) -> &str {
+        // only ASCII digits and '.' are ever written
+        core::str::from_utf8(&self.buf[self.start..]).unwrap_or("?")
+    }
+
+    fn push_front(&mut self, b: u8) {
+        self.start -= 1;
+        self.buf[self.start] = b;
+    }
+
+    fn push_digits(&mut self, mut v: u128, min_digits: usize) {
+        let end = self.start;
+        while v > 0 || end - self.start < min_digits.max(1) {
+            self.push_front(b'0' + (v % 10) as u8);
+            v /= 10;
+        }
+    }
+}
+
+// plain decimal
+pub fn dec(v: u128) -> Num {
+    let mut n = Num { buf: [0; 40], start: 40 };
+    n.push_digits(v, 1);
+    n
+}
+
+// RAY-scaled fixed point (pps) as `int.fraction` with all 12 fraction digits
+pub fn ray(v: u128) -> Num {
+    let mut n = Num { buf: [0; 40], start: 40 };
+    n.push_digits(v % RAY, RAY_DIGITS);
+    n.push_front(b'.');
+    n.push_digits(v / RAY, 1);
+    n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_full_u128_range() {
+        assert_eq!(dec(0).as_str(), "0");
+        assert_eq!(dec(1_000_000).as_str(), "1000000");
+        assert_eq!(dec(u128::MAX).as_str(), "340282366920938463463374607431768211455");
+        assert_eq!(ray(RAY).as_str(), "1.000000000000");
+        assert_eq!(ray(RAY + RAY / 4).as_str(), "1.250000000000");
+        assert_eq!(ray(7).as_str(), "0.000000000007");
+        assert_eq!(ray(u128::MAX).as_str(), "340282366920938463463374607.431768211455");
+    }
+}
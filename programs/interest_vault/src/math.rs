@@ -0,0 +1,172 @@
+// Fixed-point vault math. Every helper is checked: `None` means the result does
+// not fit its type, and ops surface it as `VaultError::MathOverflow`.
+
+pub const RAY: u128 = 1_000_000_000_000; // 1e12 fixed point PPS
+pub const BPS_DENOM: u64 = 10_000;
+
+// Share and asset decimals may differ. pps is always raw asset units per raw share
+// (scaled by RAY), so only the starting pps depends on the decimals; every other
+// helper works in raw units unchanged.
+pub const MAX_DECIMALS: u8 = 18;
+pub const MAX_EXTRA_SHARE_DECIMALS: u8 = 6; // keeps the starting pps >= RAY / 1e6
+// lowest starting pps initial_pps can return; pps never decreases
+pub const PPS_FLOOR: u128 = RAY / 1_000_000;
+
+// pps at which one whole share is worth one whole asset unit
+pub fn initial_pps(share_decimals: u8, asset_decimals: u8) -> Option<u128> {
+    if share_decimals > MAX_DECIMALS || asset_decimals > MAX_DECIMALS { return None }
+    if share_decimals > asset_decimals + MAX_EXTRA_SHARE_DECIMALS { return None }
+    Some(RAY.checked_mul(10u128.pow(asset_decimals as u32))? / 10u128.pow(share_decimals as u32))
+}
+
+// shares minted for `amount` USDC at `pps` (rounded down)
+pub fn shares_for_assets(amount: u64, pps: u128) -> Option<u128> {
+    (amount as u128).checked_mul(RAY)?.checked_div(pps)
+}
+
+// shares that cover `amount` USDC at `pps` (rounded up); what an exact-out withdraw burns
+pub fn shares_for_assets_up(amount: u64, pps: u128) -> Option<u128> {
+    let num = (amount as u128).checked_mul(RAY)?;
+    if pps == 0 { return None }
+    Some(num.div_ceil(pps))
+}
+
+// USDC paid for `shares` at `pps` (rounded down)
+pub fn assets_for_shares(shares: u128, pps: u128) -> Option<u128> {
+    Some(shares.checked_mul(pps)? / RAY)
+}
+
+// pps increase from spreading `base` USDC over `total_shares`
+pub fn pps_delta(base: u64, total_shares: u128) -> Option<u128> {
+    mul_div(base as u128, RAY, total_shares)
+}
+
+// (boost, base) parts of a donation; boost_bps must be <= BPS_DENOM
+pub fn split_donation(amount: u64, boost_bps: u16) -> Option<(u64, u64)> {
+    if boost_bps as u64 > BPS_DENOM { return None }
+    let boost = (amount as u128 * boost_bps as u128 / BPS_DENOM as u128) as u64;
+    Some((boost, amount - boost))
+}
+
+// claimer's slice of an epoch's boost pool; weights may use the full u128 range
+pub fn claim_amount(boost_total: u64, weight: u128, total_weight: u128) -> Option<u64> {
+    u64::try_from(mul_div(boost_total as u128, weight, total_weight)?).ok()
+}
+
+// floor(a * b / d) through a 256-bit product, so only the quotient has to fit u128.
+// None when d == 0 or the quotient does not fit.
+pub fn mul_div(a: u128, b: u128, d: u128) -> Option<u128> {
+    if d == 0 { return None }
+    let (hi, lo) = mul_wide(a, b);
+    if hi == 0 { return Some(lo / d) }
+    if hi >= d { return None }
+    // restoring long division of hi:lo by d; rem < d holds between steps
+    let (mut rem, mut q) = (hi, 0u128);
+    for i in (0..128).rev() {
+        let carry = rem >> 127;
+        rem = (rem << 1) | ((lo >> i) & 1);
+        q <<= 1;
+        // with the carry the true remainder is >= 2^128 > d, and wrapping gives the exact difference
+        if carry != 0 || rem >= d {
+            rem = rem.wrapping_sub(d);
+            q |= 1;
+        }
+    }
+    Some(q)
+}
+
+// full 256-bit product as (high, low) halves
+fn mul_wide(a: u128, b: u128) -> (u128, u128) {
+    const LO: u128 = u64::MAX as u128;
+    let (a1, a0) = (a >> 64, a & LO);
+    let (b1, b0) = (b >> 64, b & LO);
+    let p00 = a0 * b0;
+    let p01 = a0 * b1;
+    let p10 = a1 * b0;
+    // middle column plus the carry out of the low word; stays below 2^66
+    let mid = (p00 >> 64) + (p01 & LO) + (p10 & LO);
+    let lo = (p00 & LO) | (mid << 64);
+    let hi = a1 * b1 + (p01 >> 64) + (p10 >> 64) + (mid >> 64);
+    (hi, lo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_donation_bounds() {
+        assert_eq!(split_donation(u64::MAX, 0), Some((0, u64::MAX)));
+        assert_eq!(split_donation(u64::MAX, 10_000), Some((u64::MAX, 0)));
+        assert_eq!(split_donation(u64::MAX, 5_000), Some((u64::MAX / 2, u64::MAX - u64::MAX / 2)));
+        assert_eq!(split_donation(1, 9_999), Some((0, 1)));
+        assert_eq!(split_donation(100, 10_001), None);
+    }
+
+    #[test]
+    fn pps_delta_bounds() {
+        assert_eq!(pps_delta(u64::MAX, 1), Some(u64::MAX as u128 * RAY));
+        assert_eq!(pps_delta(u64::MAX, u128::MAX), Some(0));
+        assert_eq!(pps_delta(1, 0), None);
+    }
+
+    #[test]
+    fn share_conversions_bounds() {
+        assert_eq!(shares_for_assets(u64::MAX, RAY), Some(u64::MAX as u128));
+        assert_eq!(shares_for_assets(u64::MAX, 0), None);
+        assert_eq!(assets_for_shares(u64::MAX as u128, RAY), Some(u64::MAX as u128));
+        assert_eq!(assets_for_shares(u128::MAX, RAY), None);
+        assert_eq!(assets_for_shares(u128::MAX, 1), Some(u128::MAX / RAY));
+    }
+
+    #[test]
+    fn claim_amount_bounds() {
+        assert_eq!(claim_amount(u64::MAX, 1, 1), Some(u64::MAX));
+        assert_eq!(claim_amount(u64::MAX, 1, 2), Some(u64::MAX / 2));
+        assert_eq!(claim_amount(u64::MAX, 2, 1), None); // payout above u64
+        // the u128 product overflows, but the 256-bit intermediate does not
+        assert_eq!(claim_amount(u64::MAX, u128::MAX, u128::MAX), Some(u64::MAX));
+        assert_eq!(claim_amount(u64::MAX, u128::MAX / 2, u128::MAX), Some(u64::MAX / 2));
+        assert_eq!(claim_amount(1_000, u128::MAX / 3, u128::MAX), Some(333));
+        assert_eq!(claim_amount(1, 1, 0), None);
+    }
+
+    #[test]
+    fn mul_div_bounds() {
+        assert_eq!(mul_div(u128::MAX, u128::MAX, u128::MAX), Some(u128::MAX));
+        assert_eq!(mul_div(u128::MAX, u128::MAX, u128::MAX - 1), None); // quotient above u128
+        assert_eq!(mul_div(u128::MAX, 2, 4), Some(u128::MAX / 2));
+        assert_eq!(mul_div(u128::MAX, 3, 3), Some(u128::MAX));
+        assert_eq!(mul_div(1 << 100, 1 << 100, 1 << 90), Some(1 << 110));
+        assert_eq!(mul_div(u128::MAX, u128::MAX, 1), None);
+        assert_eq!(mul_div(7, 3, 2), Some(10)); // floors
+        assert_eq!(mul_div(1, 1, 0), None);
+        // (2^128 - 1)^2 / 2^127 = 2^129 - 4 + 2^-127, which does not fit
+        assert_eq!(mul_div(u128::MAX, u128::MAX, 1 << 127), None);
+        // (2^128 - 1) * 2^64 / (2^64 + 1): exact long-division reference
+        let q = mul_div(u128::MAX, 1 << 64, (1 << 64) + 1).unwrap();
+        assert_eq!(q, 340282366920938463444927863358058659840);
+    }
+
+    #[test]
+    fn initial_pps_scales_by_decimal_gap() {
+        assert_eq!(initial_pps(6, 6), Some(RAY));
+        assert_eq!(initial_pps(9, 6), Some(RAY / 1_000));
+        assert_eq!(initial_pps(2, 6), Some(RAY * 10_000));
+        assert_eq!(initial_pps(12, 6), Some(RAY / 1_000_000));
+        assert_eq!(initial_pps(13, 6), None);
+        assert_eq!(initial_pps(6, 19), None);
+        assert_eq!(initial_pps(0, 18), Some(RAY * 10u128.pow(18)));
+
+        // 1 USDC (6 dp) buys exactly one whole 9-dp share
+        let pps = initial_pps(9, 6).unwrap();
+        assert_eq!(shares_for_assets(1_000_000, pps), Some(1_000_000_000));
+        assert_eq!(assets_for_shares(1_000_000_000, pps), Some(1_000_000));
+    }
+
+    #[test]
+    fn boost_total_accumulation_is_checked() {
+        assert_eq!(u64::MAX.checked_add(split_donation(u64::MAX, 10_000).unwrap().0), None);
+        assert_eq!(0u64.checked_add(split_donation(u64::MAX, 10_000).unwrap().0), Some(u64::MAX));
+    }
+}
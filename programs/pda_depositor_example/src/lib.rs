@@ -0,0 +1,109 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+// Example integrator: a program whose PDA, not a wallet, is the vault "user". A
+// treasury PDA at [b"treasury", authority] holds USDC and vault shares in token
+// accounts it owns, and parks or pulls funds with Deposit/Withdraw CPIs.
+//
+// The vault needs nothing special for this. Its pull transfer (Deposit) and share
+// burn (Withdraw) are plain `invoke`s that forward the user's signer privilege, and a
+// PDA's signature granted by this program's `invoke_signed` carries through the vault
+// into the token program. The caller must sign with the treasury seeds on the
+// outer CPI; the vault cannot sign for another program's PDA.
+
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi,
+    entrypoint,
+    instruction::{AccountMeta, Instruction, Seed, Signer},
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+    ProgramResult,
+};
+
+entrypoint!(process_instruction);
+
+// ---------- Constants ----------
+const OP_DEPOSIT: u8 = 0;
+const OP_WITHDRAW: u8 = 1;
+
+const SEED_TREASURY: &[u8] = b"treasury";
+
+// interest_vault op tags
+const VAULT_OP_DEPOSIT: u8 = 1;
+const VAULT_OP_WITHDRAW: u8 = 2;
+
+// ---------- Errors ----------
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TreasuryError {
+    WrongTreasury = 1,
+}
+
+impl From<TreasuryError> for ProgramError {
+    fn from(e: TreasuryError) -> Self { ProgramError::Custom(e as u32) }
+}
+
+pub fn derive_treasury(program_id: &Pubkey, authority: &Pubkey) -> (Pubkey, u8) {
+    find_program_address(&[SEED_TREASURY, authority.as_ref()], program_id)
+}
+
+// ---------- Entry ----------
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    ix_data: &[u8],
+) -> ProgramResult {
+    if ix_data.is_empty() { return Err(ProgramError::InvalidInstructionData) }
+    match ix_data[0] {
+        OP_DEPOSIT  => op_forward(program_id, accounts, VAULT_OP_DEPOSIT, &ix_data[1..]),
+        OP_WITHDRAW => op_forward(program_id, accounts, VAULT_OP_WITHDRAW, &ix_data[1..]),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+// Deposit: data [amount:u64, usdc_decimals:u8]; Withdraw: data [shares:u64, usdc_decimals:u8].
+// Both vault ops take the same account list with the user in slot 2; only slot 1
+// differs (the share mint authority for Deposit, vault_pda for Withdraw).
+fn op_forward(program_id: &Pubkey, accs: &[AccountInfo], vault_op: u8, data: &[u8]) -> ProgramResult {
+    // accounts:
+    // 0 [s]  authority
+    // 1 []   treasury PDA [b"treasury", authority] (the vault user)
+    // 2 []   interest_vault program
+    // 3 [w]  vault_state
+    // 4 []   mint authority (Deposit) / vault_pda (Withdraw)
+    // 5 [w]  treasury USDC token account
+    // 6 [w]  vault USDC ATA
+    // 7 [w]  share mint
+    // 8 [w]  treasury share token account
+    // 9 []   token program
+    // 10 []  USDC mint
+    let [authority, treasury, vault_program, vault_state, signer_pda, treasury_usdc, vault_usdc, share_mint, treasury_shares, token_program, usdc_mint, ..] = accs
+    else { return Err(ProgramError::NotEnoughAccountKeys) };
+    if !authority.is_signer { return Err(ProgramError::MissingRequiredSignature) }
+    if data.len() < 9 { return Err(ProgramError::InvalidInstructionData) }
+    let (expected, bump) = derive_treasury(program_id, authority.key);
+    if *treasury.key != expected { return Err(TreasuryError::WrongTreasury.into()) }
+
+    let mut vault_data = [0u8; 10];
+    vault_data[0] = vault_op;
+    vault_data[1..10].copy_from_slice(&data[..9]);
+    let metas = [
+        AccountMeta::writable(vault_state.key),
+        AccountMeta::readonly(signer_pda.key),
+        AccountMeta::readonly_signer(treasury.key),
+        AccountMeta::writable(treasury_usdc.key),
+        AccountMeta::writable(vault_usdc.key),
+        AccountMeta::writable(share_mint.key),
+        AccountMeta::writable(treasury_shares.key),
+        AccountMeta::readonly(token_program.key),
+        AccountMeta::readonly(usdc_mint.key),
+    ];
+    let ix = Instruction { program_id: vault_program.key, accounts: &metas, data: &vault_data };
+    let bump = [bump];
+    let seeds = [Seed::from(SEED_TREASURY), Seed::from(authority.key.as_ref()), Seed::from(bump.as_ref())];
+    cpi::invoke_signed(
+        &ix,
+        &[vault_state, signer_pda, treasury, treasury_usdc, vault_usdc, share_mint, treasury_shares, token_program, usdc_mint],
+        &[Signer::from(&seeds)],
+    )
+}
@@ -6,5 +6,9 @@ cd "$(dirname "$0")/.."
 cd programs/interest_vault
 cargo build-sbf --features bpf-entrypoint
 echo "Built program to target/deploy/interest_vault.so"
+# example caller used by the LiteSVM PDA-depositor test
+cd ../pda_depositor_example
+cargo build-sbf --features bpf-entrypoint
+echo "Built example to programs/pda_depositor_example/target/deploy/pda_depositor_example.so"
 
 
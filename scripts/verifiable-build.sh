@@ -0,0 +1,24 @@
+#!/usr/bin/env bash
+set -euo pipefail
+cd "$(dirname "$0")/.."
+# Reproducible build of the vault program for `interest-cli verify-build`: the same
+# toolchain image, the same in-container path and the locked dependency graph give a
+# byte-identical .so on any machine, so its hash can be compared with the deployed one.
+# Requires Docker. Override the toolchain with SOLANA_VERSION to match the deployed build.
+SOLANA_VERSION="${SOLANA_VERSION:-2.2.1}"
+IMAGE="solanafoundation/solana-verifiable-build:${SOLANA_VERSION}"
+OUT=programs/interest_vault/target/verifiable
+
+if [ ! -f Cargo.lock ]; then
+  echo "error: no Cargo.lock at the workspace root; without it dependency versions are not pinned and the build cannot reproduce" >&2
+  exit 1
+fi
+
+docker run --rm \
+  -v "$PWD":/build -w /build/programs/interest_vault \
+  -e CARGO_TARGET_DIR=/build/programs/interest_vault/target/verifiable-target \
+  -e RUSTFLAGS="--remap-path-prefix=/build=." \
+  "$IMAGE" \
+  cargo build-sbf --features bpf-entrypoint --locked --sbf-out-dir /build/$OUT
+echo "Built $OUT/interest_vault.so with $IMAGE"
+sha256sum "$OUT/interest_vault.so"
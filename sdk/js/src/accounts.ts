@@ -0,0 +1,216 @@
+import {
+  AccountRole, type Address, type IAccountMeta, type IInstruction, type TransactionSigner,
+  getAssociatedTokenAccountAddress, TOKEN_PROGRAM_ADDRESS,
+} from "gill";
+import { getCreateAssociatedTokenIdempotentInstruction, ASSOCIATED_TOKEN_PROGRAM_ADDRESS, SYSTEM_PROGRAM_ADDRESS } from "gill/programs";
+import { deriveVaultPda, deriveAuthPda, deriveShareMint, deriveBoostDistributor, deriveBoostEscrow, deriveClaimsBitmap, deriveClaimEscrow, claimPage } from "./pdas.js";
+
+// Program account sizes (the program's Discriminator::LEN), for rent estimates
+// and filtering getProgramAccounts by dataSize. Each starts with a DISCRIMINATOR_LEN tag.
+export const DISCRIMINATOR_LEN = 8;
+export const ACCOUNT_LEN = {
+  vaultState: 832,
+  boostDistributor: 208,
+  claimBitmap256: 41,
+  userPrefs: 80,
+  userPosition: 144,
+  vaultRegistry: 24,
+  registryPage: 2064,
+  epochReport: 112,
+} as const;
+
+// Everything needed to address one vault.
+export type VaultRef = {
+  program: Address;
+  vaultState: Address;
+  admin: Address;
+  vaultId?: bigint; // InitializeVault's vault_id, 0 unless the admin runs several vaults on the mint
+  usdcMint: Address;
+  tokenProgram?: Address; // the program that owns usdcMint; SPL Token unless it is a Token-2022 mint
+  vaultUsdcAta: Address;
+  boostUsdcAta: Address;
+};
+
+// Account list for an op plus the ATA-creation instructions to prepend.
+export type OpAccounts = {
+  accounts: IAccountMeta[];
+  preIxs: IInstruction[];
+};
+
+// Minimal rpc surface used to skip ATAs that already exist.
+type AccountInfoRpc = {
+  getAccountInfo(address: Address, config?: { encoding: "base64" }): { send(): Promise<{ value: unknown | null }> };
+};
+
+const w = (address: Address): IAccountMeta => ({ address, role: AccountRole.WRITABLE });
+const r = (address: Address): IAccountMeta => ({ address, role: AccountRole.READONLY });
+const s = (signer: TransactionSigner) => ({ address: signer.address, role: AccountRole.READONLY_SIGNER, signer });
+const ws = (signer: TransactionSigner) => ({ address: signer.address, role: AccountRole.WRITABLE_SIGNER, signer });
+// the share mint is created under the same token program as the vault's mint
+const tokenProgram = (v: VaultRef) => v.tokenProgram ?? TOKEN_PROGRAM_ADDRESS;
+
+// Idempotent create-ATA instructions for `owner`'s accounts of each mint. With an
+// rpc, ATAs that already exist are skipped; without one, the idempotent ix is always included.
+async function ensureAtas(v: VaultRef, payer: TransactionSigner, owner: Address, mints: Address[], rpc?: AccountInfoRpc) {
+  const atas: Address[] = [];
+  const preIxs: IInstruction[] = [];
+  for (const mint of mints) {
+    const ata = await getAssociatedTokenAccountAddress(mint, owner, tokenProgram(v));
+    atas.push(ata);
+    if (rpc) {
+      const { value } = await rpc.getAccountInfo(ata, { encoding: "base64" }).send();
+      if (value) continue;
+    }
+    preIxs.push(getCreateAssociatedTokenIdempotentInstruction({
+      payer, ata, owner, mint, tokenProgram: tokenProgram(v),
+    }));
+  }
+  return { atas, preIxs };
+}
+
+// deposit: vault_state(w), auth_pda, user(s), user_usdc_ata(w), vault_usdc_ata(w),
+// share_mint(w), user_share_ata(w), token_program, usdc_mint
+// withdraw: same list with vault_pda in place of auth_pda
+async function userVaultAccounts(v: VaultRef, user: TransactionSigner, authority: Address, rpc?: AccountInfoRpc): Promise<OpAccounts> {
+  const [shareMint] = await deriveShareMint(v.program, v.vaultState);
+  const { atas: [userUsdcAta, userShareAta], preIxs } =
+    await ensureAtas(v, user, user.address, [v.usdcMint, shareMint], rpc);
+  return {
+    accounts: [
+      w(v.vaultState), r(authority), s(user), w(userUsdcAta), w(v.vaultUsdcAta),
+      w(shareMint), w(userShareAta), r(tokenProgram(v)), r(v.usdcMint),
+    ],
+    preIxs,
+  };
+}
+
+export async function depositAccounts(v: VaultRef, user: TransactionSigner, rpc?: AccountInfoRpc): Promise<OpAccounts> {
+  const [vaultPda] = await deriveVaultPda(v.program, v.usdcMint, v.admin, v.vaultId);
+  const [authPda] = await deriveAuthPda(v.program, vaultPda);
+  return userVaultAccounts(v, user, authPda, rpc);
+}
+
+// depositFor: deposit's list with the recipient's share ATA (created at the payer's expense)
+export async function depositForAccounts(v: VaultRef, payer: TransactionSigner, recipient: Address, rpc?: AccountInfoRpc): Promise<OpAccounts> {
+  const [vaultPda] = await deriveVaultPda(v.program, v.usdcMint, v.admin, v.vaultId);
+  const [authPda] = await deriveAuthPda(v.program, vaultPda);
+  const [shareMint] = await deriveShareMint(v.program, v.vaultState);
+  const usdc = await ensureAtas(v, payer, payer.address, [v.usdcMint], rpc);
+  const shares = await ensureAtas(v, payer, recipient, [shareMint], rpc);
+  return {
+    accounts: [
+      w(v.vaultState), r(authPda), s(payer), w(usdc.atas[0]), w(v.vaultUsdcAta),
+      w(shareMint), w(shares.atas[0]), r(tokenProgram(v)), r(v.usdcMint),
+    ],
+    preIxs: [...usdc.preIxs, ...shares.preIxs],
+  };
+}
+
+// with `recipient`, USDC is paid to the recipient's ATA (created at the user's expense)
+export async function withdrawAccounts(v: VaultRef, user: TransactionSigner, rpc?: AccountInfoRpc, recipient?: Address): Promise<OpAccounts> {
+  const [vaultPda] = await deriveVaultPda(v.program, v.usdcMint, v.admin, v.vaultId);
+  const ops = await userVaultAccounts(v, user, vaultPda, rpc);
+  if (recipient === undefined) return ops;
+  const dest = await ensureAtas(v, user, recipient, [v.usdcMint], rpc);
+  ops.accounts[3] = w(dest.atas[0]);
+  return { accounts: ops.accounts, preIxs: [...ops.preIxs, ...dest.preIxs] };
+}
+
+// migratePosition: withdraw's accounts, then the successor's program, vault_state(w),
+// auth_pda, vault_usdc_ata(w), share_mint(w) and the user's successor share ATA(w)
+export async function migrateAccounts(v: VaultRef, successor: VaultRef, user: TransactionSigner, rpc?: AccountInfoRpc): Promise<OpAccounts> {
+  const ops = await withdrawAccounts(v, user, rpc);
+  const [successorPda] = await deriveVaultPda(successor.program, successor.usdcMint, successor.admin, successor.vaultId);
+  const [successorAuth] = await deriveAuthPda(successor.program, successorPda);
+  const [successorShares] = await deriveShareMint(successor.program, successor.vaultState);
+  const { atas: [userSuccessorShares], preIxs } = await ensureAtas(successor, user, user.address, [successorShares], rpc);
+  return {
+    accounts: [
+      ...ops.accounts, r(successor.program), w(successor.vaultState), r(successorAuth), w(successor.vaultUsdcAta),
+      w(successorShares), w(userSuccessorShares),
+    ],
+    preIxs: [...ops.preIxs, ...preIxs],
+  };
+}
+
+// claim: vault_state(w), vault_pda, claimer(s), boost_distributor(w), claims_bitmap(w),
+// boost_usdc_ata(w), claimer_usdc_ata(w), token_program, usdc_mint, claim_escrow(w). The
+// bitmap is the page holding leaf `index`. The claim escrow is only paid when the claimer's
+// USDC account is frozen or closed; it must have been opened (OpenClaimEscrow) by then.
+// With `ataPayer` the claim creates a missing claimer ATA itself (payer(ws), system_program,
+// associated_token_program appended) instead of a separate create-ATA instruction.
+// `escrowed` pays from the epoch's escrow (OpenBoostEscrow) instead of the shared boost account.
+export async function claimAccounts(
+  v: VaultRef, claimer: TransactionSigner, epoch: bigint, rpc?: AccountInfoRpc, index = 0, ataPayer?: TransactionSigner,
+  escrowed = false,
+): Promise<OpAccounts> {
+  const [vaultPda] = await deriveVaultPda(v.program, v.usdcMint, v.admin, v.vaultId);
+  const [distributor] = await deriveBoostDistributor(v.program, vaultPda, epoch);
+  const boost = escrowed ? (await deriveBoostEscrow(v.program, distributor))[0] : v.boostUsdcAta;
+  const [bitmap] = await deriveClaimsBitmap(v.program, vaultPda, epoch, claimPage(index));
+  const [claimEscrow] = await deriveClaimEscrow(v.program, v.vaultState, claimer.address);
+  const { atas: [claimerUsdcAta], preIxs } = await ensureAtas(v, claimer, claimer.address, [v.usdcMint], rpc);
+  const accounts = [
+    w(v.vaultState), r(vaultPda), s(claimer), w(distributor), w(bitmap),
+    w(boost), w(claimerUsdcAta), r(tokenProgram(v)), r(v.usdcMint), w(claimEscrow),
+  ];
+  if (ataPayer === undefined) return { accounts, preIxs };
+  return {
+    accounts: [...accounts, ws(ataPayer), r(SYSTEM_PROGRAM_ADDRESS), r(ASSOCIATED_TOKEN_PROGRAM_ADDRESS)],
+    preIxs: [],
+  };
+}
+
+// claimEpochs: vault_state(w), vault_pda, claimer(s), claimer_usdc_ata(w), token_program, usdc_mint,
+// then per entry boost_distributor(w), claims_bitmap(w), boost account(w): the epoch's escrow
+// when it has one, else the shared boost account; then claim_escrow(w) as for claim
+export async function claimEpochsAccounts(
+  v: VaultRef, claimer: TransactionSigner, entries: { epoch: bigint; index: number; escrowed?: boolean }[], rpc?: AccountInfoRpc,
+): Promise<OpAccounts> {
+  const [vaultPda] = await deriveVaultPda(v.program, v.usdcMint, v.admin, v.vaultId);
+  const { atas: [claimerUsdcAta], preIxs } = await ensureAtas(v, claimer, claimer.address, [v.usdcMint], rpc);
+  const accounts: IAccountMeta[] = [
+    w(v.vaultState), r(vaultPda), s(claimer), w(claimerUsdcAta), r(tokenProgram(v)), r(v.usdcMint),
+  ];
+  for (const e of entries) {
+    const [distributor] = await deriveBoostDistributor(v.program, vaultPda, e.epoch);
+    const [bitmap] = await deriveClaimsBitmap(v.program, vaultPda, e.epoch, claimPage(e.index));
+    const boost = e.escrowed ? (await deriveBoostEscrow(v.program, distributor))[0] : v.boostUsdcAta;
+    accounts.push(w(distributor), w(bitmap), w(boost));
+  }
+  accounts.push(w((await deriveClaimEscrow(v.program, v.vaultState, claimer.address))[0]));
+  return { accounts, preIxs };
+}
+
+// redeemEscrow: vault_state, claimer(s), claim_escrow(w), claimer_usdc_ata(w), token_program, usdc_mint
+export async function redeemEscrowAccounts(v: VaultRef, claimer: TransactionSigner, rpc?: AccountInfoRpc): Promise<OpAccounts> {
+  const [claimEscrow] = await deriveClaimEscrow(v.program, v.vaultState, claimer.address);
+  const { atas: [claimerUsdcAta], preIxs } = await ensureAtas(v, claimer, claimer.address, [v.usdcMint], rpc);
+  return {
+    accounts: [r(v.vaultState), s(claimer), w(claimEscrow), w(claimerUsdcAta), r(tokenProgram(v)), r(v.usdcMint)],
+    preIxs,
+  };
+}
+
+// claimCompound: vault_state(w), vault_pda, claimer(s), boost_distributor(w), claims_bitmap(w),
+// boost_usdc_ata(w), vault_usdc_ata(w), token_program, usdc_mint, auth_pda, share_mint(w), claimer_share_ata(w);
+// `escrowed` as for claim
+export async function claimCompoundAccounts(
+  v: VaultRef, claimer: TransactionSigner, epoch: bigint, rpc?: AccountInfoRpc, index = 0, escrowed = false,
+): Promise<OpAccounts> {
+  const [vaultPda] = await deriveVaultPda(v.program, v.usdcMint, v.admin, v.vaultId);
+  const [authPda] = await deriveAuthPda(v.program, vaultPda);
+  const [shareMint] = await deriveShareMint(v.program, v.vaultState);
+  const [distributor] = await deriveBoostDistributor(v.program, vaultPda, epoch);
+  const [bitmap] = await deriveClaimsBitmap(v.program, vaultPda, epoch, claimPage(index));
+  const boost = escrowed ? (await deriveBoostEscrow(v.program, distributor))[0] : v.boostUsdcAta;
+  const { atas: [claimerShareAta], preIxs } = await ensureAtas(v, claimer, claimer.address, [shareMint], rpc);
+  return {
+    accounts: [
+      w(v.vaultState), r(vaultPda), s(claimer), w(distributor), w(bitmap),
+      w(boost), w(v.vaultUsdcAta), r(tokenProgram(v)), r(v.usdcMint),
+      r(authPda), w(shareMint), w(claimerShareAta),
+    ],
+    preIxs,
+  };
+}
@@ -1,18 +1,24 @@
 import {
   address, Address,
   createSolanaClient, createTransaction, signTransactionMessageWithSigners,
+  compileTransaction, getTransactionEncoder, compressTransactionMessageUsingAddressLookupTables,
+  type AddressesByLookupTableAddress,
   getAssociatedTokenAccountAddress, TOKEN_PROGRAM_ADDRESS,
-  getAddressEncoder,
+  getAddressEncoder, getAddressDecoder, type TransactionSigner, getBase64EncodedWireTransaction,
+  getSignatureFromTransaction, type Commitment,
 } from "gill";
-import { dataInit, dataDeposit, dataWithdraw, dataDonate, dataPostRoot, dataClaim } from "./instructions.js";
-import { deriveVaultPda, deriveBoostDistributor, deriveClaimsBitmap } from "./pdas.js";
+import { dataInit, dataDeposit, dataDepositFor, dataWithdraw, dataMigratePosition, dataDonate, dataPostRoot, dataClaim, dataClaimCompound, dataClaimEpochs, dataRedeemEscrow, withMeasure, decodeMeasuredUnits, type EpochClaim } from "./instructions.js";
+import { deriveVaultState, deriveRegistry, deriveRegistryPage, REGISTRY_PAGE_VAULTS, deriveVaultPda, deriveShareMint, deriveBoostUsdc, deriveBoostDistributor, deriveClaimsBitmap } from "./pdas.js";
+import { type VaultRef, depositAccounts, depositForAccounts, withdrawAccounts, migrateAccounts, claimAccounts, claimCompoundAccounts, claimEpochsAccounts, redeemEscrowAccounts } from "./accounts.js";
 
 export type Accounts = {
   program: Address;
   admin: Address;
+  vaultId?: bigint;
   operator: Address;
   usdcMint: Address;
   shareMint: Address;
+  tokenProgram?: Address; // the program that owns usdcMint; SPL Token unless it is a Token-2022 mint
   vaultUsdcAta: Address;
   boostUsdcAta: Address;
 };
@@ -22,34 +28,287 @@ export function initClient(urlOrMoniker: string = "localnet") {
   return { rpc, rpcSubscriptions, sendAndConfirmTransaction };
 }
 
-export async function buildInitializeIx(acc: Accounts, decimals = 6) {
-  const [vaultPda] = await deriveVaultPda(acc.program, acc.usdcMint, acc.admin);
+// The program creates vault_state at its PDA, and the vault's USDC ATA and shared boost
+// account, paid by the admin. `vaultCount` is the registry's count before this init
+// (registryCount), which picks the registry page.
+// A non-zero acc.vaultId needs shareDecimals (see dataInit).
+export async function buildInitializeIx(acc: Accounts, vaultCount: bigint, shareDecimals?: number) {
+  const [vaultState] = await deriveVaultState(acc.program, acc.usdcMint, acc.admin, acc.vaultId);
+  const [registry] = await deriveRegistry(acc.program);
+  const [registryPage] = await deriveRegistryPage(acc.program, Number(vaultCount / BigInt(REGISTRY_PAGE_VAULTS)));
+  const [vaultPda] = await deriveVaultPda(acc.program, acc.usdcMint, acc.admin, acc.vaultId);
+  const [shareMint] = await deriveShareMint(acc.program, vaultState);
+  const vaultUsdcAta = await getAssociatedTokenAccountAddress(acc.usdcMint, vaultPda, acc.tokenProgram ?? TOKEN_PROGRAM_ADDRESS);
+  const [boostUsdc] = await deriveBoostUsdc(acc.program, vaultPda);
   return {
     programId: acc.program,
     keys: [
       { pubkey: acc.program, isSigner: false, isWritable: false }, // kept for readability
     ],
     accounts: [
-      // must be provided by caller in tx: vault_state(w), admin(s), operator, usdcMint, shareMint, vaultPda
+      // must be provided by caller in tx: vault_state(w), admin(ws), operator, usdcMint,
+      // shareMint(w), vaultPda, system_program, token_program, registry(w), registryPage(w),
+      // vaultUsdcAta(w), boostUsdc(w), associated_token_program
     ],
-    data: dataInit(decimals),
-    vaultPda
+    data: dataInit(shareDecimals, acc.vaultId),
+    vaultState,
+    vaultPda,
+    shareMint,
+    registry,
+    registryPage,
+    vaultUsdcAta,
+    boostUsdc,
   };
 }
 
-export async function buildDepositIx(acc: Accounts, user: Address, userUsdcAta: Address, userShareAta: Address, amount: bigint) {
-  const [vaultPda] = await deriveVaultPda(acc.program, acc.usdcMint, acc.admin);
-  return {
-    data: dataDeposit(amount, 6),
-    accounts: [
-      // vault_state(w), vault_pda, user(s), user_usdc_ata(w), vault_usdc_ata(w),
-      // share_mint(w), user_share_ata(w), token_program, usdc_mint
-    ],
-    vaultPda,
+// Each builder returns the instructions to send in order: any missing ATAs, then the op.
+export async function buildDepositIxs(v: VaultRef, user: TransactionSigner, amount: bigint, usdcDecimals = 6, rpc?: Parameters<typeof depositAccounts>[2], minSharesOut = 0n) {
+  const { accounts, preIxs } = await depositAccounts(v, user, rpc);
+  return [...preIxs, { programAddress: v.program, accounts, data: dataDeposit(amount, usdcDecimals, minSharesOut) }];
+}
+
+export async function buildDepositForIxs(
+  v: VaultRef, payer: TransactionSigner, recipient: Address, amount: bigint, usdcDecimals = 6,
+  rpc?: Parameters<typeof depositForAccounts>[3], minSharesOut = 0n,
+) {
+  const { accounts, preIxs } = await depositForAccounts(v, payer, recipient, rpc);
+  return [...preIxs, { programAddress: v.program, accounts, data: dataDepositFor(amount, usdcDecimals, recipient, minSharesOut) }];
+}
+
+export async function buildWithdrawIxs(
+  v: VaultRef, user: TransactionSigner, shares: bigint, usdcDecimals = 6, rpc?: Parameters<typeof withdrawAccounts>[2], recipient?: Address,
+) {
+  const { accounts, preIxs } = await withdrawAccounts(v, user, rpc, recipient);
+  return [...preIxs, { programAddress: v.program, accounts, data: dataWithdraw(shares, usdcDecimals, recipient) }];
+}
+
+// moves `shares` (0n = all) into `successor`, which must be the vault's registered successor
+export async function buildMigratePositionIxs(
+  v: VaultRef, successor: VaultRef, user: TransactionSigner, shares = 0n, usdcDecimals = 6,
+  rpc?: Parameters<typeof migrateAccounts>[3], minSharesOut?: bigint,
+) {
+  const { accounts, preIxs } = await migrateAccounts(v, successor, user, rpc);
+  return [...preIxs, { programAddress: v.program, accounts, data: dataMigratePosition(shares, usdcDecimals, successor.program, successor.vaultState, minSharesOut) }];
+}
+
+export async function buildClaimIxs(
+  v: VaultRef, claimer: TransactionSigner,
+  epoch: bigint, index: number, weight: bigint, proof: Buffer[],
+  rpc?: Parameters<typeof claimAccounts>[3], ataPayer?: TransactionSigner, escrowed = false,
+) {
+  const { accounts, preIxs } = await claimAccounts(v, claimer, epoch, rpc, index, ataPayer, escrowed);
+  return [...preIxs, { programAddress: v.program, accounts, data: dataClaim(epoch, index, weight, proof) }];
+}
+
+// `escrowed` marks epochs opened with OpenBoostEscrow (BoostDistributor.escrow != 0)
+export async function buildClaimEpochsIxs(
+  v: VaultRef, claimer: TransactionSigner, claims: (EpochClaim & { escrowed?: boolean })[],
+  rpc?: Parameters<typeof claimEpochsAccounts>[3],
+) {
+  const { accounts, preIxs } = await claimEpochsAccounts(v, claimer, claims, rpc);
+  return [...preIxs, { programAddress: v.program, accounts, data: dataClaimEpochs(claims) }];
+}
+
+export async function buildClaimCompoundIxs(
+  v: VaultRef, claimer: TransactionSigner,
+  epoch: bigint, index: number, weight: bigint, proof: Buffer[],
+  rpc?: Parameters<typeof claimCompoundAccounts>[3], escrowed = false,
+) {
+  const { accounts, preIxs } = await claimCompoundAccounts(v, claimer, epoch, rpc, index, escrowed);
+  return [...preIxs, { programAddress: v.program, accounts, data: dataClaimCompound(epoch, index, weight, proof) }];
+}
+
+// pays out what claims parked in the claimer's claim escrow while their USDC account was frozen
+export async function buildRedeemEscrowIxs(v: VaultRef, claimer: TransactionSigner, rpc?: Parameters<typeof redeemEscrowAccounts>[2]) {
+  const { accounts, preIxs } = await redeemEscrowAccounts(v, claimer, rpc);
+  return [...preIxs, { programAddress: v.program, accounts, data: dataRedeemEscrow() }];
+}
+
+// Similar helpers for donate/postRoot ...
+
+// Largest serialized transaction a validator accepts
+export const PACKET_DATA_SIZE = 1232;
+
+// Greedy packer for operator batches (many vaults/epochs): keeps instruction order and
+// starts a new v0 transaction whenever the next instruction would not fit. With
+// `lookupTables` (ALT address -> its addresses, as fetched) shared accounts compress to
+// one-byte indexes, so more instructions fit per transaction.
+export function packIxs(feePayer: TransactionSigner, ixs: any[], latestBlockhash: any, lookupTables: AddressesByLookupTableAddress = {}) {
+  const size = (batch: any[]) => {
+    const msg = compressTransactionMessageUsingAddressLookupTables(
+      createTransaction({ version: 0, feePayer, latestBlockhash, instructions: batch }) as any,
+      lookupTables,
+    );
+    return getTransactionEncoder().encode(compileTransaction(msg as any)).length;
   };
+  const batches: any[][] = [];
+  let current: any[] = [];
+  for (const ix of ixs) {
+    if (current.length > 0 && size([...current, ix]) > PACKET_DATA_SIZE) {
+      batches.push(current);
+      current = [];
+    }
+    if (size([ix]) > PACKET_DATA_SIZE) throw new Error("instruction does not fit in a transaction on its own");
+    current.push(ix);
+  }
+  if (current.length > 0) batches.push(current);
+  return batches;
+}
+
+// Sends packed batches in order and stops at the first failure, so a prefix of `ixs` is done
+export async function sendBatched(urlOrMoniker: string, feePayer: TransactionSigner, ixs: any[], lookupTables: AddressesByLookupTableAddress = {}) {
+  const { rpc, sendAndConfirmTransaction } = createSolanaClient({ urlOrMoniker });
+  const { value: latestBlockhash } = await rpc.getLatestBlockhash().send();
+  const sigs: string[] = [];
+  for (const batch of packIxs(feePayer, ixs, latestBlockhash, lookupTables)) {
+    const { value: blockhash } = await rpc.getLatestBlockhash().send();
+    const tx = compressTransactionMessageUsingAddressLookupTables(
+      createTransaction({ version: 0, feePayer, latestBlockhash: blockhash, instructions: batch }) as any,
+      lookupTables,
+    );
+    const signed = await signTransactionMessageWithSigners(tx as any);
+    sigs.push(await sendAndConfirmTransaction(signed as any));
+  }
+  return sigs;
+}
+
+export type SendOptions = {
+  commitment?: Commitment;       // level to wait for (default "confirmed")
+  maxAttempts?: number;          // fresh blockhashes to try before giving up (default 6)
+  baseDelayMs?: number;          // first backoff; doubles per attempt, with jitter (default 500)
+  maxDelayMs?: number;           // backoff ceiling (default 8000)
+  pollMs?: number;               // status poll and rebroadcast interval (default 2000)
+  skipPreflight?: boolean | "first-only"; // default "first-only": simulate once, then skip
+  computeUnitLimit?: number;
+  computeUnitPrice?: bigint;     // priority fee, micro-lamports per unit
+  lookupTables?: AddressesByLookupTableAddress;
+};
+
+const COMMITMENT_RANK: Record<string, number> = { processed: 0, confirmed: 1, finalized: 2 };
+
+// RPC failures worth another attempt; anything else (a program error in preflight, a bad
+// signature) fails the same way every time
+const RETRYABLE = /blockhash not found|block height exceeded|node is behind|too many requests|429|502|503|timed? ?out|fetch failed|ECONNRESET|socket hang up/i;
+
+const sleep = (ms: number) => new Promise((r) => setTimeout(r, ms));
+
+// Submitter for the claim rush right after PostRoot, when sendAndConfirm loops drop many
+// transactions. Each attempt signs with a fresh blockhash, sends with RPC retries off and
+// rebroadcasts itself every pollMs until the transaction reaches `commitment` or its
+// blockhash expires. It then backs off (exponential, jittered so a crowd of clients does
+// not resend in lockstep) and tries again. Every signature sent so far is polled, so a
+// transaction that lands late is returned rather than re-sent: a second Claim would only
+// fail with AlreadyClaimed. Preflight runs on the first attempt by default, so a claim
+// that can never succeed fails fast without paying fees. Throws on an on-chain error,
+// a non-retryable RPC error, or after maxAttempts.
+export async function sendWithRetry(rpc: any, feePayer: TransactionSigner, ixs: any[], opts: SendOptions = {}) {
+  const {
+    commitment = "confirmed", maxAttempts = 6, baseDelayMs = 500, maxDelayMs = 8_000, pollMs = 2_000,
+    skipPreflight = "first-only", computeUnitLimit, computeUnitPrice, lookupTables = {},
+  } = opts;
+  const sigs: string[] = [];
+  let lastError: unknown;
+  for (let attempt = 0; attempt < maxAttempts; attempt++) {
+    if (attempt > 0) {
+      const delay = Math.min(maxDelayMs, baseDelayMs * 2 ** (attempt - 1));
+      await sleep(delay / 2 + Math.random() * (delay / 2));
+    }
+    const { value: latestBlockhash } = await rpc.getLatestBlockhash({ commitment: "confirmed" }).send();
+    const tx = compressTransactionMessageUsingAddressLookupTables(
+      createTransaction({ version: 0, feePayer, latestBlockhash, instructions: ixs, computeUnitLimit, computeUnitPrice }) as any,
+      lookupTables,
+    );
+    const signed = await signTransactionMessageWithSigners(tx as any);
+    const wire = getBase64EncodedWireTransaction(signed as any);
+    const skip = skipPreflight === "first-only" ? attempt > 0 : skipPreflight;
+    const send = (skip: boolean) => rpc.sendTransaction(wire, {
+      encoding: "base64", skipPreflight: skip, preflightCommitment: commitment, maxRetries: 0n,
+    }).send();
+    try {
+      await send(skip);
+    } catch (e) {
+      if (!RETRYABLE.test(String((e as any)?.message ?? e))) throw e;
+      lastError = e;
+      continue;
+    }
+    sigs.push(getSignatureFromTransaction(signed as any));
+
+    while (true) {
+      await sleep(pollMs);
+      const { value: statuses } = await rpc.getSignatureStatuses(sigs).send();
+      for (let i = 0; i < sigs.length; i++) {
+        const st = statuses[i];
+        if (!st) continue;
+        if (st.err) throw new Error(`transaction ${sigs[i]} failed: ${JSON.stringify(st.err, (_, v) => (typeof v === "bigint" ? v.toString() : v))}`);
+        if (COMMITMENT_RANK[st.confirmationStatus] >= COMMITMENT_RANK[commitment]) return sigs[i];
+      }
+      const height: bigint = await rpc.getBlockHeight({ commitment: "confirmed" }).send();
+      if (height > BigInt(latestBlockhash.lastValidBlockHeight)) break;
+      await send(true).catch(() => {});
+    }
+    lastError = new Error(`blockhash expired before ${sigs[sigs.length - 1]} reached ${commitment}`);
+  }
+  throw new Error(`not confirmed after ${maxAttempts} attempts: ${String((lastError as any)?.message ?? lastError)}`);
+}
+
+// allowance for instructions of other programs (e.g. idempotent ATA creation), which
+// cannot report their own usage
+export const OTHER_IX_UNITS = 30_000;
+
+// Compute units each of the vault's instructions in `ixs` uses, in order (null for other
+// programs' instructions). Return data only keeps the last value set, so the list is
+// simulated once per vault instruction with OP_MEASURE on that one; earlier instructions
+// still run, so later ops are measured against the state they will see.
+export async function measureComputeUnits(rpc: any, feePayer: TransactionSigner, ixs: any[], program: Address) {
+  const { value: latestBlockhash } = await rpc.getLatestBlockhash().send();
+  const out: (bigint | null)[] = [];
+  for (let i = 0; i < ixs.length; i++) {
+    if (ixs[i].programAddress !== program) { out.push(null); continue; }
+    const instructions = ixs.map((ix, j) => (j === i ? { ...ix, data: withMeasure(ix.data) } : ix));
+    const signed = await signTransactionMessageWithSigners(createTransaction({ version: 0, feePayer, latestBlockhash, instructions }) as any);
+    const { value } = await rpc.simulateTransaction(getBase64EncodedWireTransaction(signed as any), {
+      encoding: "base64", sigVerify: false, replaceRecentBlockhash: true,
+    }).send();
+    if (value.err || !value.returnData) throw new Error(`simulation of instruction ${i} failed: ${JSON.stringify(value.err)}`);
+    out.push(decodeMeasuredUnits(Buffer.from(value.returnData.data[0], "base64")));
+  }
+  return out;
 }
 
-// Similar helpers for withdraw/donate/postRoot/claim ...
+// A compute unit limit for `ixs`: the measured units plus `headroomBps` (default 10%),
+// and OTHER_IX_UNITS per instruction of another program. Pass it to createTransaction
+// as computeUnitLimit.
+export async function computeUnitLimit(rpc: any, feePayer: TransactionSigner, ixs: any[], program: Address, headroomBps = 1_000) {
+  const units = await measureComputeUnits(rpc, feePayer, ixs, program);
+  const measured = units.reduce<bigint>((sum, u) => sum + (u ?? 0n), 0n);
+  const others = BigInt(units.filter((u) => u === null).length * OTHER_IX_UNITS);
+  return Number(measured + (measured * BigInt(headroomBps)) / 10_000n + others);
+}
+
+// Vaults registered so far (0n before the program's first init)
+export async function registryCount(rpc: any, program: Address) {
+  const [registry] = await deriveRegistry(program);
+  const { value } = await rpc.getAccountInfo(registry, { encoding: "base64" }).send();
+  return value ? Buffer.from(value.data[0], "base64").readBigUInt64LE(8) : 0n;
+}
+
+// Every vault_state InitializeVault created, in creation order: the registry header, then
+// all its pages in one getMultipleAccounts call (up to 100 pages, 6400 vaults).
+export async function listVaults(rpc: any, program: Address): Promise<Address[]> {
+  const count = Number(await registryCount(rpc, program));
+  const pageCount = Math.ceil(count / REGISTRY_PAGE_VAULTS);
+  const pages = await Promise.all(Array.from({ length: pageCount }, (_, p) => deriveRegistryPage(program, p).then(([a]) => a)));
+  if (pages.length === 0) return [];
+  const { value } = await rpc.getMultipleAccounts(pages, { encoding: "base64" }).send();
+  const dec = getAddressDecoder();
+  const out: Address[] = [];
+  for (const acc of value) {
+    const d = Buffer.from(acc.data[0], "base64");
+    const len = d.readUInt16LE(12);
+    for (let i = 0; i < len; i++) out.push(dec.decode(d.subarray(16 + 32 * i, 48 + 32 * i)));
+  }
+  return out;
+}
 
 // Convenience submitter
 export async function sendIxs(urlOrMoniker: string, feePayer: any, ixs: any[]) {
@@ -1,4 +1,4 @@
-import { address, getAddressDecoder, type Address } from "gill";
+import { address, getAddressDecoder, getAddressEncoder, type Address } from "gill";
 
 export const OP = {
   INIT: 0,
@@ -7,50 +7,200 @@ export const OP = {
   DONATE: 3,
   POSTROOT: 4,
   CLAIM: 5,
+  SET_YIELD_ORACLE: 6,
+  SET_DONORS: 7,
+  SET_BURN_DUST: 8,
+  INIT_FROM_TEMPLATE: 9,
+  SET_AUTO_CLAIM: 10,
+  CRANK_CLAIM: 11,
+  CLAIM_MANY: 12,
+  COMMIT_WEIGHTS: 13,
+  RECONCILE_RENT: 14,
+  SET_ROOT_SIGNERS: 15,
+  SET_PPS_CEILING: 16,
+  RECORD_UPGRADE_AUTHORITY: 17,
+  SETTLE: 18,
+  PROPOSE_ADMIN: 19,
+  ACCEPT_ADMIN: 20,
+  SET_PAUSE_FLAGS: 21,
+  OPEN_BOOST_ESCROW: 22,
+  SET_GUARDIAN: 23,
+  ENTER_EMERGENCY: 24,
+  QUOTE_CLAIM: 25,
+  SET_DEPOSIT_CAP: 26,
+  SET_USER_DEPOSIT_CAP: 27,
+  CHECK_SHARE_SUPPLY: 28,
+  REPAIR_SHARE_SUPPLY: 29,
+  WITHDRAW_EXACT: 30,
+  WITHDRAW_ALL: 31,
+  DEPOSIT_FOR: 32,
+  CLAIM_COMPOUND: 33,
+  OPEN_CLAIM_PAGE: 34,
+  CLAIM_EPOCHS: 35,
+  OPEN_DISTRIBUTOR: 36,
+  CLAWBACK: 37,
+  FINALIZE_EPOCH: 38,
+  OPEN_CLAIM_ESCROW: 39,
+  REDEEM_ESCROW: 40,
+  SET_SUCCESSOR: 41,
+  MIGRATE_POSITION: 42,
+  SET_LIVENESS_WINDOW: 43,
+  TRIP_WATCHDOG: 44,
+  RECORD_ARCHIVE: 45,
+  REPORT: 46,
+}
+
+// Tag bit on the op byte: the op runs as usual and its return data becomes [units:u64],
+// the compute units it consumed. Meant for simulation (see measureComputeUnits).
+export const OP_MEASURE = 0x80;
+
+export function withMeasure(data: Uint8Array) {
+  const b = Buffer.from(data);
+  b[0] |= OP_MEASURE;
+  return b;
+}
+
+export function decodeMeasuredUnits(returnData: Buffer): bigint {
+  return returnData.readBigUInt64LE(0);
+}
+
+// VaultState.flags bits for SetPauseFlags
+export const PAUSE = {
+  DEPOSIT: 1 << 0,
+  WITHDRAW: 1 << 1,
+  DONATE: 1 << 2,
+  CLAIM: 1 << 3,
+  EMERGENCY: 1 << 4, // withdraw-only; overrides WITHDRAW
+} as const;
+
+// VaultState.upgrade_authority_status
+export const UPGRADE_AUTHORITY_STATUS = {
+  UNKNOWN: 0,
+  IMMUTABLE: 1,
+  HELD: 2,
+} as const;
+
+// which of the vault's program-owned accounts ReconcileRent targets
+export const RENT_TARGET = {
+  VAULT_STATE: 0,
+  DISTRIBUTOR: 1,
+  CLAIMS_BITMAP: 2,
+  USER_PREFS: 3,
 } as const;
 
-export function dataInit(decimals: number) {
-  return Buffer.from([OP.INIT, decimals & 0xff]);
+// share decimals default to the USDC mint's; they may be up to 6 above it (e.g. 9-dp shares over 6-dp USDC).
+// vaultId (default 0) is in the vault_state and vault PDA seeds; it follows the decimals
+// byte, so a non-zero id needs shareDecimals too.
+export function dataInit(shareDecimals?: number, vaultId = 0n) {
+  return initData(OP.INIT, shareDecimals, vaultId);
+}
+
+// accounts: init's list plus the template vault_state (same admin)
+export function dataInitFromTemplate(shareDecimals?: number, vaultId = 0n) {
+  return initData(OP.INIT_FROM_TEMPLATE, shareDecimals, vaultId);
+}
+
+function initData(op: number, shareDecimals: number | undefined, vaultId: bigint) {
+  if (shareDecimals === undefined) {
+    if (vaultId !== 0n) throw new Error("a vaultId needs explicit shareDecimals");
+    return Buffer.from([op]);
+  }
+  const b = Buffer.alloc(1 + 1 + 8);
+  b[0] = op;
+  b[1] = shareDecimals & 0xff;
+  b.writeBigUInt64LE(vaultId, 2);
+  return b;
 }
 
-export function dataDeposit(amount: bigint, usdcDecimals: number) {
-  const b = Buffer.alloc(1 + 8 + 1);
+// minSharesOut: fail with SlippageExceeded (26) rather than mint fewer shares than quoted
+export function dataDeposit(amount: bigint, usdcDecimals: number, minSharesOut: bigint = 0n) {
+  const b = Buffer.alloc(1 + 8 + 1 + 8);
   b[0] = OP.DEPOSIT;
   b.writeBigUInt64LE(amount, 1);
   b[9] = usdcDecimals & 0xff;
+  b.writeBigUInt64LE(minSharesOut, 10);
   return b;
 }
 
-export function dataWithdraw(shares: bigint, usdcDecimals: number) {
-  const b = Buffer.alloc(1 + 8 + 1);
+// payer's USDC, shares minted to `recipient`'s share account (checked on-chain)
+export function dataDepositFor(amount: bigint, usdcDecimals: number, recipient: Address, minSharesOut: bigint = 0n) {
+  const b = Buffer.alloc(1 + 8 + 1 + 32 + 8);
+  b[0] = OP.DEPOSIT_FOR;
+  b.writeBigUInt64LE(amount, 1);
+  b[9] = usdcDecimals & 0xff;
+  Buffer.from(getAddressEncoder().encode(recipient)).copy(b, 10);
+  b.writeBigUInt64LE(minSharesOut, 42);
+  return b;
+}
+
+// `recipient` authorizes paying a USDC account owned by someone other than the user
+export function dataWithdraw(shares: bigint, usdcDecimals: number, recipient?: Address) {
+  const b = Buffer.alloc(1 + 8 + 1 + (recipient === undefined ? 0 : 32));
   b[0] = OP.WITHDRAW;
   b.writeBigUInt64LE(shares, 1);
   b[9] = usdcDecimals & 0xff;
+  if (recipient !== undefined) Buffer.from(getAddressEncoder().encode(recipient)).copy(b, 10);
+  return b;
+}
+
+// pays exactly `amount` USDC, burning the shares it costs rounded up; fails with
+// SlippageExceeded if that is more than maxSharesIn. Same accounts as Withdraw.
+export function dataWithdrawExact(amount: bigint, usdcDecimals: number, maxSharesIn?: bigint) {
+  const b = Buffer.alloc(1 + 8 + 1 + (maxSharesIn === undefined ? 0 : 8));
+  b[0] = OP.WITHDRAW_EXACT;
+  b.writeBigUInt64LE(amount, 1);
+  b[9] = usdcDecimals & 0xff;
+  if (maxSharesIn !== undefined) b.writeBigUInt64LE(maxSharesIn, 10);
   return b;
 }
 
-export function dataDonate(amount: bigint, epoch: bigint, boostBps: number, usdcDecimals: number) {
-  const b = Buffer.alloc(1 + 8 + 8 + 2 + 1);
+// burns the whole user_share_ata balance as read on-chain. Same accounts as Withdraw.
+export function dataWithdrawAll(usdcDecimals: number) {
+  return Buffer.from([OP.WITHDRAW_ALL, usdcDecimals & 0xff]);
+}
+
+export const DONATION_CATEGORY = {
+  STRATEGY_YIELD: 0,
+  GRANT: 1,
+  REBATE: 2,
+  PENALTY: 3,
+} as const;
+
+export function dataDonate(amount: bigint, epoch: bigint, boostBps: number, usdcDecimals: number, category: number = DONATION_CATEGORY.STRATEGY_YIELD, memo?: Buffer) {
+  const b = Buffer.alloc(1 + 8 + 8 + 2 + 1 + 1 + (memo ? 32 : 0));
   b[0] = OP.DONATE;
   b.writeBigUInt64LE(amount, 1);
   b.writeBigUInt64LE(epoch, 9);
   b.writeUInt16LE(boostBps, 17);
   b[19] = usdcDecimals & 0xff;
+  b[20] = category & 0xff;
+  memo?.copy(b, 21, 0, 32);
   return b;
 }
 
-export function dataPostRoot(epoch: bigint, totalWeight: bigint, root: Buffer) {
-  const b = Buffer.alloc(1 + 8 + 16 + 32);
+export function dataPostRoot(epoch: bigint, totalWeight: bigint, root: Buffer, leafCount: number) {
+  const b = Buffer.alloc(1 + 8 + 16 + 32 + 4);
   b[0] = OP.POSTROOT;
   b.writeBigUInt64LE(epoch, 1);
   writeU128LE(totalWeight, b, 9);
   root.copy(b, 25);
+  b.writeUInt32LE(leafCount >>> 0, 57);
+  return b;
+}
+
+// commitment = keccak256("commit" || epoch_le || total_weight_le || root || leaf_count_le),
+// i.e. the PostRoot payload announced before the epoch ends
+export function dataCommitWeights(epoch: bigint, commitment: Buffer) {
+  const b = Buffer.alloc(1 + 8 + 32);
+  b[0] = OP.COMMIT_WEIGHTS;
+  b.writeBigUInt64LE(epoch, 1);
+  commitment.copy(b, 9);
   return b;
 }
 
-export function dataClaim(epoch: bigint, index: number, weight: bigint, proof: Buffer[]) {
+export function dataClaim(epoch: bigint, index: number, weight: bigint, proof: Buffer[], op: number = OP.CLAIM) {
   const b = Buffer.alloc(1 + 8 + 4 + 16 + 1 + 32 * proof.length);
-  b[0] = OP.CLAIM;
+  b[0] = op;
   b.writeBigUInt64LE(epoch, 1);
   b.writeUInt32LE(index >>> 0, 9);
   writeU128LE(weight, b, 13);
@@ -59,6 +209,316 @@ export function dataClaim(epoch: bigint, index: number, weight: bigint, proof: B
   return b;
 }
 
+// simulate with accounts boost_distributor, claims_bitmap, claimer; read the payout
+// from the simulation's return data with decodeClaimQuote
+export function dataQuoteClaim(epoch: bigint, index: number, weight: bigint, proof: Buffer[]) {
+  return dataClaim(epoch, index, weight, proof, OP.QUOTE_CLAIM);
+}
+
+// Claim whose payout is deposited back into the vault as shares for the claimer
+export function dataClaimCompound(epoch: bigint, index: number, weight: bigint, proof: Buffer[]) {
+  return dataClaim(epoch, index, weight, proof, OP.CLAIM_COMPOUND);
+}
+
+export function decodeClaimQuote(returnData: Buffer): bigint {
+  return returnData.readBigUInt64LE(0);
+}
+
+export type EpochClaim = { epoch: bigint; index: number; weight: bigint; proof: Buffer[] };
+
+// one claimer's leaves from up to 8 epochs; each entry is dataClaim's payload without the op byte.
+// Keep entries paid from the shared boost account next to each other so they share one transfer.
+export function dataClaimEpochs(claims: EpochClaim[]) {
+  const entries = claims.map((c) => dataClaim(c.epoch, c.index, c.weight, c.proof).subarray(1));
+  return Buffer.concat([Buffer.from([OP.CLAIM_EPOCHS, claims.length & 0xff]), ...entries]);
+}
+
+// multiproof claim for up to 8 leaves of one epoch; leaves in tree order, and the
+// accounts after usdc_mint are each leaf's claimer-owned USDC account in the same order
+export function dataClaimMany(epoch: bigint, leaves: { index: number; weight: bigint }[], proof: Buffer[], flags: boolean[]) {
+  const b = Buffer.alloc(1 + 8 + 1 + 20 * leaves.length + 1 + 32 * proof.length + 1 + flags.length);
+  b[0] = OP.CLAIM_MANY;
+  b.writeBigUInt64LE(epoch, 1);
+  b[9] = leaves.length & 0xff;
+  let off = 10;
+  for (const l of leaves) {
+    b.writeUInt32LE(l.index >>> 0, off);
+    writeU128LE(l.weight, b, off + 4);
+    off += 20;
+  }
+  b[off++] = proof.length & 0xff;
+  proof.forEach((p) => { p.copy(b, off); off += 32; });
+  b[off++] = flags.length & 0xff;
+  flags.forEach((f) => { b[off++] = f ? 1 : 0; });
+  return b;
+}
+
+// operator crank: same payload as dataClaim, for a user who opted in
+export function dataCrankClaim(epoch: bigint, index: number, weight: bigint, proof: Buffer[]) {
+  return dataClaim(epoch, index, weight, proof, OP.CRANK_CLAIM);
+}
+
+export function dataSetAutoClaim(autoClaim: boolean, compound: boolean) {
+  return Buffer.from([OP.SET_AUTO_CLAIM, autoClaim ? 1 : 0, compound ? 1 : 0]);
+}
+
+// accounts: vault_state, admin(ws), target(w), system_program. A CLAIMS_BITMAP target
+// takes the page as well (page 0 is the epoch's first bitmap; see claimPage).
+export function dataReconcileRent(kind: number, key?: { epoch: bigint; page?: number } | { user: Address }) {
+  const b = Buffer.alloc(1 + 1 + 32);
+  b[0] = OP.RECONCILE_RENT;
+  b[1] = kind & 0xff;
+  if (key && "epoch" in key) {
+    b.writeBigUInt64LE(key.epoch, 2);
+    if (kind !== RENT_TARGET.CLAIMS_BITMAP) return b.subarray(0, 10);
+    b.writeUInt16LE(key.page ?? 0, 10);
+    return b.subarray(0, 12);
+  }
+  if (key && "user" in key) {
+    Buffer.from(getAddressEncoder().encode(key.user)).copy(b, 2);
+    return b;
+  }
+  return b.subarray(0, 2);
+}
+
+export function dataSetYieldOracle(yieldOracle: Address, toleranceBps: number) {
+  const b = Buffer.alloc(1 + 32 + 2);
+  b[0] = OP.SET_YIELD_ORACLE;
+  Buffer.from(getAddressEncoder().encode(yieldOracle)).copy(b, 1);
+  b.writeUInt16LE(toleranceBps, 33);
+  return b;
+}
+
+export function dataSetDonors(restrict: boolean, donors: Address[]) {
+  const b = Buffer.alloc(1 + 1 + 1 + 32 * donors.length);
+  b[0] = OP.SET_DONORS;
+  b[1] = restrict ? 1 : 0;
+  b[2] = donors.length & 0xff;
+  const enc = getAddressEncoder();
+  donors.forEach((d, i) => Buffer.from(enc.encode(d)).copy(b, 3 + i * 32));
+  return b;
+}
+
+// PostRoot then needs `threshold` of these as signers (operator or extra accounts after the distributor)
+export function dataSetRootSigners(threshold: number, signers: Address[]) {
+  const b = Buffer.alloc(1 + 1 + 1 + 32 * signers.length);
+  b[0] = OP.SET_ROOT_SIGNERS;
+  b[1] = threshold & 0xff;
+  b[2] = signers.length & 0xff;
+  const enc = getAddressEncoder();
+  signers.forEach((k, i) => Buffer.from(enc.encode(k)).copy(b, 3 + i * 32));
+  return b;
+}
+
+export function dataSetPpsCeiling(ppsCeiling: bigint) {
+  const b = Buffer.alloc(1 + 16);
+  b[0] = OP.SET_PPS_CEILING;
+  writeU128LE(ppsCeiling, b, 1);
+  return b;
+}
+
+// accounts: vault_state(w), program_data (deriveProgramData); permissionless
+export function dataRecordUpgradeAuthority() {
+  return Buffer.from([OP.RECORD_UPGRADE_AUTHORITY]);
+}
+
+// accounts: vault_state(w), then optionally vault_pda, vault_usdc_ata(w), caller_usdc_ata(w),
+// token_program, usdc_mint to receive the settle tip
+export function dataSettle() {
+  return Buffer.from([OP.SETTLE]);
+}
+
+// full new bitmask of PAUSE values; signer is the admin (any bit) or the operator (DONATE/CLAIM only)
+export function dataSetPauseFlags(flags: number) {
+  const b = Buffer.alloc(1 + 4);
+  b[0] = OP.SET_PAUSE_FLAGS;
+  b.writeUInt32LE(flags >>> 0, 1);
+  return b;
+}
+
+// admin; 0n removes the cap (USDC base units of total managed assets)
+export function dataSetDepositCap(cap: bigint) {
+  const b = Buffer.alloc(1 + 8);
+  b[0] = OP.SET_DEPOSIT_CAP;
+  b.writeBigUInt64LE(cap, 1);
+  return b;
+}
+
+// anyone (simulate to monitor); accounts: vault_state, share_mint. Fails with
+// ShareSupplyDrift (27) when the mint supply differs from total_shares.
+export function dataCheckShareSupply() {
+  return Buffer.from([OP.CHECK_SHARE_SUPPLY]);
+}
+
+// admin; accounts: vault_state(w), admin(s), share_mint. Resets total_shares to the mint supply.
+export function dataRepairShareSupply() {
+  return Buffer.from([OP.REPAIR_SHARE_SUPPLY]);
+}
+
+// admin; 0n removes the per-wallet cap on cumulative deposits
+export function dataSetUserDepositCap(cap: bigint) {
+  const b = Buffer.alloc(1 + 8);
+  b[0] = OP.SET_USER_DEPOSIT_CAP;
+  b.writeBigUInt64LE(cap, 1);
+  return b;
+}
+
+// admin; the default address removes the guardian
+export function dataSetGuardian(guardian: Address) {
+  const b = Buffer.alloc(1 + 32);
+  b[0] = OP.SET_GUARDIAN;
+  Buffer.from(getAddressEncoder().encode(guardian)).copy(b, 1);
+  return b;
+}
+
+// admin; the vault MigratePosition moves depositors to. Its program must take this
+// program's Deposit instruction. Default addresses turn migration off.
+export function dataSetSuccessor(successorProgram: Address, successor: Address) {
+  const b = Buffer.alloc(1 + 32 + 32);
+  b[0] = OP.SET_SUCCESSOR;
+  const enc = getAddressEncoder();
+  Buffer.from(enc.encode(successorProgram)).copy(b, 1);
+  Buffer.from(enc.encode(successor)).copy(b, 33);
+  return b;
+}
+
+// user; accounts from migrateAccounts. shares = 0 migrates the whole balance.
+// `successorProgram` and `successor` are the program and vault_state the user agreed to;
+// a different registered pair fails with SuccessorMismatch (38).
+export function dataMigratePosition(
+  shares: bigint, usdcDecimals: number, successorProgram: Address, successor: Address, minSharesOut?: bigint,
+) {
+  const b = Buffer.alloc(1 + 8 + 1 + 32 + 32 + (minSharesOut === undefined ? 0 : 8));
+  b[0] = OP.MIGRATE_POSITION;
+  b.writeBigUInt64LE(shares, 1);
+  b[9] = usdcDecimals & 0xff;
+  const enc = getAddressEncoder();
+  Buffer.from(enc.encode(successorProgram)).copy(b, 10);
+  Buffer.from(enc.encode(successor)).copy(b, 42);
+  if (minSharesOut !== undefined) b.writeBigUInt64LE(minSharesOut, 74);
+  return b;
+}
+
+// admin; slots without a donation or settle before anyone may TripWatchdog (0n turns it off)
+export function dataSetLivenessWindow(slots: bigint) {
+  const b = Buffer.alloc(1 + 8);
+  b[0] = OP.SET_LIVENESS_WINDOW;
+  b.writeBigUInt64LE(slots, 1);
+  return b;
+}
+
+// anyone; accounts: vault_state(w), caller(s). Pauses deposits and clears the withdraw
+// bit once the operator has been silent past the window, else fails with OperatorActive (39).
+export function dataTripWatchdog() {
+  return Buffer.from([OP.TRIP_WATCHDOG]);
+}
+
+// guardian or admin; accounts: vault_state(w), authority(s). Only the admin's SetPauseFlags clears it.
+export function dataEnterEmergency() {
+  return Buffer.from([OP.ENTER_EMERGENCY]);
+}
+
+// admin; the default address cancels a pending proposal
+export function dataProposeAdmin(newAdmin: Address) {
+  const b = Buffer.alloc(1 + 32);
+  b[0] = OP.PROPOSE_ADMIN;
+  Buffer.from(getAddressEncoder().encode(newAdmin)).copy(b, 1);
+  return b;
+}
+
+// accounts: vault_state(w), new_admin(s)
+export function dataAcceptAdmin() {
+  return Buffer.from([OP.ACCEPT_ADMIN]);
+}
+
+// operator; accounts: vault_state, operator(ws), boost_distributor(w), escrow(w) (deriveBoostEscrow),
+// usdc_mint, system_program, token_program. Must precede the epoch's first donation.
+export function dataOpenBoostEscrow(epoch: bigint) {
+  const b = Buffer.alloc(1 + 8);
+  b[0] = OP.OPEN_BOOST_ESCROW;
+  b.writeBigUInt64LE(epoch, 1);
+  return b;
+}
+
+// permissionless; accounts: vault_state, payer(ws), boost_distributor(w) (deriveBoostDistributor),
+// system_program. Must run before the epoch's first boost donation and CommitWeights.
+export function dataOpenDistributor(epoch: bigint) {
+  const b = Buffer.alloc(1 + 8);
+  b[0] = OP.OPEN_DISTRIBUTOR;
+  b.writeBigUInt64LE(epoch, 1);
+  return b;
+}
+
+// permissionless; accounts: vault_state, payer(ws), boost_distributor, page(w)
+// (deriveClaimsBitmap with the page), system_program. Page 0 may be opened for any epoch;
+// later pages only below the distributor's bitmap_page_count.
+export function dataOpenClaimPage(epoch: bigint, page: number) {
+  const b = Buffer.alloc(1 + 8 + 2);
+  b[0] = OP.OPEN_CLAIM_PAGE;
+  b.writeBigUInt64LE(epoch, 1);
+  b.writeUInt16LE(page, 9);
+  return b;
+}
+
+// operator; accounts: vault_state(w), vault_pda, operator(s), boost_distributor(w),
+// boost_usdc_ata(w) (the epoch's escrow, or the shared boost account), vault_usdc_ata(w),
+// token_program, usdc_mint. Fails with ClaimWindowOpen (29) before the distributor's expiry_slot.
+export function dataClawback(epoch: bigint) {
+  const b = Buffer.alloc(1 + 8);
+  b[0] = OP.CLAWBACK;
+  b.writeBigUInt64LE(epoch, 1);
+  return b;
+}
+
+// operator; accounts: vault_state, operator(s), boost_distributor(w). Locks the posted
+// root: PostRoot then fails with EpochFinalized (30) for this epoch.
+export function dataFinalizeEpoch(epoch: bigint) {
+  const b = Buffer.alloc(1 + 8);
+  b[0] = OP.FINALIZE_EPOCH;
+  b.writeBigUInt64LE(epoch, 1);
+  return b;
+}
+
+// operator; accounts: vault_state, operator(s), boost_distributor(w). Pins the sha256 of the
+// epoch's archive (interest-cli epoch-archive) once it is expired and clawed back; fails with
+// ClawbackPending (40) while boost is unpaid and ArchiveRecorded (41) the second time.
+export function dataRecordArchive(epoch: bigint, archiveHash: Buffer) {
+  const b = Buffer.alloc(1 + 8 + 32);
+  b[0] = OP.RECORD_ARCHIVE;
+  b.writeBigUInt64LE(epoch, 1);
+  archiveHash.copy(b, 9);
+  return b;
+}
+
+// operator; accounts: vault_state, operator(ws), boost_distributor, report(w) (deriveEpochReport),
+// system_program. Records the epoch's figures once its root is posted; boost_allocated is
+// read from the distributor. strategyPnl is signed (a loss is negative); fees <= grossYield.
+export function dataReport(epoch: bigint, grossYield: bigint, fees: bigint, strategyPnl: bigint) {
+  const b = Buffer.alloc(1 + 8 + 8 + 8 + 8);
+  b[0] = OP.REPORT;
+  b.writeBigUInt64LE(epoch, 1);
+  b.writeBigUInt64LE(grossYield, 9);
+  b.writeBigUInt64LE(fees, 17);
+  b.writeBigInt64LE(strategyPnl, 25);
+  return b;
+}
+
+// anyone; accounts: vault_state, payer(ws), claimer, claim_escrow(w) (deriveClaimEscrow),
+// usdc_mint, system_program, token_program
+export function dataOpenClaimEscrow() {
+  return Buffer.from([OP.OPEN_CLAIM_ESCROW]);
+}
+
+// claimer; accounts from redeemEscrowAccounts. Sends the escrow's whole balance; fails
+// with InsufficientFunds when it is empty.
+export function dataRedeemEscrow() {
+  return Buffer.from([OP.REDEEM_ESCROW]);
+}
+
+export function dataSetBurnDust(burnDust: boolean) {
+  return Buffer.from([OP.SET_BURN_DUST, burnDust ? 1 : 0]);
+}
+
 function writeU128LE(n: bigint, out: Buffer, off: number) {
   let x = n;
   for (let i = 0; i < 16; i++) {
@@ -1,15 +1,68 @@
 import { address, getProgramDerivedAddress, getAddressEncoder, type Address } from "gill";
 
 export const SEED_VAULT = Buffer.from("vault");
+export const SEED_STATE = Buffer.from("state");
 export const SEED_AUTH  = Buffer.from("vault_auth");
 export const SEED_BOOST = Buffer.from("boost");
 export const SEED_CLAIMS = Buffer.from("claims");
+export const SEED_SHARES = Buffer.from("shares");
+export const SEED_PREFS = Buffer.from("prefs");
+export const SEED_ESCROW = Buffer.from("escrow");
+export const SEED_POSITION = Buffer.from("position");
+export const SEED_CLAIM_ESCROW = Buffer.from("claim_escrow");
+export const SEED_REGISTRY = Buffer.from("registry");
+export const SEED_REPORT = Buffer.from("report");
+export const SEED_BOOST_USDC = Buffer.from("boost_usdc");
 
-export async function deriveVaultPda(program: Address, usdcMint: Address, admin: Address) {
+// vault_states per registry page (the program's REGISTRY_PAGE_VAULTS)
+export const REGISTRY_PAGE_VAULTS = 64;
+
+export const BPF_LOADER_UPGRADEABLE = address("BPFLoaderUpgradeab1e11111111111111111111111");
+
+// InitializeVault creates the vault_state here: one vault per (usdc mint, admin, vault id)
+export async function deriveVaultState(program: Address, usdcMint: Address, admin: Address, vaultId = 0n) {
+  const enc = getAddressEncoder();
+  const idBuf = Buffer.alloc(8);
+  idBuf.writeBigUInt64LE(vaultId);
+  return getProgramDerivedAddress({
+    programAddress: program,
+    seeds: [SEED_STATE, enc.encode(usdcMint), enc.encode(admin), idBuf]
+  });
+}
+
+// `admin` is the vault's original admin (VaultState.seed_admin), not necessarily the current one
+export async function deriveVaultPda(program: Address, usdcMint: Address, admin: Address, vaultId = 0n) {
+  const enc = getAddressEncoder();
+  const idBuf = Buffer.alloc(8);
+  idBuf.writeBigUInt64LE(vaultId);
+  return getProgramDerivedAddress({
+    programAddress: program,
+    seeds: [SEED_VAULT, enc.encode(usdcMint), enc.encode(admin), idBuf]
+  });
+}
+
+export async function deriveShareMint(program: Address, vaultState: Address) {
+  const enc = getAddressEncoder();
+  return getProgramDerivedAddress({
+    programAddress: program,
+    seeds: [SEED_SHARES, enc.encode(vaultState)]
+  });
+}
+
+export async function deriveUserPrefs(program: Address, vaultState: Address, user: Address) {
   const enc = getAddressEncoder();
   return getProgramDerivedAddress({
     programAddress: program,
-    seeds: [SEED_VAULT, enc.encode(usdcMint), enc.encode(admin)]
+    seeds: [SEED_PREFS, enc.encode(vaultState), enc.encode(user)]
+  });
+}
+
+// cumulative-deposit tally; pass it (and the system program) after Deposit's usdc_mint
+export async function deriveUserPosition(program: Address, vaultState: Address, user: Address) {
+  const enc = getAddressEncoder();
+  return getProgramDerivedAddress({
+    programAddress: program,
+    seeds: [SEED_POSITION, enc.encode(vaultState), enc.encode(user)]
   });
 }
 
@@ -31,14 +84,83 @@ export async function deriveBoostDistributor(program: Address, vaultPda: Address
   });
 }
 
-export async function deriveClaimsBitmap(program: Address, vaultPda: Address, epoch: bigint) {
-  const enc = getAddressEncoder();
+// the operator's performance report for `epoch`, written once by Report
+export async function deriveEpochReport(program: Address, vaultPda: Address, epoch: bigint) {
   const epochBuf = Buffer.alloc(8);
   epochBuf.writeBigUInt64LE(epoch);
   return getProgramDerivedAddress({
     programAddress: program,
-    seeds: [SEED_CLAIMS, enc.encode(vaultPda), epochBuf]
+    seeds: [SEED_REPORT, getAddressEncoder().encode(vaultPda), epochBuf],
+  });
+}
+
+// the shared boost account InitializeVault creates; owned by vault_pda
+export async function deriveBoostUsdc(program: Address, vaultPda: Address) {
+  return getProgramDerivedAddress({
+    programAddress: program,
+    seeds: [SEED_BOOST_USDC, getAddressEncoder().encode(vaultPda)],
+  });
+}
+
+// per-epoch boost token account; it is its own token authority
+export async function deriveBoostEscrow(program: Address, distributor: Address) {
+  return getProgramDerivedAddress({
+    programAddress: program,
+    seeds: [SEED_ESCROW, getAddressEncoder().encode(distributor)]
   });
 }
 
+// where claims park a claimer's payout while their USDC account is frozen or closed;
+// it is its own token authority, drained by RedeemEscrow
+export async function deriveClaimEscrow(program: Address, vaultState: Address, claimer: Address) {
+  const enc = getAddressEncoder();
+  return getProgramDerivedAddress({
+    programAddress: program,
+    seeds: [SEED_CLAIM_ESCROW, enc.encode(vaultState), enc.encode(claimer)]
+  });
+}
+
+// leaves per claim bitmap page (one ClaimBitmap256)
+export const CLAIM_PAGE_BITS = 256;
+
+// page holding leaf `index`
+export function claimPage(index: number) {
+  return Math.floor(index / CLAIM_PAGE_BITS);
+}
+
+// page 0 keeps the original seeds; later pages append the page number (u16 LE)
+export async function deriveClaimsBitmap(program: Address, vaultPda: Address, epoch: bigint, page = 0) {
+  const enc = getAddressEncoder();
+  const epochBuf = Buffer.alloc(8);
+  epochBuf.writeBigUInt64LE(epoch);
+  const seeds = [SEED_CLAIMS, enc.encode(vaultPda), epochBuf];
+  if (page > 0) {
+    const pageBuf = Buffer.alloc(2);
+    pageBuf.writeUInt16LE(page);
+    seeds.push(pageBuf);
+  }
+  return getProgramDerivedAddress({ programAddress: program, seeds });
+}
+
 
+
+// header of the list of every vault InitializeVault created; holds the count
+export async function deriveRegistry(program: Address) {
+  return getProgramDerivedAddress({ programAddress: program, seeds: [SEED_REGISTRY] });
+}
+
+// page `page` holds vaults page * REGISTRY_PAGE_VAULTS onwards
+export async function deriveRegistryPage(program: Address, page: number) {
+  const pageBuf = Buffer.alloc(4);
+  pageBuf.writeUInt32LE(page);
+  return getProgramDerivedAddress({ programAddress: program, seeds: [SEED_REGISTRY, pageBuf] });
+}
+
+// the program's own ProgramData account, read by RecordUpgradeAuthority
+export async function deriveProgramData(program: Address) {
+  const enc = getAddressEncoder();
+  return getProgramDerivedAddress({
+    programAddress: BPF_LOADER_UPGRADEABLE,
+    seeds: [enc.encode(program)]
+  });
+}
@@ -0,0 +1,110 @@
+// Fixtures shared by the LiteSVM tests: packed SPL accounts and an initialized vault.
+// Each test binary compiles its own copy and uses a subset of it.
+#![allow(dead_code)]
+
+use litesvm::LiteSVM;
+use solana_account::Account;
+use solana_instruction::{Instruction, account_meta::AccountMeta};
+use solana_keypair::Keypair;
+use solana_message::Message;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+pub const TOKEN_PROGRAM_ID: Pubkey = Pubkey::from_str_const("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::from_str_const("11111111111111111111111111111111");
+pub const ATA_PROGRAM_ID: Pubkey = Pubkey::from_str_const("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+// build it first via ./scripts/build-program.sh
+pub const VAULT_SO: &[u8] = include_bytes!("../../../../programs/interest_vault/target/deploy/interest_vault.so");
+
+// Packed SPL Mint: COption<authority>, supply, decimals, is_initialized, COption<freeze>
+pub fn mint_data(authority: &Pubkey, decimals: u8) -> Vec<u8> {
+    let mut d = vec![0u8; 82];
+    d[0..4].copy_from_slice(&1u32.to_le_bytes());
+    d[4..36].copy_from_slice(authority.as_ref());
+    d[44] = decimals;
+    d[45] = 1;
+    d
+}
+
+// Packed SPL token account: mint, owner, amount, COption<delegate>, state, ...
+pub fn token_account_data(mint: &Pubkey, owner: &Pubkey, amount: u64) -> Vec<u8> {
+    let mut d = vec![0u8; 165];
+    d[0..32].copy_from_slice(mint.as_ref());
+    d[32..64].copy_from_slice(owner.as_ref());
+    d[64..72].copy_from_slice(&amount.to_le_bytes());
+    d[108] = 1; // initialized
+    d
+}
+
+pub fn account(data: Vec<u8>, owner: Pubkey) -> Account {
+    Account { lamports: 1_000_000_000, data, owner, executable: false, rent_epoch: 0 }
+}
+
+pub fn ata(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[owner.as_ref(), token_program.as_ref(), mint.as_ref()], &ATA_PROGRAM_ID).0
+}
+
+pub fn vault_state_key(program_id: &Pubkey, usdc_mint: &Pubkey, admin: &Pubkey, vault_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"state", usdc_mint.as_ref(), admin.as_ref(), &vault_id.to_le_bytes()], program_id).0
+}
+
+// InitializeVault accounts: vault_state(w), admin(ws), operator, usdc_mint, share_mint(w), vault_pda, system, token,
+// registry(w), registry_page(w), vault_usdc_ata(w), boost_usdc(w), ata program. PDAs are checked by the program, so
+// compute them off-chain.
+pub fn init_ix(program_id: Pubkey, vault_state: Pubkey, admin: &Pubkey, operator: &Pubkey, usdc_mint: Pubkey, vault_id: u64) -> Instruction {
+    let id_le = vault_id.to_le_bytes();
+    let (vault_pda, _) = Pubkey::find_program_address(&[b"vault".as_ref(), usdc_mint.as_ref(), admin.as_ref(), &id_le], &program_id);
+    let (share_mint, _) = Pubkey::find_program_address(&[b"shares".as_ref(), vault_state.as_ref()], &program_id);
+    let (boost_usdc, _) = Pubkey::find_program_address(&[b"boost_usdc".as_ref(), vault_pda.as_ref()], &program_id);
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(vault_state, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(*operator, false),
+            AccountMeta::new_readonly(usdc_mint, false),
+            AccountMeta::new(share_mint, false),
+            AccountMeta::new_readonly(vault_pda, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            // every test starts from an empty registry, so the vault lands on page 0
+            AccountMeta::new(Pubkey::find_program_address(&[b"registry"], &program_id).0, false),
+            AccountMeta::new(Pubkey::find_program_address(&[b"registry", &0u32.to_le_bytes()], &program_id).0, false),
+            AccountMeta::new(ata(&vault_pda, &usdc_mint, &TOKEN_PROGRAM_ID), false),
+            AccountMeta::new(boost_usdc, false),
+            AccountMeta::new_readonly(ATA_PROGRAM_ID, false),
+        ],
+        // [tag=INIT]; share decimals come from the USDC mint. A vault_id has to follow
+        // explicit share decimals.
+        data: if vault_id == 0 { vec![0u8] } else { [&[0u8, 6][..], &id_le].concat() },
+    }
+}
+
+pub struct Vault {
+    pub vault_state: Pubkey,
+    pub vault_pda: Pubkey,
+    pub auth_pda: Pubkey,
+    pub usdc_mint: Pubkey,
+    pub share_mint: Pubkey,
+    pub vault_usdc: Pubkey, // vault_pda's USDC ATA, created by init
+    pub boost_usdc: Pubkey, // the shared boost account, created by init
+}
+
+// A 6-decimal USDC mint and vault 0 initialized on it by `admin`, with `operator` as its
+// operator; init creates the vault's USDC and boost accounts, both empty. The admin is
+// funded here.
+pub fn init_vault(svm: &mut LiteSVM, program_id: Pubkey, admin: &Keypair, operator: &Pubkey) -> Vault {
+    let usdc_mint = Pubkey::new_unique();
+    svm.set_account(usdc_mint, account(mint_data(&Pubkey::new_unique(), 6), TOKEN_PROGRAM_ID)).unwrap();
+    let vault_state = vault_state_key(&program_id, &usdc_mint, &admin.pubkey(), 0);
+    let ix = init_ix(program_id, vault_state, &admin.pubkey(), operator, usdc_mint, 0);
+    let (share_mint, vault_pda) = (ix.accounts[4].pubkey, ix.accounts[5].pubkey);
+    let (vault_usdc, boost_usdc) = (ix.accounts[10].pubkey, ix.accounts[11].pubkey);
+    svm.airdrop(&admin.pubkey(), 10_000_000_000).unwrap();
+    let tx = Transaction::new(&[admin], Message::new(&[ix], Some(&admin.pubkey())), svm.latest_blockhash());
+    svm.send_transaction(tx).unwrap();
+
+    let (auth_pda, _) = Pubkey::find_program_address(&[b"vault_auth".as_ref(), vault_pda.as_ref()], &program_id);
+    Vault { vault_state, vault_pda, auth_pda, usdc_mint, share_mint, vault_usdc, boost_usdc }
+}
@@ -0,0 +1,284 @@
+// Token CPIs that fail partway through Deposit, Withdraw, DonateReward and Claim.
+// Every handler writes its own state before the CPI that hands value out, so a failing
+// CPI must roll those writes back with the transaction: after each failure every
+// touched account is byte-for-byte what it was before.
+mod common;
+
+use common::{TOKEN_PROGRAM_ID, VAULT_SO, Vault, account, init_vault, token_account_data};
+use interest_vault::{BoostDistributor, ClaimBitmap256, Discriminator};
+use litesvm::LiteSVM;
+use solana_instruction::{Instruction, account_meta::AccountMeta};
+use solana_keypair::Keypair;
+use solana_message::Message;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+const FUNDING: u64 = 100_000_000;
+const DEPOSIT: u64 = 10_000_000;
+const EPOCH: u64 = 1;
+const WEIGHT: u128 = 100;
+
+struct Env {
+    svm: LiteSVM,
+    program_id: Pubkey,
+    user: Keypair,
+    vault_state: Pubkey,
+    vault_pda: Pubkey,
+    auth_pda: Pubkey,
+    usdc_mint: Pubkey,
+    share_mint: Pubkey,
+    user_usdc: Pubkey,
+    user_shares: Pubkey,
+    vault_ata: Pubkey,
+    boost_ata: Pubkey,
+}
+
+impl Env {
+    // Initialized vault; `user` holds FUNDING USDC and an empty share account, and is
+    // also the donor and the only leaf of the claim tree
+    fn new() -> Self {
+        let program_id = Pubkey::new_unique();
+        let mut svm = LiteSVM::new();
+        svm.add_program(program_id, VAULT_SO);
+
+        let admin = Keypair::new();
+        let user = Keypair::new();
+        let Vault { vault_state, vault_pda, auth_pda, usdc_mint, share_mint, vault_usdc: vault_ata, boost_usdc: boost_ata } = init_vault(&mut svm, program_id, &admin, &admin.pubkey());
+        svm.airdrop(&user.pubkey(), 10_000_000_000).unwrap();
+
+        let (user_usdc, user_shares) = (Pubkey::new_unique(), Pubkey::new_unique());
+        svm.set_account(user_usdc, account(token_account_data(&usdc_mint, &user.pubkey(), FUNDING), TOKEN_PROGRAM_ID)).unwrap();
+        svm.set_account(user_shares, account(token_account_data(&share_mint, &user.pubkey(), 0), TOKEN_PROGRAM_ID)).unwrap();
+
+        Env { svm, program_id, user, vault_state, vault_pda, auth_pda, usdc_mint, share_mint, user_usdc, user_shares, vault_ata, boost_ata }
+    }
+
+    fn send(&mut self, accounts: Vec<AccountMeta>, data: Vec<u8>) -> Result<(), String> {
+        let ix = Instruction { program_id: self.program_id, accounts, data };
+        self.svm.expire_blockhash();
+        let tx = Transaction::new(&[&self.user], Message::new(&[ix], Some(&self.user.pubkey())), self.svm.latest_blockhash());
+        self.svm.send_transaction(tx).map(|_| ()).map_err(|e| format!("{:?}", e.err))
+    }
+
+    fn deposit(&mut self, amount: u64) -> Result<(), String> {
+        let mut data = vec![1u8];
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.push(6);
+        let accounts = vec![
+            AccountMeta::new(self.vault_state, false),
+            AccountMeta::new_readonly(self.auth_pda, false),
+            AccountMeta::new_readonly(self.user.pubkey(), true),
+            AccountMeta::new(self.user_usdc, false),
+            AccountMeta::new(self.vault_ata, false),
+            AccountMeta::new(self.share_mint, false),
+            AccountMeta::new(self.user_shares, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(self.usdc_mint, false),
+        ];
+        self.send(accounts, data)
+    }
+
+    fn withdraw(&mut self, shares: u64) -> Result<(), String> {
+        let mut data = vec![2u8];
+        data.extend_from_slice(&shares.to_le_bytes());
+        data.push(6);
+        let accounts = vec![
+            AccountMeta::new(self.vault_state, false),
+            AccountMeta::new_readonly(self.vault_pda, false),
+            AccountMeta::new_readonly(self.user.pubkey(), true),
+            AccountMeta::new(self.user_usdc, false),
+            AccountMeta::new(self.vault_ata, false),
+            AccountMeta::new(self.share_mint, false),
+            AccountMeta::new(self.user_shares, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(self.usdc_mint, false),
+        ];
+        self.send(accounts, data)
+    }
+
+    fn donate(&mut self, amount: u64, boost_bps: u16, distributor: Pubkey) -> Result<(), String> {
+        let mut data = vec![3u8];
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.extend_from_slice(&EPOCH.to_le_bytes());
+        data.extend_from_slice(&boost_bps.to_le_bytes());
+        data.push(6);
+        let accounts = vec![
+            AccountMeta::new(self.vault_state, false),
+            AccountMeta::new_readonly(self.vault_pda, false),
+            AccountMeta::new_readonly(self.user.pubkey(), true),
+            AccountMeta::new(self.user_usdc, false),
+            AccountMeta::new(self.vault_ata, false),
+            AccountMeta::new(self.boost_ata, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(self.usdc_mint, false),
+            AccountMeta::new(distributor, false),
+        ];
+        self.send(accounts, data)
+    }
+
+    // single-leaf tree for `user`, so the root is the leaf and the proof is empty
+    fn claim(&mut self, distributor: Pubkey, bitmap: Pubkey) -> Result<(), String> {
+        let mut data = vec![5u8];
+        data.extend_from_slice(&EPOCH.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&WEIGHT.to_le_bytes());
+        data.push(0);
+        let accounts = vec![
+            AccountMeta::new(self.vault_state, false),
+            AccountMeta::new_readonly(self.vault_pda, false),
+            AccountMeta::new_readonly(self.user.pubkey(), true),
+            AccountMeta::new(distributor, false),
+            AccountMeta::new(bitmap, false),
+            AccountMeta::new(self.boost_ata, false),
+            AccountMeta::new(self.user_usdc, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(self.usdc_mint, false),
+        ];
+        self.send(accounts, data)
+    }
+
+    // distributor for EPOCH with a posted single-leaf root and `boost_total` to pay out
+    fn posted_distributor(&mut self, boost_total: u64) -> (Pubkey, Pubkey) {
+        let leaf = solana_keccak_hasher::hashv(&[
+            b"weight", &0u32.to_le_bytes(), self.user.pubkey().as_ref(), &WEIGHT.to_le_bytes(),
+        ]).to_bytes();
+        // claims only accept the distributor and bitmap page at their PDAs; a single leaf lives on page 0
+        let (distributor, distributor_bump) = self.distributor_pda();
+        let (bitmap, bitmap_bump) = Pubkey::find_program_address(
+            &[b"claims".as_ref(), self.vault_pda.as_ref(), &EPOCH.to_le_bytes()], &self.program_id,
+        );
+        let bd = BoostDistributor {
+            discriminator: BoostDistributor::DISCRIMINATOR, epoch: EPOCH, root: leaf, leaf_count: 1, total_weight: WEIGHT, boost_total,
+            bitmap_page_count: 1, bitmap_page_size: 256, bump: distributor_bump,
+            ..bytemuck::Zeroable::zeroed()
+        };
+        self.svm.set_account(distributor, account(bytemuck::bytes_of(&bd).to_vec(), self.program_id)).unwrap();
+        let bm = ClaimBitmap256 { discriminator: ClaimBitmap256::DISCRIMINATOR, bump: bitmap_bump, ..bytemuck::Zeroable::zeroed() };
+        self.svm.set_account(bitmap, account(bytemuck::bytes_of(&bm).to_vec(), self.program_id)).unwrap();
+        (distributor, bitmap)
+    }
+
+    // EPOCH's distributor as OpenDistributor leaves it, ready for a donation to credit
+    fn open_distributor(&mut self) -> Pubkey {
+        let (key, bump) = self.distributor_pda();
+        let bd = BoostDistributor { discriminator: BoostDistributor::DISCRIMINATOR, epoch: EPOCH, bump, ..bytemuck::Zeroable::zeroed() };
+        self.svm.set_account(key, account(bytemuck::bytes_of(&bd).to_vec(), self.program_id)).unwrap();
+        key
+    }
+
+    fn distributor_pda(&self) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"boost".as_ref(), self.vault_pda.as_ref(), &EPOCH.to_le_bytes()], &self.program_id)
+    }
+
+    fn set_balance(&mut self, ata: Pubkey, amount: u64) {
+        let mut acc = self.svm.get_account(&ata).unwrap();
+        acc.data[64..72].copy_from_slice(&amount.to_le_bytes());
+        self.svm.set_account(ata, acc).unwrap();
+    }
+
+    fn set_frozen(&mut self, ata: Pubkey, frozen: bool) {
+        let mut acc = self.svm.get_account(&ata).unwrap();
+        acc.data[108] = if frozen { 2 } else { 1 }; // AccountState::Frozen / Initialized
+        self.svm.set_account(ata, acc).unwrap();
+    }
+
+    fn balance(&self, ata: &Pubkey) -> u64 {
+        let d = self.svm.get_account(ata).unwrap().data;
+        u64::from_le_bytes(d[64..72].try_into().unwrap())
+    }
+
+    fn snapshot(&self, keys: &[Pubkey]) -> Vec<Vec<u8>> {
+        keys.iter().map(|k| self.svm.get_account(k).unwrap().data).collect()
+    }
+
+    fn touched(&self) -> Vec<Pubkey> {
+        vec![self.vault_state, self.share_mint, self.user_usdc, self.user_shares, self.vault_ata, self.boost_ata]
+    }
+}
+
+// runs `op`, which must fail, and checks that none of `keys` changed
+fn assert_rolled_back(env: &mut Env, keys: &[Pubkey], op: impl FnOnce(&mut Env) -> Result<(), String>) {
+    let before = env.snapshot(keys);
+    assert!(op(env).is_err());
+    assert_eq!(env.snapshot(keys), before);
+}
+
+#[test]
+fn deposit_rolls_back_when_usdc_pull_fails() {
+    let mut env = Env::new();
+    env.set_balance(env.user_usdc, DEPOSIT - 1);
+    let keys = env.touched();
+    assert_rolled_back(&mut env, &keys, |e| e.deposit(DEPOSIT));
+}
+
+#[test]
+fn deposit_rolls_back_when_share_mint_fails() {
+    // the USDC pull has succeeded and total_shares/bootstrap are already written
+    let mut env = Env::new();
+    env.set_frozen(env.user_shares, true);
+    let keys = env.touched();
+    assert_rolled_back(&mut env, &keys, |e| e.deposit(DEPOSIT));
+}
+
+#[test]
+fn withdraw_rolls_back_when_burn_fails() {
+    let mut env = Env::new();
+    env.deposit(DEPOSIT).unwrap();
+    env.set_frozen(env.user_shares, true);
+    let keys = env.touched();
+    assert_rolled_back(&mut env, &keys, |e| e.withdraw(DEPOSIT / 2));
+}
+
+#[test]
+fn withdraw_rolls_back_when_payout_fails() {
+    // total_shares is lowered and the burn has succeeded before the payout
+    let mut env = Env::new();
+    env.deposit(DEPOSIT).unwrap();
+    env.set_balance(env.vault_ata, DEPOSIT / 2 - 1);
+    let keys = env.touched();
+    assert_rolled_back(&mut env, &keys, |e| e.withdraw(DEPOSIT / 2));
+}
+
+#[test]
+fn donate_rolls_back_when_pull_fails() {
+    let mut env = Env::new();
+    env.deposit(DEPOSIT).unwrap();
+    env.set_balance(env.user_usdc, 0);
+    let bd = env.open_distributor();
+    let mut keys = env.touched();
+    keys.push(bd);
+    assert_rolled_back(&mut env, &keys, |e| e.donate(DEPOSIT, 5_000, bd));
+}
+
+#[test]
+fn donate_rolls_back_when_boost_transfer_fails() {
+    // pps and the distributor's boost_total are already bumped when the boost transfer runs
+    let mut env = Env::new();
+    env.deposit(DEPOSIT).unwrap();
+    env.set_frozen(env.boost_ata, true);
+    let bd = env.open_distributor();
+    let mut keys = env.touched();
+    keys.push(bd);
+    assert_rolled_back(&mut env, &keys, |e| e.donate(DEPOSIT, 5_000, bd));
+}
+
+#[test]
+fn claim_rolls_back_when_payout_fails() {
+    // the leaf's bit and claimed_total are recorded before the payout
+    let mut env = Env::new();
+    let (bd, bm) = env.posted_distributor(DEPOSIT);
+    env.set_balance(env.boost_ata, DEPOSIT - 1);
+    let mut keys = env.touched();
+    keys.extend([bd, bm]);
+    assert_rolled_back(&mut env, &keys, |e| e.claim(bd, bm));
+
+    env.set_balance(env.boost_ata, DEPOSIT);
+    env.set_frozen(env.user_usdc, true);
+    assert_rolled_back(&mut env, &keys, |e| e.claim(bd, bm));
+
+    // nothing was left half-claimed: the same leaf still pays out in full
+    env.set_frozen(env.user_usdc, false);
+    env.claim(bd, bm).unwrap();
+    assert_eq!(env.balance(&env.user_usdc), FUNDING + DEPOSIT);
+}
@@ -0,0 +1,97 @@
+mod common;
+
+use common::{TOKEN_PROGRAM_ID, VAULT_SO, Vault, account, init_vault};
+use interest_vault::{Discriminator, UserPrefs, VaultError};
+use litesvm::LiteSVM;
+use solana_instruction::{Instruction, account_meta::AccountMeta};
+use solana_keypair::Keypair;
+use solana_message::Message;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+const OP_CRANK_CLAIM: u8 = 11;
+
+struct Env {
+    svm: LiteSVM,
+    program_id: Pubkey,
+    operator: Keypair,
+    claimer: Pubkey,
+    vault_state: Pubkey,
+    vault_pda: Pubkey,
+    usdc_mint: Pubkey,
+    vault_ata: Pubkey,
+    boost_ata: Pubkey,
+}
+
+impl Env {
+    // Initialized vault, with the vault and boost accounts init created, and a claimer
+    // who opted into compounding crank claims
+    fn new() -> Self {
+        let program_id = Pubkey::new_unique();
+        let mut svm = LiteSVM::new();
+        svm.add_program(program_id, VAULT_SO);
+
+        let operator = Keypair::new();
+        let Vault { vault_state, vault_pda, usdc_mint, vault_usdc: vault_ata, boost_usdc: boost_ata, .. } = init_vault(&mut svm, program_id, &Keypair::new(), &operator.pubkey());
+        svm.airdrop(&operator.pubkey(), 10_000_000_000).unwrap();
+
+        let claimer = Pubkey::new_unique();
+        let (prefs, bump) = Pubkey::find_program_address(&[b"prefs".as_ref(), vault_state.as_ref(), claimer.as_ref()], &program_id);
+        let p = UserPrefs {
+            discriminator: UserPrefs::DISCRIMINATOR, vault_state: vault_state.to_bytes(), user: claimer.to_bytes(),
+            auto_claim: 1, compound: 1, bump, _pad: [0; 5],
+        };
+        svm.set_account(prefs, account(bytemuck::bytes_of(&p).to_vec(), program_id)).unwrap();
+
+        Env { svm, program_id, operator, claimer, vault_state, vault_pda, usdc_mint, vault_ata, boost_ata }
+    }
+
+    // The destination is checked before the claim itself, so the distributor, bitmap and
+    // share accounts are placeholders that do not exist
+    fn crank(&mut self, destination: Pubkey) -> Result<(), String> {
+        let prefs = Pubkey::find_program_address(&[b"prefs".as_ref(), self.vault_state.as_ref(), self.claimer.as_ref()], &self.program_id).0;
+        let ix = Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(self.vault_state, false),
+                AccountMeta::new_readonly(self.vault_pda, false),
+                AccountMeta::new_readonly(self.operator.pubkey(), true),
+                AccountMeta::new_readonly(self.claimer, false),
+                AccountMeta::new_readonly(prefs, false),
+                AccountMeta::new(Pubkey::new_unique(), false),
+                AccountMeta::new(Pubkey::new_unique(), false),
+                AccountMeta::new(self.boost_ata, false),
+                AccountMeta::new(destination, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(self.usdc_mint, false),
+                AccountMeta::new_readonly(Pubkey::new_unique(), false),
+                AccountMeta::new(Pubkey::new_unique(), false),
+                AccountMeta::new(Pubkey::new_unique(), false),
+            ],
+            data: vec![OP_CRANK_CLAIM],
+        };
+        self.svm.expire_blockhash();
+        let tx = Transaction::new(&[&self.operator], Message::new(&[ix], Some(&self.operator.pubkey())), self.svm.latest_blockhash());
+        self.svm.send_transaction(tx).map(|_| ()).map_err(|e| format!("{:?}", e.err))
+    }
+}
+
+#[test]
+fn compounding_crank_rejects_boost_account_as_destination() {
+    let mut env = Env::new();
+    // owned by vault_pda and on the right mint, but paying boost -> boost would mint shares
+    // against USDC that never moved
+    let err = env.crank(env.boost_ata).unwrap_err();
+    let code = format!("Custom({})", VaultError::PayoutAccountMismatch as u32);
+    assert!(err.contains(&code), "{err}");
+}
+
+#[test]
+fn compounding_crank_accepts_vault_account_as_destination() {
+    let mut env = Env::new();
+    // gets past the destination and settle, then fails loading the placeholder distributor
+    // (instruction 0), which the program does not own
+    let err = env.crank(env.vault_ata).unwrap_err();
+    assert_eq!(err, "InstructionError(0, IncorrectProgramId)");
+}
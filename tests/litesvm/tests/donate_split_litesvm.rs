@@ -0,0 +1,196 @@
+mod common;
+
+use common::{SYSTEM_PROGRAM_ID, TOKEN_PROGRAM_ID, VAULT_SO, Vault, account, init_vault, token_account_data};
+use interest_vault::{BoostDistributor, Discriminator, VaultError};
+use litesvm::LiteSVM;
+use solana_instruction::{Instruction, account_meta::AccountMeta};
+use solana_keypair::Keypair;
+use solana_message::Message;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+const DONATION: u64 = 1_000_000;
+
+struct Env {
+    svm: LiteSVM,
+    program_id: Pubkey,
+    donor: Keypair,
+    vault_state: Pubkey,
+    vault_pda: Pubkey,
+    usdc_mint: Pubkey,
+    donor_ata: Pubkey,
+    vault_ata: Pubkey,
+    boost_ata: Pubkey,
+}
+
+impl Env {
+    // Initialized vault with funded donor, vault and boost ATAs
+    fn new() -> Self {
+        let program_id = Pubkey::new_unique();
+        let mut svm = LiteSVM::new();
+        svm.add_program(program_id, VAULT_SO);
+
+        let donor = Keypair::new();
+        let Vault { vault_state, vault_pda, usdc_mint, vault_usdc: vault_ata, boost_usdc: boost_ata, .. } = init_vault(&mut svm, program_id, &Keypair::new(), &donor.pubkey());
+        svm.airdrop(&donor.pubkey(), 10_000_000_000).unwrap();
+
+        let donor_ata = Pubkey::new_unique();
+        svm.set_account(donor_ata, account(token_account_data(&usdc_mint, &donor.pubkey(), 10 * DONATION), TOKEN_PROGRAM_ID)).unwrap();
+
+        Env { svm, program_id, donor, vault_state, vault_pda, usdc_mint, donor_ata, vault_ata, boost_ata }
+    }
+
+    // The epoch's distributor at its PDA, tagged with the epoch as OpenDistributor leaves it
+    fn distributor(&mut self, epoch: u64) -> Pubkey {
+        let (key, bump) = Pubkey::find_program_address(
+            &[b"boost".as_ref(), self.vault_pda.as_ref(), &epoch.to_le_bytes()], &self.program_id,
+        );
+        let bd = BoostDistributor { discriminator: BoostDistributor::DISCRIMINATOR, epoch, bump, ..bytemuck::Zeroable::zeroed() };
+        self.svm.set_account(key, account(bytemuck::bytes_of(&bd).to_vec(), self.program_id)).unwrap();
+        key
+    }
+
+    // distributor = None passes a non-program account in the optional slot
+    fn donate(&mut self, amount: u64, epoch: u64, boost_bps: u16, distributor: Option<Pubkey>) -> Result<(), String> {
+        let mut data = vec![3u8];
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.extend_from_slice(&epoch.to_le_bytes());
+        data.extend_from_slice(&boost_bps.to_le_bytes());
+        data.push(6);
+        let ix = Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(self.vault_state, false),
+                AccountMeta::new_readonly(self.vault_pda, false),
+                AccountMeta::new_readonly(self.donor.pubkey(), true),
+                AccountMeta::new(self.donor_ata, false),
+                AccountMeta::new(self.vault_ata, false),
+                AccountMeta::new(self.boost_ata, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(self.usdc_mint, false),
+                match distributor {
+                    Some(d) => AccountMeta::new(d, false),
+                    None => AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                },
+            ],
+            data,
+        };
+        self.svm.expire_blockhash();
+        let tx = Transaction::new(&[&self.donor], Message::new(&[ix], Some(&self.donor.pubkey())), self.svm.latest_blockhash());
+        self.svm.send_transaction(tx).map(|_| ()).map_err(|e| format!("{:?}", e.err))
+    }
+
+    fn balance(&self, ata: &Pubkey) -> u64 {
+        let d = self.svm.get_account(ata).unwrap().data;
+        u64::from_le_bytes(d[64..72].try_into().unwrap())
+    }
+
+    fn read_distributor(&self, key: &Pubkey) -> BoostDistributor {
+        let d = self.svm.get_account(key).unwrap().data;
+        *bytemuck::from_bytes::<BoostDistributor>(&d[..BoostDistributor::LEN])
+    }
+}
+
+#[test]
+fn donate_splits_boost_and_base_across_bps() {
+    for (bps, boost) in [(0u16, 0u64), (2_500, 250_000), (5_000, 500_000), (9_999, 999_900), (10_000, DONATION)] {
+        for with_distributor in [true, false] {
+            let mut env = Env::new();
+            let bd = with_distributor.then(|| env.distributor(1));
+            env.donate(DONATION, 1, bps, bd).unwrap();
+
+            assert_eq!(env.balance(&env.donor_ata), 9 * DONATION, "bps={bps}");
+            assert_eq!(env.balance(&env.boost_ata), boost, "bps={bps}");
+            assert_eq!(env.balance(&env.vault_ata), DONATION - boost, "bps={bps}");
+            if let Some(bd) = bd {
+                let bd = env.read_distributor(&bd);
+                assert_eq!(bd.epoch, 1);
+                assert_eq!(bd.boost_total, boost);
+            }
+        }
+    }
+}
+
+#[test]
+fn donate_accumulates_boost_total_within_epoch() {
+    let mut env = Env::new();
+    let bd = env.distributor(4);
+    env.donate(DONATION, 4, 2_500, Some(bd)).unwrap();
+    env.donate(DONATION, 4, 10_000, Some(bd)).unwrap();
+    env.donate(DONATION, 4, 0, Some(bd)).unwrap();
+
+    assert_eq!(env.read_distributor(&bd).boost_total, 250_000 + DONATION);
+    assert_eq!(env.balance(&env.boost_ata), 250_000 + DONATION);
+    assert_eq!(env.balance(&env.vault_ata), 3 * DONATION - 250_000 - DONATION);
+}
+
+#[test]
+fn donate_rejects_distributor_epoch_mismatch() {
+    let mut env = Env::new();
+    let bd = env.distributor(7);
+    assert!(env.donate(DONATION, 8, 5_000, Some(bd)).is_err());
+
+    // the failed tx moved nothing
+    assert_eq!(env.balance(&env.donor_ata), 10 * DONATION);
+    assert_eq!(env.balance(&env.boost_ata), 0);
+    assert_eq!(env.read_distributor(&bd).boost_total, 0);
+}
+
+#[test]
+fn donate_rejects_distributor_off_its_pda() {
+    let mut env = Env::new();
+    // program-owned and tagged with the right epoch, but not at the epoch's PDA
+    let bd = Pubkey::new_unique();
+    let data = BoostDistributor { discriminator: BoostDistributor::DISCRIMINATOR, epoch: 1, ..bytemuck::Zeroable::zeroed() };
+    env.svm.set_account(bd, account(bytemuck::bytes_of(&data).to_vec(), env.program_id)).unwrap();
+    assert!(env.donate(DONATION, 1, 5_000, Some(bd)).is_err());
+    assert_eq!(env.balance(&env.donor_ata), 10 * DONATION);
+    assert_eq!(env.read_distributor(&bd).boost_total, 0);
+}
+
+#[test]
+fn donate_rejects_boost_account_other_than_the_vaults() {
+    let mut env = Env::new();
+    // a USDC account vault_pda owns, but not the boost account init created and stored
+    let other = Pubkey::new_unique();
+    env.svm.set_account(other, account(token_account_data(&env.usdc_mint, &env.vault_pda, 0), TOKEN_PROGRAM_ID)).unwrap();
+    env.boost_ata = other;
+    let err = env.donate(DONATION, 1, 5_000, None).unwrap_err();
+    let code = format!("Custom({})", VaultError::BoostEscrowMismatch as u32);
+    assert!(err.contains(&code), "{err}");
+    assert_eq!(env.balance(&env.donor_ata), 10 * DONATION);
+}
+
+#[test]
+fn donate_rejects_boost_bps_above_denominator() {
+    let mut env = Env::new();
+    let bd = env.distributor(1);
+    assert!(env.donate(DONATION, 1, 10_001, Some(bd)).is_err());
+    assert_eq!(env.balance(&env.vault_ata), 0);
+}
+
+#[test]
+fn donate_rejects_vault_state_owned_by_another_program() {
+    let mut env = Env::new();
+    // the real vault's bytes, tag and all, but under a program that could have written them
+    let mut forged = env.svm.get_account(&env.vault_state).unwrap();
+    forged.owner = Pubkey::new_unique();
+    env.svm.set_account(env.vault_state, forged).unwrap();
+    let err = env.donate(DONATION, 1, 0, None).unwrap_err();
+    assert!(err.contains("IncorrectProgramId"), "{err}");
+    assert_eq!(env.balance(&env.donor_ata), 10 * DONATION);
+}
+
+#[test]
+fn donate_rejects_vault_state_copy_off_its_pda() {
+    let mut env = Env::new();
+    // program-owned and tagged, but the stored seeds name the original account
+    let copy = Pubkey::new_unique();
+    let original = env.svm.get_account(&env.vault_state).unwrap();
+    env.svm.set_account(copy, original).unwrap();
+    env.vault_state = copy;
+    let err = env.donate(DONATION, 1, 0, None).unwrap_err();
+    assert!(err.contains("InvalidSeeds"), "{err}");
+    assert_eq!(env.balance(&env.donor_ata), 10 * DONATION);
+}
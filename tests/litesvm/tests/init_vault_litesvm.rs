@@ -1,54 +1,224 @@
+mod common;
+
+use common::{TOKEN_PROGRAM_ID, VAULT_SO, account, ata, init_ix, mint_data, vault_state_key};
+use interest_vault::{Discriminator, RegistryPage, VaultRegistry};
 use litesvm::LiteSVM;
-use solana_instruction::{Instruction, account_meta::AccountMeta};
+use solana_instruction::account_meta::AccountMeta;
 use solana_keypair::Keypair;
 use solana_message::Message;
 use solana_pubkey::Pubkey;
 use solana_signer::Signer;
 use solana_transaction::Transaction;
 
-#[test]
-fn init_vault_succeeds() {
-    let program_id = Pubkey::new_unique();
-
-    // Load program bytes (build the .so first via ./scripts/build-program.sh)
-    // Adjust the path if needed.
-    let bytes = include_bytes!("../../../programs/interest_vault/target/deploy/interest_vault.so");
+const TOKEN_2022_PROGRAM_ID: Pubkey = Pubkey::from_str_const("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
 
+// The program and a USDC mint (6 decimals); InitializeVault creates vault_state itself
+fn setup(program_id: Pubkey, usdc_mint: Pubkey) -> LiteSVM {
     let mut svm = LiteSVM::new();
-    svm.add_program(program_id, bytes);
+    svm.add_program(program_id, VAULT_SO);
+    svm.set_account(usdc_mint, account(mint_data(&Pubkey::new_unique(), 6), TOKEN_PROGRAM_ID)).unwrap();
+    svm
+}
 
+#[test]
+fn init_vault_succeeds() {
+    let program_id = Pubkey::new_unique();
     let admin = Keypair::new();
     let operator = Keypair::new();
     let usdc_mint = Pubkey::new_unique();
-    let share_mint = Pubkey::new_unique();
-    let vault_state = Keypair::new();
-
-    // Build instruction data: [tag=INIT, decimals=6]
-    let data = vec![0u8, 6u8];
-
-    // Accounts: vault_state(w), admin(s), operator, usdc_mint, share_mint, vault_pda
-    // For init test we don't need a real PDA (program checks it, so compute off-chain)
-    let seeds = [b"vault".as_ref(), usdc_mint.as_ref(), admin.pubkey().as_ref()];
-    let (vault_pda, _bump) = Pubkey::find_program_address(&seeds, &program_id);
-
-    let ix = Instruction {
-        program_id,
-        accounts: vec![
-            AccountMeta::new(vault_state.pubkey(), true),
-            AccountMeta::new_readonly(admin.pubkey(), true),
-            AccountMeta::new_readonly(operator.pubkey(), false),
-            AccountMeta::new_readonly(usdc_mint, false),
-            AccountMeta::new_readonly(share_mint, false),
-            AccountMeta::new_readonly(vault_pda, false),
-        ],
-        data,
-    };
+    let vault_state = vault_state_key(&program_id, &usdc_mint, &admin.pubkey(), 0);
+    let mut svm = setup(program_id, usdc_mint);
 
+    let ix = init_ix(program_id, vault_state, &admin.pubkey(), &operator.pubkey(), usdc_mint, 0);
+    let (share_mint, vault_pda) = (ix.accounts[4].pubkey, ix.accounts[5].pubkey);
+    let (vault_usdc, boost_usdc) = (ix.accounts[10].pubkey, ix.accounts[11].pubkey);
     svm.airdrop(&admin.pubkey(), 10_000_000_000).unwrap();
     let blockhash = svm.latest_blockhash();
-    let tx = Transaction::new(&[&admin, &vault_state], Message::new(&[ix], Some(&admin.pubkey())), blockhash);
+    let tx = Transaction::new(&[&admin], Message::new(&[ix], Some(&admin.pubkey())), blockhash);
     let res = svm.send_transaction(tx);
     assert!(res.is_ok());
+
+    // share mint exists, is owned by the token program and copies the USDC decimals
+    let mint = svm.get_account(&share_mint).unwrap();
+    assert_eq!(mint.owner, TOKEN_PROGRAM_ID);
+    assert_eq!(mint.data[44], 6);
+
+    // the vault's USDC and boost accounts exist, are owned by vault_pda and are stored
+    for key in [vault_usdc, boost_usdc] {
+        let acc = svm.get_account(&key).unwrap();
+        assert_eq!(acc.owner, TOKEN_PROGRAM_ID);
+        assert_eq!((&acc.data[0..32], &acc.data[32..64]), (usdc_mint.as_ref(), vault_pda.as_ref()));
+    }
+    let st: interest_vault::VaultState = bytemuck::pod_read_unaligned(&svm.get_account(&vault_state).unwrap().data);
+    assert_eq!((st.vault_usdc, st.boost_usdc), (vault_usdc.to_bytes(), boost_usdc.to_bytes()));
+}
+
+#[test]
+fn init_appends_to_registry() {
+    let program_id = Pubkey::new_unique();
+    let usdc_mint = Pubkey::new_unique();
+    let mut svm = setup(program_id, usdc_mint);
+    let admins = [Keypair::new(), Keypair::new()];
+    for admin in &admins {
+        svm.airdrop(&admin.pubkey(), 10_000_000_000).unwrap();
+        let ix = init_ix(program_id, vault_state_key(&program_id, &usdc_mint, &admin.pubkey(), 0), &admin.pubkey(), &admin.pubkey(), usdc_mint, 0);
+        let tx = Transaction::new(&[admin], Message::new(&[ix], Some(&admin.pubkey())), svm.latest_blockhash());
+        svm.send_transaction(tx).unwrap();
+    }
+
+    let registry = svm.get_account(&Pubkey::find_program_address(&[b"registry"], &program_id).0).unwrap();
+    let registry: VaultRegistry = bytemuck::pod_read_unaligned(&registry.data[..VaultRegistry::LEN]);
+    assert_eq!(registry.count, 2);
+    let page = svm.get_account(&Pubkey::find_program_address(&[b"registry", &0u32.to_le_bytes()], &program_id).0).unwrap();
+    let page: RegistryPage = bytemuck::pod_read_unaligned(&page.data[..RegistryPage::LEN]);
+    assert_eq!(page.len, 2);
+    for (i, admin) in admins.iter().enumerate() {
+        assert_eq!(page.vaults[i], vault_state_key(&program_id, &usdc_mint, &admin.pubkey(), 0).to_bytes());
+    }
+}
+
+// Risk tiers: the same admin runs two vaults on one USDC mint, told apart by vault_id
+#[test]
+fn one_admin_runs_several_vaults_per_mint() {
+    let program_id = Pubkey::new_unique();
+    let admin = Keypair::new();
+    let usdc_mint = Pubkey::new_unique();
+    let mut svm = setup(program_id, usdc_mint);
+    svm.airdrop(&admin.pubkey(), 10_000_000_000).unwrap();
+
+    let mut share_mints = Vec::new();
+    for vault_id in [0, 7] {
+        let vault_state = vault_state_key(&program_id, &usdc_mint, &admin.pubkey(), vault_id);
+        let ix = init_ix(program_id, vault_state, &admin.pubkey(), &admin.pubkey(), usdc_mint, vault_id);
+        share_mints.push(ix.accounts[4].pubkey);
+        let vault_pda = ix.accounts[5].pubkey;
+        let tx = Transaction::new(&[&admin], Message::new(&[ix], Some(&admin.pubkey())), svm.latest_blockhash());
+        svm.send_transaction(tx).unwrap();
+
+        let st: interest_vault::VaultState = bytemuck::pod_read_unaligned(&svm.get_account(&vault_state).unwrap().data);
+        assert_eq!((st.vault_id, st.vault_pda), (vault_id, vault_pda.to_bytes()));
+    }
+    assert_ne!(share_mints[0], share_mints[1]);
+
+    // the id is part of the seeds, so vault 0's state cannot be initialized as vault 8
+    let ix = init_ix(program_id, vault_state_key(&program_id, &usdc_mint, &admin.pubkey(), 0), &admin.pubkey(), &admin.pubkey(), usdc_mint, 8);
+    let tx = Transaction::new(&[&admin], Message::new(&[ix], Some(&admin.pubkey())), svm.latest_blockhash());
+    let err = svm.send_transaction(tx).unwrap_err();
+    assert!(format!("{:?}", err.err).contains("InvalidSeeds"), "{:?}", err.err);
+}
+
+// A second vault cloned from the first: every config field the admin set on the template,
+// caps and root signers included, lands on the new vault
+#[test]
+fn init_from_template_copies_config() {
+    let program_id = Pubkey::new_unique();
+    let admin = Keypair::new();
+    let usdc_mint = Pubkey::new_unique();
+    let mut svm = setup(program_id, usdc_mint);
+    svm.airdrop(&admin.pubkey(), 10_000_000_000).unwrap();
+
+    let template = vault_state_key(&program_id, &usdc_mint, &admin.pubkey(), 0);
+    let ix = init_ix(program_id, template, &admin.pubkey(), &admin.pubkey(), usdc_mint, 0);
+    let tx = Transaction::new(&[&admin], Message::new(&[ix], Some(&admin.pubkey())), svm.latest_blockhash());
+    svm.send_transaction(tx).unwrap();
+
+    // config the admin would have set through the Set* ops
+    let mut acc = svm.get_account(&template).unwrap();
+    let mut tpl: interest_vault::VaultState = bytemuck::pod_read_unaligned(&acc.data);
+    tpl.deposit_cap = 5_000_000_000;
+    tpl.user_deposit_cap = 100_000_000;
+    tpl.root_signers[0] = Pubkey::new_unique().to_bytes();
+    tpl.root_signers[1] = Pubkey::new_unique().to_bytes();
+    tpl.root_signers_len = 2;
+    tpl.root_threshold = 2;
+    tpl.liveness_slots = 216_000;
+    acc.data.copy_from_slice(bytemuck::bytes_of(&tpl));
+    svm.set_account(template, acc).unwrap();
+
+    let vault_state = vault_state_key(&program_id, &usdc_mint, &admin.pubkey(), 1);
+    let mut ix = init_ix(program_id, vault_state, &admin.pubkey(), &admin.pubkey(), usdc_mint, 1);
+    ix.data[0] = 9; // InitializeVaultFromTemplate
+    ix.accounts.push(AccountMeta::new_readonly(template, false));
+    let tx = Transaction::new(&[&admin], Message::new(&[ix], Some(&admin.pubkey())), svm.latest_blockhash());
+    svm.send_transaction(tx).unwrap();
+
+    let st: interest_vault::VaultState = bytemuck::pod_read_unaligned(&svm.get_account(&vault_state).unwrap().data);
+    assert_eq!((st.deposit_cap, st.user_deposit_cap), (tpl.deposit_cap, tpl.user_deposit_cap));
+    assert_eq!(st.root_signers, tpl.root_signers);
+    assert_eq!((st.root_signers_len, st.root_threshold), (2, 2));
+    assert_eq!(st.liveness_slots, tpl.liveness_slots);
+    // per-vault fields are the new vault's own
+    assert_eq!(st.vault_id, 1);
+    assert_ne!(st.vault_pda, tpl.vault_pda);
+}
+
+// A Token-2022 stable: the share mint is created under the same program, and the token
+// program passed in has to be the one that owns the mint
+#[test]
+fn init_follows_the_mints_token_program() {
+    let program_id = Pubkey::new_unique();
+    let admin = Keypair::new();
+    let usdc_mint = Pubkey::new_unique();
+    let vault_state = vault_state_key(&program_id, &usdc_mint, &admin.pubkey(), 0);
+    let mut svm = setup(program_id, usdc_mint);
+    let mut mint = svm.get_account(&usdc_mint).unwrap();
+    mint.owner = TOKEN_2022_PROGRAM_ID;
+    svm.set_account(usdc_mint, mint).unwrap();
+    svm.airdrop(&admin.pubkey(), 10_000_000_000).unwrap();
+
+    let ix = init_ix(program_id, vault_state, &admin.pubkey(), &admin.pubkey(), usdc_mint, 0);
+    let tx = Transaction::new(&[&admin], Message::new(&[ix.clone()], Some(&admin.pubkey())), svm.latest_blockhash());
+    let err = svm.send_transaction(tx).unwrap_err();
+    let code = format!("Custom({})", interest_vault::VaultError::InvalidTokenProgram as u32);
+    assert!(format!("{:?}", err.err).contains(&code), "{:?}", err.err);
+
+    let mut ix = ix;
+    ix.accounts[7].pubkey = TOKEN_2022_PROGRAM_ID;
+    ix.accounts[10].pubkey = ata(&ix.accounts[5].pubkey, &usdc_mint, &TOKEN_2022_PROGRAM_ID);
+    let share_mint = ix.accounts[4].pubkey;
+    let tx = Transaction::new(&[&admin], Message::new(&[ix], Some(&admin.pubkey())), svm.latest_blockhash());
+    svm.send_transaction(tx).unwrap();
+    assert_eq!(svm.get_account(&share_mint).unwrap().owner, TOKEN_2022_PROGRAM_ID);
 }
 
+// Re-running InitializeVault on a live vault would reset pps and total_shares and hand
+// the vault to whoever signed; the state PDA's seeds and the discriminator written by
+// the first init stop it.
+#[test]
+fn reinit_of_live_vault_fails() {
+    let program_id = Pubkey::new_unique();
+    let admin = Keypair::new();
+    let attacker = Keypair::new();
+    let usdc_mint = Pubkey::new_unique();
+    let vault_state = vault_state_key(&program_id, &usdc_mint, &admin.pubkey(), 0);
+    let mut svm = setup(program_id, usdc_mint);
+    svm.airdrop(&admin.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&attacker.pubkey(), 10_000_000_000).unwrap();
+
+    let ix = init_ix(program_id, vault_state, &admin.pubkey(), &admin.pubkey(), usdc_mint, 0);
+    let tx = Transaction::new(&[&admin], Message::new(&[ix], Some(&admin.pubkey())), svm.latest_blockhash());
+    svm.send_transaction(tx).unwrap();
 
+    // pretend deposits and donations happened since
+    let mut acc = svm.get_account(&vault_state).unwrap();
+    let mut st: interest_vault::VaultState = bytemuck::pod_read_unaligned(&acc.data);
+    st.total_shares = 5_000_000;
+    st.pps *= 2;
+    acc.data.copy_from_slice(bytemuck::bytes_of(&st));
+    svm.set_account(vault_state, acc.clone()).unwrap();
+
+    // the attacker re-initializes as admin and operator of the same vault_state, which is
+    // not the PDA of their key
+    let ix = init_ix(program_id, vault_state, &attacker.pubkey(), &attacker.pubkey(), usdc_mint, 0);
+    let tx = Transaction::new(&[&attacker], Message::new(&[ix], Some(&attacker.pubkey())), svm.latest_blockhash());
+    let err = svm.send_transaction(tx).unwrap_err();
+    assert!(format!("{:?}", err.err).contains("InvalidSeeds"), "{:?}", err.err);
+
+    // the admin's own second init (other share decimals, so a new transaction) hits the tag
+    let mut ix = init_ix(program_id, vault_state, &admin.pubkey(), &admin.pubkey(), usdc_mint, 0);
+    ix.data.push(9);
+    let tx = Transaction::new(&[&admin], Message::new(&[ix], Some(&admin.pubkey())), svm.latest_blockhash());
+    let err = svm.send_transaction(tx).unwrap_err();
+    assert!(format!("{:?}", err.err).contains("AccountAlreadyInitialized"), "{:?}", err.err);
+    assert_eq!(svm.get_account(&vault_state).unwrap().data, acc.data);
+}
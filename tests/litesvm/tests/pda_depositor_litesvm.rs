@@ -0,0 +1,130 @@
+// A program-derived "user": the pda_depositor_example treasury PDA deposits into and
+// withdraws from the vault through CPI, signing with invoke_signed.
+mod common;
+
+use common::{TOKEN_PROGRAM_ID, VAULT_SO, Vault, account, init_vault, token_account_data};
+use interest_vault::Discriminator;
+use litesvm::LiteSVM;
+use solana_instruction::{Instruction, account_meta::AccountMeta};
+use solana_keypair::Keypair;
+use solana_message::Message;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+const FUNDING: u64 = 10_000_000;
+
+struct Env {
+    svm: LiteSVM,
+    vault_program: Pubkey,
+    caller_program: Pubkey,
+    authority: Keypair,
+    vault_state: Pubkey,
+    vault_pda: Pubkey,
+    auth_pda: Pubkey,
+    usdc_mint: Pubkey,
+    share_mint: Pubkey,
+    vault_ata: Pubkey,
+    treasury: Pubkey,
+    treasury_usdc: Pubkey,
+    treasury_shares: Pubkey,
+}
+
+impl Env {
+    // Initialized vault plus a treasury PDA holding FUNDING USDC and an empty share account
+    fn new() -> Self {
+        let vault_program = Pubkey::new_unique();
+        let caller_program = Pubkey::new_unique();
+        let mut svm = LiteSVM::new();
+        svm.add_program(vault_program, VAULT_SO);
+        svm.add_program(caller_program, include_bytes!("../../../programs/pda_depositor_example/target/deploy/pda_depositor_example.so"));
+
+        let admin = Keypair::new();
+        let authority = Keypair::new();
+        let Vault { vault_state, vault_pda, auth_pda, usdc_mint, share_mint, vault_usdc: vault_ata, .. } = init_vault(&mut svm, vault_program, &admin, &admin.pubkey());
+        svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+
+        let (treasury, _) = Pubkey::find_program_address(&[b"treasury".as_ref(), authority.pubkey().as_ref()], &caller_program);
+        let (treasury_usdc, treasury_shares) = (Pubkey::new_unique(), Pubkey::new_unique());
+        svm.set_account(treasury_usdc, account(token_account_data(&usdc_mint, &treasury, FUNDING), TOKEN_PROGRAM_ID)).unwrap();
+        svm.set_account(treasury_shares, account(token_account_data(&share_mint, &treasury, 0), TOKEN_PROGRAM_ID)).unwrap();
+
+        Env {
+            svm, vault_program, caller_program, authority, vault_state, vault_pda, auth_pda, usdc_mint, share_mint,
+            vault_ata, treasury, treasury_usdc, treasury_shares,
+        }
+    }
+
+    // op 0 = Deposit, 1 = Withdraw on the caller program, signed by `signer`
+    fn forward(&mut self, op: u8, amount: u64, signer: &Keypair) -> Result<(), String> {
+        let mut data = vec![op];
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.push(6);
+        let ix = Instruction {
+            program_id: self.caller_program,
+            accounts: vec![
+                AccountMeta::new_readonly(signer.pubkey(), true),
+                AccountMeta::new_readonly(self.treasury, false),
+                AccountMeta::new_readonly(self.vault_program, false),
+                AccountMeta::new(self.vault_state, false),
+                AccountMeta::new_readonly(if op == 0 { self.auth_pda } else { self.vault_pda }, false),
+                AccountMeta::new(self.treasury_usdc, false),
+                AccountMeta::new(self.vault_ata, false),
+                AccountMeta::new(self.share_mint, false),
+                AccountMeta::new(self.treasury_shares, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(self.usdc_mint, false),
+            ],
+            data,
+        };
+        self.svm.expire_blockhash();
+        let tx = Transaction::new(&[signer], Message::new(&[ix], Some(&signer.pubkey())), self.svm.latest_blockhash());
+        self.svm.send_transaction(tx).map(|_| ()).map_err(|e| format!("{:?}", e.err))
+    }
+
+    fn balance(&self, ata: &Pubkey) -> u64 {
+        let d = self.svm.get_account(ata).unwrap().data;
+        u64::from_le_bytes(d[64..72].try_into().unwrap())
+    }
+
+    // the share mint's supply and VaultState.total_shares are updated separately; they must agree
+    fn assert_share_supply(&self) {
+        let mint = self.svm.get_account(&self.share_mint).unwrap().data;
+        let supply = u64::from_le_bytes(mint[36..44].try_into().unwrap());
+        let d = self.svm.get_account(&self.vault_state).unwrap().data;
+        let st = bytemuck::pod_read_unaligned::<interest_vault::VaultState>(&d[..interest_vault::VaultState::LEN]);
+        assert_eq!(st.total_shares, supply as u128);
+    }
+}
+
+#[test]
+fn pda_user_deposits_and_withdraws_via_cpi() {
+    let mut env = Env::new();
+    let authority = env.authority.insecure_clone();
+
+    // pps starts at 1.0, so shares == USDC base units
+    env.forward(0, 5_000_000, &authority).unwrap();
+    assert_eq!(env.balance(&env.treasury_usdc), FUNDING - 5_000_000);
+    assert_eq!(env.balance(&env.vault_ata), 5_000_000);
+    assert_eq!(env.balance(&env.treasury_shares), 5_000_000);
+    env.assert_share_supply();
+
+    // the first 1_000_000 shares stay locked by the bootstrap guard
+    env.forward(1, 4_000_000, &authority).unwrap();
+    assert_eq!(env.balance(&env.treasury_shares), 1_000_000);
+    assert_eq!(env.balance(&env.treasury_usdc), FUNDING - 1_000_000);
+    assert_eq!(env.balance(&env.vault_ata), 1_000_000);
+    env.assert_share_supply();
+}
+
+#[test]
+fn pda_user_funds_only_move_for_its_authority() {
+    let mut env = Env::new();
+    let stranger = Keypair::new();
+    env.svm.airdrop(&stranger.pubkey(), 10_000_000_000).unwrap();
+
+    // the treasury is derived from the signer, so another key derives a different PDA
+    assert!(env.forward(0, 5_000_000, &stranger).is_err());
+    assert_eq!(env.balance(&env.treasury_usdc), FUNDING);
+    assert_eq!(env.balance(&env.treasury_shares), 0);
+}
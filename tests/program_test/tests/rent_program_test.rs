@@ -0,0 +1,150 @@
+// Same instructions as the LiteSVM tests, but run through solana-program-test's BanksClient,
+// which uses the real runtime's Rent sysvar, rent-state checks and CPI/realloc limits.
+use solana_program_test::{BanksClient, ProgramTest};
+use solana_sdk::{
+    account::Account,
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::{Keypair, Signer},
+    system_program,
+    transaction::Transaction,
+};
+
+const TOKEN_PROGRAM_ID: Pubkey = Pubkey::from_str_const("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+const ATA_PROGRAM_ID: Pubkey = Pubkey::from_str_const("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+const STATE_LEN: usize = <interest_vault::VaultState as interest_vault::Discriminator>::LEN;
+
+// Packed SPL Mint: COption<authority>, supply, decimals, is_initialized, COption<freeze>
+fn mint_data(authority: &Pubkey, decimals: u8) -> Vec<u8> {
+    let mut d = vec![0u8; 82];
+    d[0..4].copy_from_slice(&1u32.to_le_bytes());
+    d[4..36].copy_from_slice(authority.as_ref());
+    d[44] = decimals;
+    d[45] = 1;
+    d
+}
+
+struct Env {
+    banks: BanksClient,
+    payer: Keypair,
+    blockhash: Hash,
+    program_id: Pubkey,
+    admin: Keypair,
+    vault_state: Pubkey,
+    rent: Rent,
+}
+
+impl Env {
+    // Initialized vault; vault_state starts with `state_lamports` (None = exactly rent-exempt)
+    async fn new(state_lamports: Option<u64>) -> Self {
+        // program-test looks for interest_vault.so here (build it first via ./scripts/build-program.sh)
+        std::env::set_var("SBF_OUT_DIR", concat!(env!("CARGO_MANIFEST_DIR"), "/../../programs/interest_vault/target/deploy"));
+        let program_id = Pubkey::new_unique();
+        let mut pt = ProgramTest::new("interest_vault", program_id, None);
+        pt.prefer_bpf(true);
+
+        let rent = Rent::default();
+        let admin = Keypair::new();
+        let usdc_mint = Pubkey::new_unique();
+        // InitializeVault creates vault_state unless it is already allocated at its PDA, which
+        // lets the test give it excess lamports up front
+        let (vault_state, _) = Pubkey::find_program_address(&[b"state", usdc_mint.as_ref(), admin.pubkey().as_ref(), &0u64.to_le_bytes()], &program_id);
+        pt.add_account(admin.pubkey(), Account::new(10_000_000_000, 0, &system_program::ID));
+        pt.add_account(usdc_mint, Account {
+            lamports: rent.minimum_balance(82),
+            data: mint_data(&Pubkey::new_unique(), 6),
+            owner: TOKEN_PROGRAM_ID,
+            executable: false,
+            rent_epoch: 0,
+        });
+        pt.add_account(vault_state, Account::new(
+            state_lamports.unwrap_or(rent.minimum_balance(STATE_LEN)), STATE_LEN, &program_id,
+        ));
+        let (mut banks, payer, blockhash) = pt.start().await;
+        let rent = banks.get_rent().await.unwrap();
+
+        let (vault_pda, _) = Pubkey::find_program_address(&[b"vault", usdc_mint.as_ref(), admin.pubkey().as_ref(), &0u64.to_le_bytes()], &program_id);
+        let (share_mint, _) = Pubkey::find_program_address(&[b"shares", vault_state.as_ref()], &program_id);
+        let (vault_usdc, _) = Pubkey::find_program_address(&[vault_pda.as_ref(), TOKEN_PROGRAM_ID.as_ref(), usdc_mint.as_ref()], &ATA_PROGRAM_ID);
+        let (boost_usdc, _) = Pubkey::find_program_address(&[b"boost_usdc", vault_pda.as_ref()], &program_id);
+        let init = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(vault_state, false),
+                AccountMeta::new(admin.pubkey(), true),
+                AccountMeta::new_readonly(Pubkey::new_unique(), false),
+                AccountMeta::new_readonly(usdc_mint, false),
+                AccountMeta::new(share_mint, false),
+                AccountMeta::new_readonly(vault_pda, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new(Pubkey::find_program_address(&[b"registry"], &program_id).0, false),
+                AccountMeta::new(Pubkey::find_program_address(&[b"registry", &0u32.to_le_bytes()], &program_id).0, false),
+                AccountMeta::new(vault_usdc, false),
+                AccountMeta::new(boost_usdc, false),
+                AccountMeta::new_readonly(ATA_PROGRAM_ID, false),
+            ],
+            data: vec![0u8],
+        };
+        let tx = Transaction::new_signed_with_payer(&[init], Some(&payer.pubkey()), &[&payer, &admin], blockhash);
+        banks.process_transaction(tx).await.unwrap();
+
+        // the share mint was created by CPI, so it must satisfy the real rent check
+        let mint = banks.get_account(share_mint).await.unwrap().unwrap();
+        assert_eq!(mint.owner, TOKEN_PROGRAM_ID);
+        assert!(rent.is_exempt(mint.lamports, mint.data.len()));
+        // as were the vault's USDC and boost accounts
+        for key in [vault_usdc, boost_usdc] {
+            let acct = banks.get_account(key).await.unwrap().unwrap();
+            assert!(rent.is_exempt(acct.lamports, acct.data.len()));
+        }
+
+        Env { banks, payer, blockhash, program_id, admin, vault_state, rent }
+    }
+
+    async fn reconcile_vault_state_rent(&mut self) -> Result<(), String> {
+        let ix = Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(self.vault_state, false),
+                AccountMeta::new(self.admin.pubkey(), true),
+                AccountMeta::new(self.vault_state, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ],
+            data: vec![14u8, 0],
+        };
+        self.blockhash = self.banks.get_new_latest_blockhash(&self.blockhash).await.unwrap();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&self.payer.pubkey()), &[&self.payer, &self.admin], self.blockhash);
+        self.banks.process_transaction(tx).await.map_err(|e| format!("{e:?}"))
+    }
+
+    async fn lamports(&mut self, key: Pubkey) -> u64 {
+        self.banks.get_account(key).await.unwrap().unwrap().lamports
+    }
+}
+
+#[tokio::test]
+async fn reconcile_rent_sweeps_excess_to_runtime_minimum() {
+    let mut env = Env::new(Some(1_000_000_000)).await;
+    let admin_before = env.lamports(env.admin.pubkey()).await;
+
+    env.reconcile_vault_state_rent().await.unwrap();
+
+    let min = env.rent.minimum_balance(STATE_LEN);
+    assert_eq!(env.lamports(env.vault_state).await, min);
+    // fees are paid by the separate payer, so the admin receives exactly the excess
+    assert_eq!(env.lamports(env.admin.pubkey()).await, admin_before + 1_000_000_000 - min);
+}
+
+#[tokio::test]
+async fn reconcile_rent_is_noop_when_exactly_exempt() {
+    let mut env = Env::new(None).await;
+    let admin_before = env.lamports(env.admin.pubkey()).await;
+
+    env.reconcile_vault_state_rent().await.unwrap();
+
+    assert_eq!(env.lamports(env.vault_state).await, env.rent.minimum_balance(STATE_LEN));
+    assert_eq!(env.lamports(env.admin.pubkey()).await, admin_before);
+}
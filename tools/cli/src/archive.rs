@@ -0,0 +1,173 @@
+// `epoch-archive`: freeze a settled epoch's reward data into one content-addressed file
+// for long-term storage. Once an epoch has expired and been clawed back nothing on it can
+// change, so the tree (the operator's `index,claimer,weight` CSV, checked against the
+// posted leaf_count and total_weight), a snapshot of every claim bitmap page and the
+// payout ledger (amount and claimed flag per leaf) are written as plain text to
+// `<out-dir>/<sha256>.archive`. With `--keypair` (the operator) the hash is then pinned on
+// the epoch's distributor with RecordArchive, so a copy found years later can be checked
+// against the chain by hashing it.
+
+use std::{fs, path::Path};
+
+use interest_vault::{math, BoostDistributor, ClaimBitmap256, VaultState};
+use sha2::{Digest, Sha256};
+use solana_instruction::{account_meta::AccountMeta, Instruction};
+use solana_keypair::read_keypair_file;
+use solana_pubkey::Pubkey;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_signer::Signer;
+
+use crate::{audit::{claims_page, is_claimed, parse_tree, Leaf}, flag, pubkey, required, Res};
+
+const OP_RECORD_ARCHIVE: u8 = 45;
+const FORMAT: &str = "interest-epoch-archive v1";
+
+// where the archived epoch lives on chain; written into the archive header
+pub struct Source {
+    pub program_id: Pubkey,
+    pub vault_state: Pubkey,
+    pub distributor: Pubkey,
+}
+
+// epoch-archive --vault-state <pk> --epoch <n> --tree <csv> --out-dir <dir> [--keypair <file>]
+pub fn run(rpc: &RpcClient, args: &[String]) -> Res<()> {
+    let vault_state = pubkey(&required(args, "--vault-state")?)?;
+    let epoch: u64 = required(args, "--epoch")?.parse()?;
+    let leaves = parse_tree(&fs::read_to_string(required(args, "--tree")?)?)?;
+    let out_dir = required(args, "--out-dir")?;
+
+    let acc = rpc.get_account(&vault_state)?;
+    let program_id = acc.owner;
+    let st: VaultState = read(&acc.data, "VaultState")?;
+    let vault_pda = Pubkey::new_from_array(st.vault_pda);
+    let (distributor, _) = Pubkey::find_program_address(&[b"boost", vault_pda.as_ref(), &epoch.to_le_bytes()], &program_id);
+    let bd: BoostDistributor = read(&rpc.get_account(&distributor)?.data, "BoostDistributor")?;
+    if !bd.funding_closed() { return Err(format!("epoch {epoch} has no posted root").into()) }
+    // archiving earlier would snapshot a ledger that can still change
+    if !bd.settled(rpc.get_slot()?) {
+        return Err(format!("epoch {epoch} is not settled: wait for expiry slot {} and run Clawback first", bd.expiry_slot).into())
+    }
+    let mut pages = Vec::new();
+    for page in 0..bd.bitmap_page_count.max(1) {
+        pages.push(match rpc.get_account(&claims_page(&program_id, &vault_pda, epoch, page)) {
+            Ok(a) => read::<ClaimBitmap256>(&a.data, "ClaimBitmap256")?,
+            Err(_) => bytemuck::Zeroable::zeroed(),
+        });
+    }
+
+    let text = render(&Source { program_id, vault_state, distributor }, &bd, &leaves, &pages)?;
+    let hash: [u8; 32] = Sha256::digest(text.as_bytes()).into();
+    let path = Path::new(&out_dir).join(format!("{}.archive", hex(&hash)));
+    fs::create_dir_all(&out_dir)?;
+    fs::write(&path, &text)?;
+    println!("archive  {}", path.display());
+    println!("sha256   {}", hex(&hash));
+
+    if bd.archive_hash != [0u8; 32] {
+        if bd.archive_hash == hash {
+            println!("already recorded on distributor {distributor}");
+        } else {
+            println!("WARNING: distributor {distributor} already records a different archive {}", hex(&bd.archive_hash));
+        }
+        return Ok(())
+    }
+    let Some(keypair) = flag(args, "--keypair") else {
+        println!("not recorded; pass --keypair <operator> to pin the hash on distributor {distributor}");
+        return Ok(())
+    };
+    let operator = read_keypair_file(&keypair).map_err(|e| format!("reading {keypair}: {e}"))?;
+    let mut data = vec![OP_RECORD_ARCHIVE];
+    data.extend_from_slice(&epoch.to_le_bytes());
+    data.extend_from_slice(&hash);
+    let ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(vault_state, false),
+            AccountMeta::new_readonly(operator.pubkey(), true),
+            AccountMeta::new(distributor, false),
+        ],
+        data,
+    };
+    let tx = solana_transaction::Transaction::new_signed_with_payer(&[ix], Some(&operator.pubkey()), &[&operator], rpc.get_latest_blockhash()?);
+    println!("recorded {}", rpc.send_and_confirm_transaction(&tx)?);
+    Ok(())
+}
+
+// The archive body. Byte-for-byte deterministic for the same chain state and tree, so
+// re-exporting an epoch reproduces its hash.
+pub fn render(src: &Source, bd: &BoostDistributor, leaves: &[Leaf], pages: &[ClaimBitmap256]) -> Res<String> {
+    // the file has to be the tree that was posted, or the ledger is meaningless
+    let file_weight: u128 = leaves.iter().map(|l| l.weight).sum();
+    if leaves.len() != bd.leaf_count as usize || file_weight != bd.total_weight {
+        return Err(format!(
+            "tree file does not match epoch {}: {} leaves / total weight {file_weight}, on-chain {} / {}",
+            bd.epoch, leaves.len(), bd.leaf_count, bd.total_weight,
+        ).into())
+    }
+
+    let mut out = format!("# {FORMAT}\n");
+    for (k, v) in [
+        ("program", src.program_id.to_string()),
+        ("vault_state", src.vault_state.to_string()),
+        ("distributor", src.distributor.to_string()),
+        ("epoch", bd.epoch.to_string()),
+        ("root", hex(&bd.root)),
+        ("leaf_count", bd.leaf_count.to_string()),
+        ("total_weight", bd.total_weight.to_string()),
+        ("boost_total", bd.boost_total.to_string()),
+        ("claimed_total", bd.claimed_total.to_string()),
+        ("clawed_back", bd.clawed_back.to_string()),
+        ("expiry_slot", bd.expiry_slot.to_string()),
+        ("bitmap_page_size", bd.bitmap_page_size.to_string()),
+    ] {
+        out.push_str(&format!("{k},{v}\n"));
+    }
+    out.push_str("\n# bitmap: page,words_hex\n");
+    for (page, bm) in pages.iter().enumerate() {
+        out.push_str(&format!("{page},{}\n", hex(&bm.words)));
+    }
+    out.push_str("\n# ledger: index,claimer,weight,amount,claimed\n");
+    for l in leaves {
+        let amount = math::claim_amount(bd.boost_total, l.weight, bd.total_weight).ok_or("claim amount overflows")?;
+        let claimed = is_claimed(bd, pages, l.index) as u8;
+        out.push_str(&format!("{},{},{},{amount},{claimed}\n", l.index, l.claimer, l.weight));
+    }
+    Ok(out)
+}
+
+fn hex(b: &[u8]) -> String {
+    b.iter().map(|x| format!("{x:02x}")).collect()
+}
+
+fn read<T: bytemuck::Pod>(d: &[u8], what: &str) -> Res<T> {
+    if d.len() < core::mem::size_of::<T>() { return Err(format!("account too small for {what}").into()) }
+    Ok(bytemuck::pod_read_unaligned(&d[..core::mem::size_of::<T>()]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytemuck::Zeroable;
+
+    #[test]
+    fn ledger_marks_claimed_leaves() {
+        let src = Source { program_id: Pubkey::new_unique(), vault_state: Pubkey::new_unique(), distributor: Pubkey::new_unique() };
+        let leaves: Vec<Leaf> = [3u128, 1].iter().enumerate()
+            .map(|(i, &weight)| Leaf { index: i as u32, claimer: Pubkey::new_unique(), weight })
+            .collect();
+        let bd = BoostDistributor {
+            epoch: 7, leaf_count: 2, total_weight: 4, boost_total: 1_000, claimed_total: 750, clawed_back: 250,
+            bitmap_page_size: 256, bitmap_page_count: 1, ..BoostDistributor::zeroed()
+        };
+        let mut page = ClaimBitmap256::zeroed();
+        page.words[0] = 1;
+
+        let text = render(&src, &bd, &leaves, &[page]).unwrap();
+        assert!(text.contains(&format!("\n0,{},3,750,1\n", leaves[0].claimer)));
+        assert!(text.ends_with(&format!("\n1,{},1,250,0\n", leaves[1].claimer)));
+        assert!(text.contains(&format!("\n0,01{}\n", "0".repeat(62))));
+        assert_eq!(render(&src, &bd, &leaves, &[page]).unwrap(), text);
+        // a tree that is not the posted one is refused
+        assert!(render(&src, &BoostDistributor { total_weight: 5, ..bd }, &leaves, &[page]).is_err());
+    }
+}
@@ -0,0 +1,138 @@
+// `claim-audit`: reconcile an epoch's weight tree against its on-chain claim bitmap pages.
+// Lists every unclaimed (index, claimer, amount), sums the remaining liability and
+// compares it with what the distributor and the boost token account can still pay.
+// Used before Clawback and for monthly reconciliation.
+//
+// The tree file is the operator's leaf list as CSV, one `index,claimer,weight` per line
+// (blank lines and lines starting with `#` are skipped), in any order.
+
+use std::fs;
+
+use interest_vault::{math, BoostDistributor, ClaimBitmap256, VaultState};
+use solana_pubkey::Pubkey;
+use solana_rpc_client::rpc_client::RpcClient;
+
+use crate::{pubkey, required, Res};
+
+const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+
+pub struct Leaf {
+    pub index: u32,
+    pub claimer: Pubkey,
+    pub weight: u128,
+}
+
+// claim-audit --vault-state <pk> --epoch <n> --tree <file> [--boost-ata <pk>]
+pub fn run(rpc: &RpcClient, args: &[String]) -> Res<()> {
+    let vault_state = pubkey(&required(args, "--vault-state")?)?;
+    let epoch: u64 = required(args, "--epoch")?.parse()?;
+    let leaves = parse_tree(&fs::read_to_string(required(args, "--tree")?)?)?;
+
+    let acc = rpc.get_account(&vault_state)?;
+    let program_id = acc.owner;
+    let st: VaultState = read(&acc.data, "VaultState")?;
+    let vault_pda = Pubkey::new_from_array(st.vault_pda);
+    let epoch_le = epoch.to_le_bytes();
+    let (distributor, _) = Pubkey::find_program_address(&[b"boost", vault_pda.as_ref(), &epoch_le], &program_id);
+    let bd: BoostDistributor = read(&rpc.get_account(&distributor)?.data, "BoostDistributor")?;
+    // an escrowed epoch is paid only from its own escrow; older epochs share one boost account
+    let boost_ata = if bd.escrow != 0 {
+        Pubkey::find_program_address(&[b"escrow", distributor.as_ref()], &program_id).0
+    } else {
+        pubkey(&required(args, "--boost-ata")?)?
+    };
+    // a page that was never opened means nothing on it has been claimed yet
+    let mut pages = Vec::new();
+    for page in 0..bd.bitmap_page_count.max(1) {
+        pages.push(match rpc.get_account(&claims_page(&program_id, &vault_pda, epoch, page)) {
+            Ok(a) => read::<ClaimBitmap256>(&a.data, "ClaimBitmap256")?,
+            Err(_) => bytemuck::Zeroable::zeroed(),
+        });
+    }
+    let boost_balance = {
+        let d = rpc.get_account(&boost_ata)?.data;
+        if d.len() < TOKEN_ACCOUNT_AMOUNT_OFFSET + 8 { return Err("boost ATA is not a token account".into()) }
+        u64::from_le_bytes(d[TOKEN_ACCOUNT_AMOUNT_OFFSET..TOKEN_ACCOUNT_AMOUNT_OFFSET + 8].try_into().unwrap())
+    };
+
+    // the file has to be the tree that was posted, or the listing is meaningless
+    let file_weight: u128 = leaves.iter().map(|l| l.weight).sum();
+    if leaves.len() != bd.leaf_count as usize || file_weight != bd.total_weight {
+        return Err(format!(
+            "tree file does not match epoch {epoch}: {} leaves / total weight {file_weight}, on-chain {} / {}",
+            leaves.len(), bd.leaf_count, bd.total_weight,
+        ).into())
+    }
+
+    println!("epoch {epoch} distributor {distributor}");
+    println!("{:>6}  {:<44}  {:>20}", "index", "claimer", "amount");
+    let mut unclaimed_count = 0usize;
+    let mut liability: u128 = 0;
+    for l in &leaves {
+        if is_claimed(&bd, &pages, l.index) { continue }
+        let amount = math::claim_amount(bd.boost_total, l.weight, bd.total_weight).ok_or("claim amount overflows")?;
+        println!("{:>6}  {:<44}  {:>20}", l.index, l.claimer, amount);
+        unclaimed_count += 1;
+        liability += amount as u128;
+    }
+
+    let remaining = bd.boost_total.saturating_sub(bd.claimed_total).saturating_sub(bd.clawed_back);
+    println!();
+    println!("unclaimed leaves      {unclaimed_count} of {}", leaves.len());
+    println!("unclaimed liability   {liability}");
+    println!(
+        "distributor remaining {remaining} (boost_total {} - claimed_total {} - clawed_back {})",
+        bd.boost_total, bd.claimed_total, bd.clawed_back,
+    );
+    match bd.expiry_slot {
+        0 => println!("expiry                never"),
+        slot => println!("expiry                slot {slot}; Clawback returns the remaining amount to the vault after it"),
+    }
+    // once clawed back, the leaves above can no longer be claimed and nothing is owed
+    if bd.clawed_back != 0 { return Ok(()) }
+    println!("boost ATA balance     {boost_balance} ({})", if bd.escrow != 0 { "this epoch's escrow" } else { "shared by every epoch" });
+    // per-leaf rounding leaves at most one base unit of dust per leaf in the distributor
+    if liability > remaining as u128 {
+        println!("WARNING: liability exceeds distributor remaining by {}", liability - remaining as u128);
+    }
+    if liability > boost_balance as u128 {
+        println!("WARNING: boost ATA is short by {}", liability - boost_balance as u128);
+    }
+    Ok(())
+}
+
+pub fn parse_tree(s: &str) -> Res<Vec<Leaf>> {
+    let mut leaves = Vec::new();
+    for (n, line) in s.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') { continue }
+        let f: Vec<&str> = line.split(',').map(str::trim).collect();
+        let [index, claimer, weight] = f.as_slice() else {
+            return Err(format!("line {}: expected index,claimer,weight", n + 1).into())
+        };
+        leaves.push(Leaf { index: index.parse()?, claimer: pubkey(claimer)?, weight: weight.parse()? });
+    }
+    leaves.sort_by_key(|l| l.index);
+    Ok(leaves)
+}
+
+// bitmap page holding a leaf; page 0 keeps the pre-paging seeds
+pub fn claims_page(program_id: &Pubkey, vault_pda: &Pubkey, epoch: u64, page: u16) -> Pubkey {
+    let epoch_le = epoch.to_le_bytes();
+    let page_le = page.to_le_bytes();
+    let mut seeds: Vec<&[u8]> = vec![b"claims", vault_pda.as_ref(), &epoch_le];
+    if page != 0 { seeds.push(&page_le) }
+    Pubkey::find_program_address(&seeds, program_id).0
+}
+
+pub fn is_claimed(bd: &BoostDistributor, pages: &[ClaimBitmap256], index: u32) -> bool {
+    let (page, slot) = bd.claim_page(index);
+    pages.get(page as usize)
+        .and_then(|bm| bm.words.get(slot as usize / 8))
+        .is_some_and(|b| b & (1 << (slot % 8)) != 0)
+}
+
+fn read<T: bytemuck::Pod>(d: &[u8], what: &str) -> Res<T> {
+    if d.len() < core::mem::size_of::<T>() { return Err(format!("account too small for {what}").into()) }
+    Ok(bytemuck::pod_read_unaligned(&d[..core::mem::size_of::<T>()]))
+}
@@ -0,0 +1,247 @@
+// Batch operator commands for running many vaults: `batch-donate` and `batch-post-roots`
+// read one row per vault/epoch from a CSV, build every instruction up front and pack
+// them greedily into as few v0 transactions as fit the 1232-byte packet limit. Passing
+// an address lookup table (or `--create-lookup-table`) moves the shared accounts out of
+// each message, which is what lets several vaults share one transaction.
+//
+// The keypair signs everything (donor for donations, operator for roots). Vaults with a
+// yield oracle or a root threshold need extra accounts/signers per vault and are
+// rejected here; send those individually.
+
+use std::{fs, thread::sleep, time::Duration};
+
+use interest_vault::{BoostDistributor, VaultState};
+use solana_address_lookup_table_interface::{
+    instruction::{create_lookup_table, extend_lookup_table},
+    state::AddressLookupTable,
+};
+use solana_instruction::{account_meta::AccountMeta, Instruction};
+use solana_keypair::{read_keypair_file, Keypair};
+use solana_message::{v0, AddressLookupTableAccount, VersionedMessage};
+use solana_pubkey::Pubkey;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_signer::Signer;
+use solana_transaction::versioned::VersionedTransaction;
+
+use crate::{flag, pubkey, required, Res};
+
+const OP_DONATE: u8 = 3;
+const OP_POSTROOT: u8 = 4;
+const ATA_PROGRAM_ID: Pubkey = Pubkey::from_str_const("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+const MINT_DECIMALS_OFFSET: usize = 44;
+
+const PACKET_DATA_SIZE: usize = 1232;
+const ALT_EXTEND_CHUNK: usize = 20; // addresses per ExtendLookupTable, well under the packet limit
+
+// batch-donate --csv <file> --keypair <file> [--lookup-table <pk> | --create-lookup-table] [--dry-run]
+// CSV: vault_state,vault_ata,boost_ata,epoch,amount,boost_bps
+pub fn donate(rpc: &RpcClient, args: &[String]) -> Res<()> {
+    let payer = keypair(args)?;
+    let mut ixs = Vec::new();
+    for (n, row) in rows(&required(args, "--csv")?, 6)? {
+        let at = |e: Box<dyn std::error::Error>| format!("row {n}: {e}");
+        let vault_state = pubkey(&row[0]).map_err(at)?;
+        let st = vault(rpc, &vault_state).map_err(at)?;
+        if st.yield_oracle != [0u8; 32] { return Err(format!("row {n}: {vault_state} needs an oracle attestation").into()) }
+        let program_id = rpc.get_account(&vault_state)?.owner;
+        let usdc_mint = Pubkey::new_from_array(st.usdc_mint);
+        let vault_pda = Pubkey::new_from_array(st.vault_pda);
+        let epoch: u64 = row[3].parse()?;
+        let amount: u64 = row[4].parse()?;
+        let boost_bps: u16 = row[5].parse()?;
+        // SPL Token or Token-2022: the vault requires the program that owns the mint
+        let mint = rpc.get_account(&usdc_mint)?;
+        let token_program = mint.owner;
+        let decimals = *mint.data.get(MINT_DECIMALS_OFFSET).ok_or("USDC mint too small")?;
+
+        let distributor = derive_distributor(&program_id, &vault_pda, epoch);
+        let mut accounts = vec![
+            AccountMeta::new(vault_state, false),
+            AccountMeta::new_readonly(vault_pda, false),
+            AccountMeta::new_readonly(payer.pubkey(), true),
+            AccountMeta::new(ata(&payer.pubkey(), &usdc_mint, &token_program), false),
+            AccountMeta::new(pubkey(&row[1])?, false),
+            AccountMeta::new(pubkey(&row[2])?, false),
+            AccountMeta::new_readonly(token_program, false),
+            AccountMeta::new_readonly(usdc_mint, false),
+            AccountMeta::new(distributor, false),
+        ];
+        // a closed epoch rolls the boost into the next one, which must be passed too
+        if let Ok(a) = rpc.get_account(&distributor) {
+            if a.owner == program_id && read::<BoostDistributor>(&a.data).is_ok_and(|bd| bd.funding_closed()) {
+                accounts.push(AccountMeta::new(derive_distributor(&program_id, &vault_pda, epoch + 1), false));
+            }
+        }
+        let mut data = vec![OP_DONATE];
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.extend_from_slice(&epoch.to_le_bytes());
+        data.extend_from_slice(&boost_bps.to_le_bytes());
+        data.push(decimals);
+        ixs.push(Instruction { program_id, accounts, data });
+    }
+    send_packed(rpc, &payer, ixs, args)
+}
+
+// batch-post-roots --csv <file> --keypair <file> [--lookup-table <pk> | --create-lookup-table] [--dry-run]
+// CSV: vault_state,epoch,total_weight,root_hex,leaf_count
+pub fn post_roots(rpc: &RpcClient, args: &[String]) -> Res<()> {
+    let payer = keypair(args)?;
+    let mut ixs = Vec::new();
+    for (n, row) in rows(&required(args, "--csv")?, 5)? {
+        let at = |e: Box<dyn std::error::Error>| format!("row {n}: {e}");
+        let vault_state = pubkey(&row[0]).map_err(at)?;
+        let st = vault(rpc, &vault_state).map_err(at)?;
+        // PostRoot counts the operator only if it is a listed root signer, so any threshold
+        // can need co-signers this command does not take
+        if st.root_threshold > 0 {
+            return Err(format!("row {n}: {vault_state} has a root threshold of {}; post its root with its root signers individually", st.root_threshold).into())
+        }
+        let program_id = rpc.get_account(&vault_state)?.owner;
+        let epoch: u64 = row[1].parse()?;
+        let total_weight: u128 = row[2].parse()?;
+        let root = hex32(&row[3]).map_err(at)?;
+        let leaf_count: u32 = row[4].parse()?;
+
+        let mut data = vec![OP_POSTROOT];
+        data.extend_from_slice(&epoch.to_le_bytes());
+        data.extend_from_slice(&total_weight.to_le_bytes());
+        data.extend_from_slice(&root);
+        data.extend_from_slice(&leaf_count.to_le_bytes());
+        ixs.push(Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(vault_state, false),
+                AccountMeta::new_readonly(payer.pubkey(), true),
+                AccountMeta::new(derive_distributor(&program_id, &Pubkey::new_from_array(st.vault_pda), epoch), false),
+            ],
+            data,
+        });
+    }
+    send_packed(rpc, &payer, ixs, args)
+}
+
+// Greedy first-fit in CSV order: an instruction joins the current transaction unless
+// that would push it past the packet limit. Order is kept so a failed transaction
+// leaves a clean prefix of the CSV done.
+fn send_packed(rpc: &RpcClient, payer: &Keypair, ixs: Vec<Instruction>, args: &[String]) -> Res<()> {
+    if ixs.is_empty() { return Err("CSV has no rows".into()) }
+    let dry_run = args.iter().any(|a| a == "--dry-run");
+    let tables = match flag(args, "--lookup-table") {
+        Some(t) => vec![load_lookup_table(rpc, &pubkey(&t)?)?],
+        None if args.iter().any(|a| a == "--create-lookup-table") && !dry_run => vec![create_table(rpc, payer, &ixs)?],
+        None => Vec::new(),
+    };
+
+    let blockhash = rpc.get_latest_blockhash()?;
+    let mut batches: Vec<Vec<Instruction>> = Vec::new();
+    let mut current: Vec<Instruction> = Vec::new();
+    for ix in ixs {
+        current.push(ix);
+        if tx_size(payer, &current, &tables, blockhash)? > PACKET_DATA_SIZE {
+            let ix = current.pop().unwrap();
+            if current.is_empty() { return Err("a single instruction does not fit in a transaction".into()) }
+            batches.push(std::mem::replace(&mut current, vec![ix]));
+        }
+    }
+    batches.push(current);
+
+    let total: usize = batches.iter().map(Vec::len).sum();
+    println!("{total} instructions in {} transactions", batches.len());
+    for (i, batch) in batches.iter().enumerate() {
+        if dry_run {
+            println!("  tx {i}: {} instructions, {} bytes", batch.len(), tx_size(payer, batch, &tables, blockhash)?);
+            continue
+        }
+        let msg = v0::Message::try_compile(&payer.pubkey(), batch, &tables, rpc.get_latest_blockhash()?)?;
+        let tx = VersionedTransaction::try_new(VersionedMessage::V0(msg), &[payer])?;
+        let sig = rpc.send_and_confirm_transaction(&tx)?;
+        println!("  tx {i}: {} instructions {sig}", batch.len());
+    }
+    Ok(())
+}
+
+fn tx_size(payer: &Keypair, ixs: &[Instruction], tables: &[AddressLookupTableAccount], blockhash: solana_hash::Hash) -> Res<usize> {
+    let msg = VersionedMessage::V0(v0::Message::try_compile(&payer.pubkey(), ixs, tables, blockhash)?);
+    let sigs = msg.header().num_required_signatures as usize;
+    // shortvec signature count (1 byte below 128) + signatures + message
+    Ok(1 + 64 * sigs + bincode::serialized_size(&msg)? as usize)
+}
+
+// Puts every account the batch touches (except the signer) into a new table and waits
+// until the table is usable. Print the address so later runs can pass --lookup-table.
+fn create_table(rpc: &RpcClient, payer: &Keypair, ixs: &[Instruction]) -> Res<AddressLookupTableAccount> {
+    let mut addresses: Vec<Pubkey> = Vec::new();
+    for ix in ixs {
+        for k in std::iter::once(&ix.program_id).chain(ix.accounts.iter().map(|m| &m.pubkey)) {
+            if *k != payer.pubkey() && !addresses.contains(k) { addresses.push(*k); }
+        }
+    }
+    let slot = rpc.get_slot()?;
+    let (create, table) = create_lookup_table(payer.pubkey(), payer.pubkey(), slot);
+    send_legacy(rpc, payer, &[create])?;
+    for chunk in addresses.chunks(ALT_EXTEND_CHUNK) {
+        send_legacy(rpc, payer, &[extend_lookup_table(table, payer.pubkey(), Some(payer.pubkey()), chunk.to_vec())])?;
+    }
+    // new entries are only resolvable from the slot after they were added
+    let added = rpc.get_slot()?;
+    while rpc.get_slot()? <= added { sleep(Duration::from_millis(400)); }
+    println!("created lookup table {table} with {} addresses", addresses.len());
+    Ok(AddressLookupTableAccount { key: table, addresses })
+}
+
+fn load_lookup_table(rpc: &RpcClient, key: &Pubkey) -> Res<AddressLookupTableAccount> {
+    let data = rpc.get_account(key)?.data;
+    let table = AddressLookupTable::deserialize(&data).map_err(|e| format!("{key} is not a lookup table: {e}"))?;
+    Ok(AddressLookupTableAccount { key: *key, addresses: table.addresses.to_vec() })
+}
+
+fn send_legacy(rpc: &RpcClient, payer: &Keypair, ixs: &[Instruction]) -> Res<()> {
+    let tx = solana_transaction::Transaction::new_signed_with_payer(ixs, Some(&payer.pubkey()), &[payer], rpc.get_latest_blockhash()?);
+    rpc.send_and_confirm_transaction(&tx)?;
+    Ok(())
+}
+
+// (1-based line number, fields) for every non-blank, non-comment line
+fn rows(path: &str, fields: usize) -> Res<Vec<(usize, Vec<String>)>> {
+    let mut out = Vec::new();
+    for (n, line) in fs::read_to_string(path)?.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') { continue }
+        let f: Vec<String> = line.split(',').map(|v| v.trim().to_string()).collect();
+        if f.len() != fields { return Err(format!("line {}: expected {fields} fields, got {}", n + 1, f.len()).into()) }
+        out.push((n + 1, f));
+    }
+    Ok(out)
+}
+
+fn keypair(args: &[String]) -> Res<Keypair> {
+    let path = required(args, "--keypair")?;
+    read_keypair_file(&path).map_err(|e| format!("reading {path}: {e}").into())
+}
+
+fn vault(rpc: &RpcClient, key: &Pubkey) -> Res<VaultState> {
+    read(&rpc.get_account(key)?.data)
+}
+
+fn read<T: bytemuck::Pod>(d: &[u8]) -> Res<T> {
+    if d.len() < core::mem::size_of::<T>() { return Err("account too small".into()) }
+    Ok(bytemuck::pod_read_unaligned(&d[..core::mem::size_of::<T>()]))
+}
+
+fn derive_distributor(program_id: &Pubkey, vault_pda: &Pubkey, epoch: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"boost", vault_pda.as_ref(), &epoch.to_le_bytes()], program_id).0
+}
+
+fn ata(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[owner.as_ref(), token_program.as_ref(), mint.as_ref()], &ATA_PROGRAM_ID).0
+}
+
+fn hex32(s: &str) -> Res<[u8; 32]> {
+    let s = s.trim_start_matches("0x");
+    if s.len() != 64 { return Err(format!("{s} is not 32 bytes of hex").into()) }
+    let mut out = [0u8; 32];
+    for (i, b) in out.iter_mut().enumerate() {
+        *b = u8::from_str_radix(&s[2 * i..2 * i + 2], 16)?;
+    }
+    Ok(out)
+}
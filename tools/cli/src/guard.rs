@@ -0,0 +1,116 @@
+// `donation-check`: off-chain sanity gate the operator runs before DonateReward. It
+// refuses (non-zero exit, ALERT on stderr) when the donation would move pps by more
+// than a configured amount or strays too far from the trailing average, complementing
+// the on-chain yield-oracle tolerance and pps ceiling.
+
+use interest_vault::{math, Discriminator, VaultState};
+use solana_rpc_client::rpc_client::RpcClient;
+
+use crate::{flag, pubkey, required, Res};
+
+const BPS_DENOM: u128 = 10_000;
+const DEFAULT_MAX_PPS_MOVE_BPS: u128 = 100;
+const DEFAULT_MAX_DEVIATION_BPS: u128 = 5_000;
+
+pub struct Limits {
+    pub max_pps_move_bps: u128,
+    pub max_deviation_bps: u128,
+}
+
+// pps move (bps of current pps) and deviation from the trailing mean (bps), if any
+#[derive(Debug, PartialEq)]
+pub struct DonationCheck {
+    pub pps_move_bps: u128,
+    pub deviation_bps: Option<u128>,
+}
+
+// donation-check --vault-state <pk> --amount <n> [--boost-bps <n>] [--max-pps-move-bps <n>]
+//                [--trailing <n,...>] [--max-deviation-bps <n>]
+pub fn run(rpc: &RpcClient, args: &[String]) -> Res<()> {
+    let vault_state = pubkey(&required(args, "--vault-state")?)?;
+    let amount: u64 = required(args, "--amount")?.parse()?;
+    let boost_bps: u16 = flag(args, "--boost-bps").map(|v| v.parse()).transpose()?.unwrap_or(0);
+    let trailing = match flag(args, "--trailing") {
+        Some(t) => t.split(',').map(|v| v.trim().parse::<u64>()).collect::<Result<Vec<_>, _>>()?,
+        None => Vec::new(),
+    };
+    let limits = Limits {
+        max_pps_move_bps: flag(args, "--max-pps-move-bps").map(|v| v.parse()).transpose()?.unwrap_or(DEFAULT_MAX_PPS_MOVE_BPS),
+        max_deviation_bps: flag(args, "--max-deviation-bps").map(|v| v.parse()).transpose()?.unwrap_or(DEFAULT_MAX_DEVIATION_BPS),
+    };
+
+    let data = rpc.get_account(&vault_state)?.data;
+    if data.len() < VaultState::LEN { return Err("not a VaultState account".into()) }
+    let st: VaultState = bytemuck::pod_read_unaligned(&data[..VaultState::LEN]);
+
+    match check_donation(&st, amount, boost_bps, &trailing, &limits) {
+        Ok(c) => {
+            print!("ok: pps move {} bps", c.pps_move_bps);
+            if let Some(d) = c.deviation_bps { print!(", {d} bps from trailing average"); }
+            println!();
+            Ok(())
+        }
+        Err(reason) => {
+            eprintln!("ALERT: refusing donation of {amount}: {reason}");
+            Err(reason.into())
+        }
+    }
+}
+
+pub fn check_donation(st: &VaultState, amount: u64, boost_bps: u16, trailing: &[u64], limits: &Limits) -> Result<DonationCheck, String> {
+    let (_, base) = math::split_donation(amount, boost_bps).ok_or("boost_bps above 10000")?;
+    // an empty vault buffers the base instead of moving pps
+    let pps_move_bps = if st.total_shares == 0 || st.pps == 0 {
+        0
+    } else {
+        let delta = math::pps_delta(base, st.total_shares).ok_or("pps delta overflows")?;
+        delta.saturating_mul(BPS_DENOM) / st.pps
+    };
+    if pps_move_bps > limits.max_pps_move_bps {
+        return Err(format!("moves pps by {pps_move_bps} bps (limit {})", limits.max_pps_move_bps))
+    }
+
+    let deviation_bps = if trailing.is_empty() {
+        None
+    } else {
+        let avg = trailing.iter().map(|&v| v as u128).sum::<u128>() / trailing.len() as u128;
+        let dev = if avg == 0 { u128::MAX } else { (amount as u128).abs_diff(avg) * BPS_DENOM / avg };
+        if dev > limits.max_deviation_bps {
+            return Err(format!("{amount} deviates from trailing average {avg} by more than {} bps", limits.max_deviation_bps))
+        }
+        Some(dev)
+    };
+    Ok(DonationCheck { pps_move_bps, deviation_bps })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytemuck::Zeroable;
+    use interest_vault::math::RAY;
+
+    const LIMITS: Limits = Limits { max_pps_move_bps: 100, max_deviation_bps: 5_000 };
+
+    fn vault(total_shares: u128) -> VaultState {
+        VaultState { pps: RAY, total_shares, ..VaultState::zeroed() }
+    }
+
+    #[test]
+    fn pps_move_limit() {
+        // 1% of a 1_000_000-share vault at pps 1.0
+        assert_eq!(check_donation(&vault(1_000_000), 10_000, 0, &[], &LIMITS), Ok(DonationCheck { pps_move_bps: 100, deviation_bps: None }));
+        assert!(check_donation(&vault(1_000_000), 10_100, 0, &[], &LIMITS).is_err());
+        // boost does not move pps
+        assert!(check_donation(&vault(1_000_000), 20_000, 5_000, &[], &LIMITS).is_ok());
+        assert_eq!(check_donation(&vault(0), u64::MAX, 0, &[], &LIMITS).unwrap().pps_move_bps, 0);
+    }
+
+    #[test]
+    fn trailing_average_limit() {
+        let v = vault(u64::MAX as u128);
+        assert_eq!(check_donation(&v, 150, 0, &[100, 100], &LIMITS).unwrap().deviation_bps, Some(5_000));
+        assert!(check_donation(&v, 151, 0, &[100, 100], &LIMITS).is_err());
+        assert!(check_donation(&v, 49, 0, &[100, 100], &LIMITS).is_err());
+        assert!(check_donation(&v, 1, 0, &[0], &LIMITS).is_err());
+    }
+}
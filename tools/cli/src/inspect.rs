@@ -0,0 +1,225 @@
+// `inspect <pubkey>`: human-readable dump of a vault program account.
+//
+// The type is picked by the account's 8-byte discriminator (interest_vault::Discriminator).
+
+use core::mem::size_of;
+
+use bytemuck::Pod;
+use interest_vault::{
+    math::RAY, BoostDistributor, ClaimBitmap256, Discriminator, EpochReport, RegistryPage, UserPosition, UserPrefs, VaultRegistry, VaultState, PAUSE_CLAIM,
+    PAUSE_DEPOSIT, PAUSE_DONATE, PAUSE_EMERGENCY, PAUSE_WITHDRAW, REGISTRY_PAGE_VAULTS, UPGRADE_AUTHORITY_HELD, UPGRADE_AUTHORITY_IMMUTABLE,
+};
+use solana_pubkey::Pubkey;
+use solana_rpc_client::rpc_client::RpcClient;
+
+use crate::Res;
+
+pub fn run(rpc: &RpcClient, key: &Pubkey) -> Res<()> {
+    let acc = rpc.get_account(key)?;
+    println!("account   {key}");
+    println!("owner     {}", acc.owner);
+    println!("lamports  {}", acc.lamports);
+    println!("data_len  {}", acc.data.len());
+    println!();
+
+    let d = acc.data.as_slice();
+    match d.get(..8) {
+        Some(t) if t == VaultState::DISCRIMINATOR => print_vault(read(d)?),
+        Some(t) if t == BoostDistributor::DISCRIMINATOR => print_distributor(read(d)?),
+        Some(t) if t == ClaimBitmap256::DISCRIMINATOR => print_bitmap(read(d)?),
+        Some(t) if t == UserPrefs::DISCRIMINATOR => print_prefs(read(d)?),
+        Some(t) if t == UserPosition::DISCRIMINATOR => print_position(read(d)?),
+        Some(t) if t == VaultRegistry::DISCRIMINATOR => print_registry(read(d)?),
+        Some(t) if t == RegistryPage::DISCRIMINATOR => print_registry_page(read(d)?),
+        Some(t) if t == EpochReport::DISCRIMINATOR => print_report(read(d)?),
+        _ => return Err("unrecognized account: no vault account discriminator".into()),
+    }
+    Ok(())
+}
+
+fn read<T: Pod>(d: &[u8]) -> Res<T> {
+    if d.len() < size_of::<T>() { return Err("account is shorter than its layout".into()) }
+    Ok(bytemuck::pod_read_unaligned(&d[..size_of::<T>()]))
+}
+
+fn key(k: &[u8; 32]) -> Pubkey { Pubkey::new_from_array(*k) }
+
+fn hex(b: &[u8]) -> String {
+    b.iter().map(|x| format!("{x:02x}")).collect()
+}
+
+// raw USDC per raw share, RAY-scaled fixed point → decimal
+fn ray(v: u128) -> String {
+    format!("{}.{:012}", v / RAY, v % RAY)
+}
+
+fn flag(v: u8) -> &'static str { if v != 0 { "on" } else { "off" } }
+
+fn print_vault(st: VaultState) {
+    println!("VaultState");
+    println!("  admin                {}", key(&st.admin));
+    if st.pending_admin != [0u8; 32] {
+        println!("  pending_admin        {}", key(&st.pending_admin));
+    }
+    if st.seed_admin != [0u8; 32] && st.seed_admin != st.admin {
+        println!("  seed_admin           {} (vault_pda seed)", key(&st.seed_admin));
+    }
+    println!("  operator             {}", key(&st.operator));
+    println!("  usdc_mint            {}", key(&st.usdc_mint));
+    println!("  share_mint           {} (decimals {})", key(&st.share_mint), st.share_decimals);
+    println!("  vault_pda            {} (bump {})", key(&st.vault_pda), st.vault_bump);
+    println!("  vault_id             {}", st.vault_id);
+    println!("  auth_pda             {} (bump {})", key(&st.auth_pda), st.auth_bump);
+    println!("  vault_usdc           {}", key(&st.vault_usdc));
+    println!("  boost_usdc           {}", key(&st.boost_usdc));
+    println!("  total_shares         {}", st.total_shares);
+    println!("  pps                  {} ({} raw)", ray(st.pps), st.pps);
+    println!("  pps_ceiling          {}", ray(st.pps_ceiling));
+    match st.total_managed_assets() {
+        Some(a) => println!("  total_managed_assets {a}"),
+        None => println!("  total_managed_assets overflow"),
+    }
+    println!("  buffered_base        {}", st.buffered_base);
+    if st.deposit_cap != 0 {
+        println!("  deposit_cap          {}", st.deposit_cap);
+    } else {
+        println!("  deposit_cap          none");
+    }
+    if st.user_deposit_cap != 0 {
+        println!("  user_deposit_cap     {}", st.user_deposit_cap);
+    }
+    println!("  last_settle_slot     {}", st.last_settle_slot);
+    if st.liveness_slots != 0 {
+        println!("  liveness             {} slots since activity at slot {}", st.liveness_slots, st.last_activity_slot);
+    }
+    if st.yield_oracle != [0u8; 32] {
+        println!("  yield_oracle         {} (tolerance {} bps)", key(&st.yield_oracle), st.oracle_tolerance_bps);
+    } else {
+        println!("  yield_oracle         disabled");
+    }
+    println!("  restrict_donors      {}", flag(st.restrict_donors));
+    for d in &st.donor_allowlist[..(st.donor_allowlist_len as usize).min(st.donor_allowlist.len())] {
+        println!("    donor              {}", key(d));
+    }
+    println!("  burn_dust            {}", flag(st.burn_dust));
+    let paused: Vec<&str> = [
+        (PAUSE_EMERGENCY, "EMERGENCY (withdraw-only)"), (PAUSE_DEPOSIT, "deposit"), (PAUSE_WITHDRAW, "withdraw"),
+        (PAUSE_DONATE, "donate"), (PAUSE_CLAIM, "claim"),
+    ]
+        .into_iter()
+        .filter(|(bit, _)| st.flags & bit != 0)
+        .map(|(_, name)| name)
+        .collect();
+    println!("  paused               {}", if paused.is_empty() { "none".to_string() } else { paused.join(", ") });
+    if st.guardian != [0u8; 32] {
+        println!("  guardian             {}", key(&st.guardian));
+    }
+    if st.successor != [0u8; 32] {
+        println!("  successor            {} (program {})", key(&st.successor), key(&st.successor_program));
+    }
+    println!("  bootstrap            {} shares locked until slot {}", st.bootstrap_locked_shares, st.bootstrap_unlock_slot);
+    println!("  root_threshold       {} of {}", st.root_threshold, st.root_signers_len);
+    for s in &st.root_signers[..(st.root_signers_len as usize).min(st.root_signers.len())] {
+        println!("    root_signer        {}", key(s));
+    }
+    match st.upgrade_authority_status {
+        UPGRADE_AUTHORITY_IMMUTABLE => println!("  upgrade_authority    none, program immutable (recorded slot {})", st.upgrade_authority_slot),
+        UPGRADE_AUTHORITY_HELD => println!("  upgrade_authority    {} (recorded slot {})", key(&st.upgrade_authority), st.upgrade_authority_slot),
+        _ => println!("  upgrade_authority    not recorded"),
+    }
+}
+
+fn print_distributor(bd: BoostDistributor) {
+    println!("BoostDistributor");
+    println!("  epoch             {}", bd.epoch);
+    if bd.funding_closed() {
+        println!("  root              {}{}", hex(&bd.root), if bd.finalized != 0 { " (finalized)" } else { "" });
+    } else {
+        println!("  root              (not posted)");
+    }
+    println!("  leaf_count        {}", bd.leaf_count);
+    if bd.bitmap_page_count != 0 {
+        println!("  bitmap_pages      {} x {} leaves", bd.bitmap_page_count, bd.bitmap_page_size);
+    } else {
+        println!("  bitmap_pages      (posted before paging; one 256-leaf page)");
+    }
+    println!("  total_weight      {}", bd.total_weight);
+    println!("  claimed_weight    {}", bd.claimed_weight);
+    println!("  boost_total       {}", bd.boost_total);
+    let unclaimed = bd.boost_total.saturating_sub(bd.claimed_total).saturating_sub(bd.clawed_back);
+    println!("  claimed_total     {} ({} unclaimed)", bd.claimed_total, unclaimed);
+    println!("  clawed_back       {}", bd.clawed_back);
+    if bd.expiry_slot != 0 {
+        println!("  expiry_slot       {}", bd.expiry_slot);
+    } else {
+        println!("  expiry_slot       (never expires)");
+    }
+    println!("  escrow            {}", if bd.escrow != 0 { "own escrow [b\"escrow\", distributor]" } else { "shared boost account" });
+    if bd.weight_commitment != [0u8; 32] {
+        println!("  weight_commitment {}", hex(&bd.weight_commitment));
+    } else {
+        println!("  weight_commitment (none)");
+    }
+    if bd.archive_hash != [0u8; 32] {
+        println!("  archive_hash      {}", hex(&bd.archive_hash));
+    }
+}
+
+fn print_bitmap(bm: ClaimBitmap256) {
+    let claimed: Vec<String> = (0..256usize)
+        .filter(|i| bm.words[i / 8] & (1 << (i % 8)) != 0)
+        .map(|i| i.to_string())
+        .collect();
+    println!("ClaimBitmap256");
+    println!("  claimed   {} of 256", claimed.len());
+    if !claimed.is_empty() {
+        println!("  indices   {}", claimed.join(", "));
+    }
+}
+
+fn print_prefs(p: UserPrefs) {
+    println!("UserPrefs");
+    println!("  vault_state {}", key(&p.vault_state));
+    println!("  user        {}", key(&p.user));
+    println!("  auto_claim  {}", flag(p.auto_claim));
+    println!("  compound    {}", flag(p.compound));
+    println!("  bump        {}", p.bump);
+}
+
+fn print_position(p: UserPosition) {
+    println!("UserPosition");
+    println!("  vault_state {}", key(&p.vault_state));
+    println!("  user        {}", key(&p.user));
+    println!("  deposited   {}", p.deposited);
+    println!("  epoch       {} ({} shares deposited, share-slots {})", p.epoch, p.epoch_shares, p.epoch_share_slots);
+    println!("  last_deposit_slot {}", p.last_deposit_slot);
+    println!("  bump        {}", p.bump);
+}
+
+fn print_registry(r: VaultRegistry) {
+    println!("VaultRegistry");
+    println!("  vaults      {}", r.count);
+    println!("  pages       {}", r.count.div_ceil(REGISTRY_PAGE_VAULTS as u64));
+    println!("  bump        {}", r.bump);
+}
+
+fn print_registry_page(p: RegistryPage) {
+    println!("RegistryPage {}", p.page);
+    let first = p.page as usize * REGISTRY_PAGE_VAULTS;
+    for (i, v) in p.vaults[..(p.len as usize).min(REGISTRY_PAGE_VAULTS)].iter().enumerate() {
+        println!("  {:>6}  {}", first + i, key(v));
+    }
+    println!("  bump        {}", p.bump);
+}
+
+fn print_report(r: EpochReport) {
+    println!("EpochReport {}", r.epoch);
+    println!("  gross_yield     {}", r.gross_yield);
+    println!("  fees            {}", r.fees);
+    println!("  boost_allocated {}", r.boost_allocated);
+    println!("  strategy_pnl    {}", r.strategy_pnl);
+    println!("  pps             {}", ray(r.pps));
+    println!("  reported_slot   {}", r.reported_slot);
+    println!("  operator        {}", key(&r.operator));
+    println!("  bump            {}", r.bump);
+}
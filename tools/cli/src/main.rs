@@ -0,0 +1,111 @@
+// Operator/debugging CLI for the interest vault.
+//
+//   interest-cli [--url <rpc>] inspect <pubkey>
+//   interest-cli [--url <rpc>] claim-ticket --vault-state .. --claimer .. --out <file>
+//   interest-cli [--url <rpc>] broadcast <file>
+//   interest-cli [--url <rpc>] donation-check --vault-state .. --amount ..
+//   interest-cli [--url <rpc>] claim-audit --vault-state .. --epoch .. --tree <csv> [--boost-ata ..]
+//   interest-cli [--url <rpc>] epoch-archive --vault-state .. --epoch .. --tree <csv> --out-dir <dir> [--keypair <file>]
+//   interest-cli [--url <rpc>] reward-preview --tree <csv> (--boost-total .. | --vault-state .. --epoch ..)
+//   interest-cli [--url <rpc>] batch-donate --csv <file> --keypair <file>
+//   interest-cli [--url <rpc>] batch-post-roots --csv <file> --keypair <file>
+//   interest-cli [--url <rpc>] verify-build --program <pk> [--so <path>]
+//
+// The RPC defaults to $INTEREST_RPC_URL, then localnet.
+
+mod archive;
+mod audit;
+mod batch;
+mod guard;
+mod inspect;
+mod preview;
+mod ticket;
+mod verify;
+
+use std::{error::Error, str::FromStr};
+
+use solana_commitment_config::CommitmentConfig;
+use solana_pubkey::Pubkey;
+use solana_rpc_client::rpc_client::RpcClient;
+
+const DEFAULT_RPC_URL: &str = "http://127.0.0.1:8899";
+
+type Res<T> = Result<T, Box<dyn Error>>;
+
+const USAGE: &str = "usage: interest-cli [--url <rpc>] <command>
+
+commands:
+  inspect <pubkey>   fetch an account and print it as VaultState, BoostDistributor,
+                     ClaimBitmap256, UserPrefs or UserPosition
+  claim-ticket --vault-state <pk> [--boost-ata <pk>] --claimer <pk> --epoch <n>
+               --index <n> --weight <n> [--proof <hex32,...>] [--nonce <pk>] --out <file>
+                     write an unsigned Claim transaction (base64) for offline signing
+  broadcast <file>   send a signed claim ticket
+  donation-check --vault-state <pk> --amount <n> [--boost-bps <n>] [--max-pps-move-bps <n>]
+                 [--trailing <n,...>] [--max-deviation-bps <n>]
+                     fail with an ALERT if a pending donation moves pps too far or strays
+                     from the trailing average (defaults: 100 bps, 5000 bps)
+  claim-audit --vault-state <pk> --epoch <n> --tree <csv> [--boost-ata <pk>]
+                     list unclaimed (index, claimer, amount) for an epoch and compare the
+                     remaining liability with the distributor and boost ATA
+  epoch-archive --vault-state <pk> --epoch <n> --tree <csv> --out-dir <dir> [--keypair <file>]
+                     after expiry and Clawback: write the tree, claim bitmap pages and payout
+                     ledger to <out-dir>/<sha256>.archive; with the operator keypair, record
+                     the hash on the distributor (RecordArchive)
+  reward-preview --tree <csv> (--boost-total <n> | --vault-state <pk> --epoch <n>) [--top <n>] [--dust <n>]
+                     before PostRoot: per-leaf payouts, Gini and top-N share, rounding and
+                     dust totals (defaults: top 10, dust below 1 base unit)
+  batch-donate --csv <file> --keypair <file> [--lookup-table <pk> | --create-lookup-table] [--dry-run]
+                     DonateReward for every row (vault_state,vault_ata,boost_ata,epoch,amount,boost_bps),
+                     packed into as few v0 transactions as fit
+  batch-post-roots --csv <file> --keypair <file> [--lookup-table <pk> | --create-lookup-table] [--dry-run]
+                     PostRoot for every row (vault_state,epoch,total_weight,root_hex,leaf_count)
+  verify-build --program <pk> [--so <path>]
+                     compare the .so from scripts/verifiable-build.sh with the deployed code;
+                     prints a JSON verdict and fails on a mismatch
+
+--boost-ata is only needed for epochs without their own boost escrow.";
+
+fn main() {
+    if let Err(e) = run(std::env::args().skip(1).collect()) {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+}
+
+fn run(mut args: Vec<String>) -> Res<()> {
+    let mut url = std::env::var("INTEREST_RPC_URL").unwrap_or_else(|_| DEFAULT_RPC_URL.to_string());
+    if args.first().map(String::as_str) == Some("--url") {
+        if args.len() < 2 { return Err(USAGE.into()) }
+        url = args.remove(1);
+        args.remove(0);
+    }
+    let rpc = RpcClient::new_with_commitment(url, CommitmentConfig::confirmed());
+
+    match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["inspect", key] => inspect::run(&rpc, &pubkey(key)?),
+        ["claim-ticket", ..] => ticket::export(&rpc, &args[1..]),
+        ["broadcast", path] => ticket::broadcast(&rpc, path),
+        ["donation-check", ..] => guard::run(&rpc, &args[1..]),
+        ["claim-audit", ..] => audit::run(&rpc, &args[1..]),
+        ["epoch-archive", ..] => archive::run(&rpc, &args[1..]),
+        ["reward-preview", ..] => preview::run(&rpc, &args[1..]),
+        ["batch-donate", ..] => batch::donate(&rpc, &args[1..]),
+        ["batch-post-roots", ..] => batch::post_roots(&rpc, &args[1..]),
+        ["verify-build", ..] => verify::run(&rpc, &args[1..]),
+        _ => Err(USAGE.into()),
+    }
+}
+
+fn pubkey(s: &str) -> Res<Pubkey> {
+    Pubkey::from_str(s).map_err(|e| format!("invalid pubkey {s}: {e}").into())
+}
+
+// value following `name` in a flat `--name value` list
+fn flag(args: &[String], name: &str) -> Option<String> {
+    args.iter().position(|a| a == name).and_then(|i| args.get(i + 1)).cloned()
+}
+
+fn required(args: &[String], name: &str) -> Res<String> {
+    flag(args, name).ok_or_else(|| format!("missing {name}\n\n{USAGE}").into())
+}
@@ -0,0 +1,189 @@
+// `reward-preview`: dry run of an epoch's boost split before PostRoot. Given the weight
+// snapshot (the same `index,claimer,weight` CSV that claim-audit reads) and the boost
+// pool, prints each leaf's payout, how concentrated the payouts are (Gini, top-N share)
+// and what is lost to rounding and dust, so the operator can sanity-check the tree
+// before it is committed and can no longer be changed.
+//
+// The pool is `--boost-total`, or the epoch distributor's boost_total read from chain.
+
+use std::{collections::HashSet, fs};
+
+use interest_vault::{math, BoostDistributor, VaultState};
+use solana_pubkey::Pubkey;
+use solana_rpc_client::rpc_client::RpcClient;
+
+use crate::{audit::{parse_tree, Leaf}, flag, pubkey, required, Res};
+
+const BPS_DENOM: u128 = 10_000;
+const DEFAULT_TOP: usize = 10;
+// payouts below this many base units count as dust
+const DEFAULT_DUST: u64 = 1;
+
+pub struct Payout {
+    pub index: u32,
+    pub claimer: Pubkey,
+    pub weight: u128,
+    pub amount: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Stats {
+    pub paid: u128,
+    // boost_total minus what the leaves can claim; stays in the distributor
+    pub remainder: u128,
+    pub gini_bps: u128,
+    pub top_share_bps: u128,
+    pub dust_leaves: usize,
+    pub dust_amount: u128,
+}
+
+// reward-preview --tree <csv> (--boost-total <n> | --vault-state <pk> --epoch <n>) [--top <n>] [--dust <n>]
+pub fn run(rpc: &RpcClient, args: &[String]) -> Res<()> {
+    let leaves = parse_tree(&fs::read_to_string(required(args, "--tree")?)?)?;
+    let top: usize = flag(args, "--top").map(|v| v.parse()).transpose()?.unwrap_or(DEFAULT_TOP);
+    let dust: u64 = flag(args, "--dust").map(|v| v.parse()).transpose()?.unwrap_or(DEFAULT_DUST);
+    let boost_total = match flag(args, "--boost-total") {
+        Some(v) => v.parse()?,
+        None => pool_from_chain(rpc, args)?,
+    };
+
+    let payouts = payouts(&leaves, boost_total)?;
+    let stats = stats(&payouts, boost_total, top, dust);
+
+    println!("{:>6}  {:<44}  {:>24}  {:>20}", "index", "claimer", "weight", "amount");
+    for p in &payouts {
+        println!("{:>6}  {:<44}  {:>24}  {:>20}", p.index, p.claimer, p.weight, p.amount);
+    }
+    let total_weight: u128 = leaves.iter().map(|l| l.weight).sum();
+    println!();
+    println!("leaves          {} (total weight {total_weight})", leaves.len());
+    println!("boost_total     {boost_total}");
+    println!("paid out        {}", stats.paid);
+    println!("rounding left   {} (stays in the distributor)", stats.remainder);
+    println!("gini            {}.{:04}", stats.gini_bps / BPS_DENOM, stats.gini_bps % BPS_DENOM);
+    println!("top {:<11} {}.{:02}% of payouts", top.min(payouts.len()), stats.top_share_bps / 100, stats.top_share_bps % 100);
+    println!("dust (< {dust})     {} leaves, {} total", stats.dust_leaves, stats.dust_amount);
+    for w in warnings(&leaves) {
+        println!("WARNING: {w}");
+    }
+    Ok(())
+}
+
+// boost accumulated on the epoch's distributor so far
+fn pool_from_chain(rpc: &RpcClient, args: &[String]) -> Res<u64> {
+    let vault_state = pubkey(&required(args, "--vault-state")?)?;
+    let epoch: u64 = required(args, "--epoch")?.parse()?;
+    let acc = rpc.get_account(&vault_state)?;
+    let st: VaultState = read(&acc.data, "VaultState")?;
+    let vault_pda = Pubkey::new_from_array(st.vault_pda);
+    let (distributor, _) = Pubkey::find_program_address(&[b"boost", vault_pda.as_ref(), &epoch.to_le_bytes()], &acc.owner);
+    let bd: BoostDistributor = read(&rpc.get_account(&distributor)?.data, "BoostDistributor")?;
+    if bd.funding_closed() {
+        println!("note: epoch {epoch} already has a posted root; previewing against its boost_total");
+    }
+    Ok(bd.boost_total)
+}
+
+// the amount Claim pays each leaf, largest first
+pub fn payouts(leaves: &[Leaf], boost_total: u64) -> Res<Vec<Payout>> {
+    let total_weight: u128 = leaves.iter().try_fold(0u128, |acc, l| acc.checked_add(l.weight)).ok_or("total weight overflows u128")?;
+    if total_weight == 0 { return Err("tree has no weight".into()) }
+    let mut out = Vec::with_capacity(leaves.len());
+    for l in leaves {
+        let amount = math::claim_amount(boost_total, l.weight, total_weight).ok_or("claim amount overflows")?;
+        out.push(Payout { index: l.index, claimer: l.claimer, weight: l.weight, amount });
+    }
+    out.sort_by(|a, b| b.amount.cmp(&a.amount).then(a.index.cmp(&b.index)));
+    Ok(out)
+}
+
+// `payouts` must be sorted largest first, as `payouts()` returns them
+pub fn stats(payouts: &[Payout], boost_total: u64, top: usize, dust: u64) -> Stats {
+    let paid: u128 = payouts.iter().map(|p| p.amount as u128).sum();
+    let top_paid: u128 = payouts.iter().take(top).map(|p| p.amount as u128).sum();
+    let dust_amounts = payouts.iter().filter(|p| p.amount < dust).map(|p| p.amount as u128);
+    Stats {
+        paid,
+        remainder: (boost_total as u128).saturating_sub(paid),
+        gini_bps: gini_bps(payouts, paid),
+        top_share_bps: (top_paid * BPS_DENOM).checked_div(paid).unwrap_or(0),
+        dust_leaves: dust_amounts.clone().count(),
+        dust_amount: dust_amounts.sum(),
+    }
+}
+
+// Gini coefficient of the payouts in bps: 0 when everyone gets the same, approaching
+// 10000 when one leaf takes everything. With x ascending and 1-based rank i,
+// G = (2 * sum(i * x_i) - (n + 1) * sum(x)) / (n * sum(x)).
+fn gini_bps(desc: &[Payout], paid: u128) -> u128 {
+    let n = desc.len() as u128;
+    if n == 0 || paid == 0 { return 0 }
+    let ranked: u128 = desc.iter().rev().enumerate().map(|(i, p)| (i as u128 + 1) * p.amount as u128).sum();
+    (2 * ranked - (n + 1) * paid) * BPS_DENOM / (n * paid)
+}
+
+// tree problems PostRoot would accept but claims would trip over
+fn warnings(leaves: &[Leaf]) -> Vec<String> {
+    let mut out = Vec::new();
+    // claims need index < leaf_count, and leaf_count is the number of leaves
+    let beyond: Vec<String> = leaves.iter().filter(|l| l.index as usize >= leaves.len()).map(|l| l.index.to_string()).collect();
+    if !beyond.is_empty() {
+        out.push(format!("indices {} are >= the leaf count and could never be claimed", beyond.join(", ")));
+    }
+    for pair in leaves.windows(2) {
+        if pair[0].index == pair[1].index { out.push(format!("index {} appears more than once", pair[0].index)); }
+    }
+    let mut seen = HashSet::new();
+    for l in leaves {
+        if !seen.insert(l.claimer) { out.push(format!("claimer {} has more than one leaf", l.claimer)); }
+        if l.weight == 0 { out.push(format!("index {} has zero weight", l.index)); }
+    }
+    out
+}
+
+fn read<T: bytemuck::Pod>(d: &[u8], what: &str) -> Res<T> {
+    if d.len() < core::mem::size_of::<T>() { return Err(format!("account too small for {what}").into()) }
+    Ok(bytemuck::pod_read_unaligned(&d[..core::mem::size_of::<T>()]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree(weights: &[u128]) -> Vec<Leaf> {
+        weights.iter().enumerate().map(|(i, &weight)| Leaf { index: i as u32, claimer: Pubkey::new_unique(), weight }).collect()
+    }
+
+    #[test]
+    fn even_split_has_zero_gini() {
+        let p = payouts(&tree(&[5, 5, 5, 5]), 1_000).unwrap();
+        assert!(p.iter().all(|p| p.amount == 250));
+        assert_eq!(stats(&p, 1_000, 1, 1), Stats {
+            paid: 1_000, remainder: 0, gini_bps: 0, top_share_bps: 2_500, dust_leaves: 0, dust_amount: 0,
+        });
+    }
+
+    #[test]
+    fn concentrated_split_and_dust() {
+        // 997, 2, 0 and 0 after rounding; 1 base unit stays behind
+        let p = payouts(&tree(&[9_970, 4, 3, 23]), 1_000).unwrap();
+        assert_eq!(p.iter().map(|p| p.amount).collect::<Vec<_>>(), [997, 2, 0, 0]);
+        let s = stats(&p, 1_000, 1, 1);
+        assert_eq!((s.paid, s.remainder), (999, 1));
+        assert_eq!(s.top_share_bps, 9_979);
+        assert_eq!((s.dust_leaves, s.dust_amount), (2, 0));
+        // x ascending 0,0,2,997: (2 * (3*2 + 4*997) - 5 * 999) / (4 * 999)
+        assert_eq!(s.gini_bps, (2 * (6 + 3_988) - 5 * 999) * BPS_DENOM / (4 * 999));
+        assert_eq!(stats(&p, 1_000, 4, 3).dust_amount, 2);
+    }
+
+    #[test]
+    fn flags_unclaimable_tree_shapes() {
+        let mut leaves = tree(&[1, 0]);
+        leaves[1].index = 5;
+        leaves[1].claimer = leaves[0].claimer;
+        let w = warnings(&leaves);
+        assert_eq!(w.len(), 3);
+        assert!(payouts(&tree(&[0, 0]), 1).is_err());
+    }
+}
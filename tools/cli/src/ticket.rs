@@ -0,0 +1,174 @@
+// Cold-wallet claims: `claim-ticket` builds an unsigned Claim transaction (proof
+// embedded) and writes it as base64; the wallet signs it offline, and `broadcast`
+// sends the signed file from any online machine.
+//
+// A recent blockhash expires in ~1 minute, which is too short for an offline round
+// trip, so `--nonce <account>` uses a durable nonce (authority = the claimer) instead.
+
+use std::fs;
+
+use base64::{engine::general_purpose::STANDARD as B64, Engine};
+use interest_vault::{math, BoostDistributor, VaultState};
+use solana_hash::Hash;
+use solana_instruction::{account_meta::AccountMeta, Instruction};
+use solana_message::Message;
+use solana_pubkey::Pubkey;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_transaction::Transaction;
+
+use crate::{audit::claims_page, flag, pubkey, required, Res};
+
+const OP_CLAIM: u8 = 5;
+const ATA_PROGRAM_ID: Pubkey = Pubkey::from_str_const("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::from_str_const("11111111111111111111111111111111");
+const RECENT_BLOCKHASHES_ID: Pubkey = Pubkey::from_str_const("SysvarRecentB1ockHashes11111111111111111111");
+const SYS_IX_ADVANCE_NONCE: u32 = 4;
+
+// Nonce account: Versions tag u32, State tag u32 (1 = initialized), authority, durable nonce, fee calculator
+const NONCE_LEN: usize = 80;
+const NONCE_AUTHORITY_OFFSET: usize = 8;
+const NONCE_HASH_OFFSET: usize = 40;
+
+// claim-ticket --vault-state <pk> [--boost-ata <pk>] --claimer <pk> --epoch <n> --index <n>
+//              --weight <n> [--proof <hex32,...>] [--nonce <pk>] --out <file>
+pub fn export(rpc: &RpcClient, args: &[String]) -> Res<()> {
+    let vault_state = pubkey(&required(args, "--vault-state")?)?;
+    let claimer = pubkey(&required(args, "--claimer")?)?;
+    let epoch: u64 = required(args, "--epoch")?.parse()?;
+    let index: u32 = required(args, "--index")?.parse()?;
+    let weight: u128 = required(args, "--weight")?.parse()?;
+    let proof = match flag(args, "--proof") {
+        Some(p) if !p.is_empty() => p.split(',').map(node).collect::<Res<Vec<_>>>()?,
+        _ => Vec::new(),
+    };
+    let out = required(args, "--out")?;
+
+    let acc = rpc.get_account(&vault_state)?;
+    let program_id = acc.owner;
+    let st: VaultState = read(&acc.data, "VaultState")?;
+    let usdc_mint = Pubkey::new_from_array(st.usdc_mint);
+    let vault_pda = Pubkey::new_from_array(st.vault_pda);
+    let epoch_le = epoch.to_le_bytes();
+    let (distributor, _) = Pubkey::find_program_address(&[b"boost", vault_pda.as_ref(), &epoch_le], &program_id);
+    // SPL Token or Token-2022: the vault requires the program that owns the mint
+    let token_program = rpc.get_account(&usdc_mint)?.owner;
+    let claimer_ata = ata(&claimer, &usdc_mint, &token_program);
+
+    // show what the user is about to sign for
+    let bd: BoostDistributor = read(&rpc.get_account(&distributor)?.data, "BoostDistributor")?;
+    let boost_ata = if bd.escrow != 0 {
+        Pubkey::find_program_address(&[b"escrow", distributor.as_ref()], &program_id).0
+    } else {
+        pubkey(&required(args, "--boost-ata")?)?
+    };
+    let bitmap = claims_page(&program_id, &vault_pda, epoch, bd.claim_page(index).0);
+    let payout = math::claim_amount(bd.boost_total, weight, bd.total_weight).ok_or("payout overflows")?;
+
+    let mut data = vec![OP_CLAIM];
+    data.extend_from_slice(&epoch_le);
+    data.extend_from_slice(&index.to_le_bytes());
+    data.extend_from_slice(&weight.to_le_bytes());
+    data.push(proof.len() as u8);
+    for p in &proof { data.extend_from_slice(p); }
+    let claim = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(vault_state, false),
+            AccountMeta::new_readonly(vault_pda, false),
+            AccountMeta::new_readonly(claimer, true),
+            AccountMeta::new(distributor, false),
+            AccountMeta::new(bitmap, false),
+            AccountMeta::new(boost_ata, false),
+            AccountMeta::new(claimer_ata, false),
+            AccountMeta::new_readonly(token_program, false),
+            AccountMeta::new_readonly(usdc_mint, false),
+        ],
+        data,
+    };
+
+    let mut ixs = Vec::new();
+    let blockhash = match flag(args, "--nonce") {
+        Some(n) => {
+            let nonce = pubkey(&n)?;
+            let d = rpc.get_account(&nonce)?.data;
+            if d.len() < NONCE_LEN { return Err(format!("{nonce} is not a nonce account").into()) }
+            if d[NONCE_AUTHORITY_OFFSET..NONCE_AUTHORITY_OFFSET + 32] != *claimer.as_ref() {
+                return Err("nonce authority must be the claimer".into())
+            }
+            ixs.push(advance_nonce(&nonce, &claimer));
+            Hash::new_from_array(d[NONCE_HASH_OFFSET..NONCE_HASH_OFFSET + 32].try_into().unwrap())
+        }
+        None => rpc.get_latest_blockhash()?,
+    };
+    ixs.push(create_ata(&claimer, &claimer, &usdc_mint, &token_program));
+    ixs.push(claim);
+
+    let tx = Transaction::new_unsigned(Message::new_with_blockhash(&ixs, Some(&claimer), &blockhash));
+    fs::write(&out, B64.encode(bincode::serialize(&tx)?))?;
+
+    println!("wrote unsigned claim ticket to {out}");
+    println!("  claimer   {claimer} (fee payer, only signer)");
+    println!("  epoch     {epoch}, index {index}, weight {weight}");
+    println!("  payout    {payout} USDC base units to {claimer_ata}");
+    println!("  message   {}", B64.encode(tx.message_data()));
+    if flag(args, "--nonce").is_none() {
+        println!("  note      uses a recent blockhash; sign and broadcast within ~60s or pass --nonce");
+    }
+    Ok(())
+}
+
+// broadcast <file>: base64 transaction with all signatures filled in
+pub fn broadcast(rpc: &RpcClient, path: &str) -> Res<()> {
+    let raw = B64.decode(fs::read_to_string(path)?.trim())?;
+    let tx: Transaction = bincode::deserialize(&raw)?;
+    tx.verify().map_err(|e| format!("ticket is not fully signed: {e}"))?;
+    let sig = rpc.send_and_confirm_transaction(&tx)?;
+    println!("{sig}");
+    Ok(())
+}
+
+fn read<T: bytemuck::Pod>(d: &[u8], what: &str) -> Res<T> {
+    if d.len() < core::mem::size_of::<T>() { return Err(format!("account too small for {what}").into()) }
+    Ok(bytemuck::pod_read_unaligned(&d[..core::mem::size_of::<T>()]))
+}
+
+fn node(s: &str) -> Res<[u8; 32]> {
+    let s = s.trim().trim_start_matches("0x");
+    if s.len() != 64 { return Err(format!("proof node {s} is not 32 bytes of hex").into()) }
+    let mut out = [0u8; 32];
+    for (i, b) in out.iter_mut().enumerate() {
+        *b = u8::from_str_radix(&s[2 * i..2 * i + 2], 16)?;
+    }
+    Ok(out)
+}
+
+fn ata(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[owner.as_ref(), token_program.as_ref(), mint.as_ref()], &ATA_PROGRAM_ID).0
+}
+
+fn create_ata(payer: &Pubkey, owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: ATA_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(ata(owner, mint, token_program), false),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(*token_program, false),
+        ],
+        data: vec![1], // CreateIdempotent
+    }
+}
+
+fn advance_nonce(nonce: &Pubkey, authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: SYSTEM_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*nonce, false),
+            AccountMeta::new_readonly(RECENT_BLOCKHASHES_ID, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: SYS_IX_ADVANCE_NONCE.to_le_bytes().to_vec(),
+    }
+}
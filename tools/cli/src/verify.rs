@@ -0,0 +1,112 @@
+// `verify-build`: check that a deployed program runs the code in this repo. The .so from
+// scripts/verifiable-build.sh is hashed the way solana-verify hashes executables (sha256
+// of the ELF with the trailing zero padding stripped) and compared with the bytes in the
+// program's ProgramData account. The verdict is one JSON line (program, both hashes,
+// deploy slot, upgrade authority, verified) that can be stored as-is in a verification
+// or metadata record; a mismatch also exits non-zero.
+
+use std::fs;
+
+use sha2::{Digest, Sha256};
+use solana_pubkey::Pubkey;
+use solana_rpc_client::rpc_client::RpcClient;
+
+use crate::{flag, pubkey, required, Res};
+
+const BPF_LOADER_UPGRADEABLE_ID: Pubkey = Pubkey::from_str_const("BPFLoaderUpgradeab1e11111111111111111111111");
+const DEFAULT_SO: &str = "programs/interest_vault/target/verifiable/interest_vault.so";
+// UpgradeableLoaderState tags
+const PROGRAM_TAG: u32 = 2;
+const PROGRAM_DATA_TAG: u32 = 3;
+// [tag:u32, slot:u64, authority: Option<Pubkey> (u8 tag + 32)], then the ELF
+const PROGRAM_DATA_HEADER: usize = 45;
+
+#[derive(Debug, PartialEq)]
+pub struct ProgramData<'a> {
+    pub slot: u64,
+    pub authority: Option<Pubkey>,
+    pub elf: &'a [u8],
+}
+
+// verify-build --program <pk> [--so <path>]
+pub fn run(rpc: &RpcClient, args: &[String]) -> Res<()> {
+    let program = pubkey(&required(args, "--program")?)?;
+    let so_path = flag(args, "--so").unwrap_or_else(|| DEFAULT_SO.to_string());
+    let build = fs::read(&so_path).map_err(|e| format!("reading {so_path}: {e} (run scripts/verifiable-build.sh first)"))?;
+
+    let acc = rpc.get_account(&program)?;
+    if acc.owner != BPF_LOADER_UPGRADEABLE_ID { return Err(format!("{program} is not owned by the upgradeable loader").into()) }
+    let program_data = program_data_address(&acc.data)?;
+    let data = rpc.get_account(&program_data)?.data;
+    let pd = parse_program_data(&data)?;
+
+    let deployed = executable_hash(pd.elf);
+    let built = executable_hash(&build);
+    let verified = deployed == built;
+    let authority = pd.authority.map_or("null".to_string(), |a| format!("\"{a}\""));
+    println!(
+        "{{\"program\":\"{program}\",\"executable_hash\":\"{}\",\"build_hash\":\"{}\",\"deploy_slot\":{},\"upgrade_authority\":{authority},\"verified\":{verified}}}",
+        hex(&deployed), hex(&built), pd.slot,
+    );
+    if !verified { return Err(format!("{so_path} does not match the code deployed at {program}").into()) }
+    Ok(())
+}
+
+// the Program account holds only the address of its ProgramData account
+fn program_data_address(d: &[u8]) -> Res<Pubkey> {
+    if d.len() < 36 || u32::from_le_bytes(d[0..4].try_into().unwrap()) != PROGRAM_TAG {
+        return Err("not an upgradeable Program account".into())
+    }
+    Ok(Pubkey::new_from_array(d[4..36].try_into().unwrap()))
+}
+
+pub fn parse_program_data(d: &[u8]) -> Res<ProgramData<'_>> {
+    if d.len() < PROGRAM_DATA_HEADER || u32::from_le_bytes(d[0..4].try_into().unwrap()) != PROGRAM_DATA_TAG {
+        return Err("not a ProgramData account".into())
+    }
+    let authority = match d[12] {
+        0 => None,
+        1 => Some(Pubkey::new_from_array(d[13..45].try_into().unwrap())),
+        _ => return Err("bad upgrade authority tag".into()),
+    };
+    Ok(ProgramData { slot: u64::from_le_bytes(d[4..12].try_into().unwrap()), authority, elf: &d[PROGRAM_DATA_HEADER..] })
+}
+
+// ProgramData is allocated larger than the ELF (room for upgrades) and zero-filled, so
+// both sides drop trailing zeros before hashing
+pub fn executable_hash(elf: &[u8]) -> [u8; 32] {
+    let end = elf.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+    Sha256::digest(&elf[..end]).into()
+}
+
+fn hex(b: &[u8]) -> String {
+    b.iter().map(|x| format!("{x:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deployed_padding_does_not_change_the_hash() {
+        let elf = b"\x7fELF\x02\x01\x01\x00code";
+        let authority = Pubkey::new_unique();
+        let mut d = Vec::new();
+        d.extend_from_slice(&PROGRAM_DATA_TAG.to_le_bytes());
+        d.extend_from_slice(&42u64.to_le_bytes());
+        d.push(1);
+        d.extend_from_slice(authority.as_ref());
+        d.extend_from_slice(elf);
+        d.extend_from_slice(&[0; 100]);
+
+        let pd = parse_program_data(&d).unwrap();
+        assert_eq!((pd.slot, pd.authority), (42, Some(authority)));
+        assert_eq!(executable_hash(pd.elf), executable_hash(elf));
+        assert_ne!(executable_hash(pd.elf), executable_hash(b"\x7fELF\x02\x01\x01\x00cod3"));
+
+        // an immutable program has no authority
+        d[12] = 0;
+        assert_eq!(parse_program_data(&d).unwrap().authority, None);
+        assert!(parse_program_data(&d[..PROGRAM_DATA_HEADER - 1]).is_err());
+    }
+}
@@ -0,0 +1,706 @@
+// Decodes interest_vault instructions (raw data plus the account keys in order) into
+// named arguments, named accounts and a one-line description ("Deposit 100 USDC to vault
+// X"), for explorer plugins and wallet transaction previews. Layouts follow the
+// `// data:` comments of the program's handlers; op numbers are duplicated here the same
+// way the SDK and CLI do, so keep `INSTRUCTIONS` in step with the program's OP_* list.
+//
+// Nothing here reads chain state: claim payouts, share prices and the USDC mint's
+// decimals (where the instruction does not carry them) are not known to the parser.
+
+#[cfg(feature = "wasm")]
+mod wasm;
+
+use std::fmt;
+
+pub type Key = [u8; 32];
+
+// Accounts past an instruction's fixed list
+#[derive(Clone, Copy)]
+pub enum Rest {
+    None,
+    // every extra account has this name
+    Each(&'static str),
+    // extra accounts repeat in groups with these names
+    Groups(&'static [&'static str]),
+}
+
+pub struct InstructionSpec {
+    pub op: u8,
+    pub name: &'static str,
+    pub accounts: &'static [&'static str],
+    pub rest: Rest,
+}
+
+const ADMIN: &[&str] = &["vault_state", "admin"];
+const INIT: &[&str] = &[
+    "vault_state", "admin", "operator", "usdc_mint", "share_mint", "vault_pda", "system_program", "token_program", "registry", "registry_page",
+    "vault_usdc_ata", "boost_usdc", "associated_token_program",
+];
+const DEPOSIT: &[&str] = &[
+    "vault_state", "mint_authority", "user", "user_usdc_ata", "vault_usdc_ata", "share_mint", "user_share_ata", "token_program", "usdc_mint",
+    "user_position", "system_program",
+];
+const WITHDRAW: &[&str] = &[
+    "vault_state", "vault_pda", "user", "user_usdc_ata", "vault_usdc_ata", "share_mint", "user_share_ata", "token_program", "usdc_mint",
+];
+const CLAIM: &[&str] = &[
+    "vault_state", "vault_pda", "claimer", "boost_distributor", "claims_bitmap", "boost_usdc_ata", "claimer_usdc_ata", "token_program", "usdc_mint",
+    "claim_escrow", "payer", "system_program", "associated_token_program",
+];
+
+// Every instruction the program dispatches, by op number.
+pub const INSTRUCTIONS: &[InstructionSpec] = &[
+    InstructionSpec { op: 0, name: "InitializeVault", accounts: INIT, rest: Rest::None },
+    InstructionSpec { op: 1, name: "Deposit", accounts: DEPOSIT, rest: Rest::None },
+    InstructionSpec { op: 2, name: "Withdraw", accounts: WITHDRAW, rest: Rest::None },
+    InstructionSpec {
+        op: 3, name: "DonateReward",
+        accounts: &[
+            "vault_state", "vault_pda", "donor", "donor_usdc_ata", "vault_usdc_ata", "boost_usdc_ata", "token_program", "usdc_mint",
+            "boost_distributor",
+        ],
+        // the yield attestation when an oracle is set, then the rollover distributor
+        rest: Rest::Each("extra"),
+    },
+    InstructionSpec { op: 4, name: "PostRoot", accounts: &["vault_state", "operator", "boost_distributor"], rest: Rest::Each("co_signer") },
+    InstructionSpec { op: 5, name: "Claim", accounts: CLAIM, rest: Rest::None },
+    InstructionSpec { op: 6, name: "SetYieldOracle", accounts: ADMIN, rest: Rest::None },
+    InstructionSpec { op: 7, name: "SetDonors", accounts: ADMIN, rest: Rest::None },
+    InstructionSpec { op: 8, name: "SetBurnDust", accounts: ADMIN, rest: Rest::None },
+    InstructionSpec {
+        op: 9, name: "InitializeVaultFromTemplate",
+        accounts: &[
+            "vault_state", "admin", "operator", "usdc_mint", "share_mint", "vault_pda", "system_program", "token_program", "registry",
+            "registry_page", "vault_usdc_ata", "boost_usdc", "associated_token_program", "template",
+        ],
+        rest: Rest::None,
+    },
+    InstructionSpec { op: 10, name: "SetAutoClaim", accounts: &["vault_state", "user", "prefs", "system_program"], rest: Rest::None },
+    InstructionSpec {
+        op: 11, name: "CrankClaim",
+        accounts: &[
+            "vault_state", "vault_pda", "operator", "claimer", "prefs", "boost_distributor", "claims_bitmap", "boost_usdc_ata", "destination",
+            "token_program", "usdc_mint", "mint_authority", "share_mint", "claimer_share_ata",
+        ],
+        rest: Rest::None,
+    },
+    InstructionSpec {
+        op: 12, name: "ClaimMany",
+        accounts: &["vault_state", "vault_pda", "sender", "boost_distributor", "claims_bitmap", "boost_usdc_ata", "token_program", "usdc_mint"],
+        rest: Rest::Each("destination"),
+    },
+    InstructionSpec { op: 13, name: "CommitWeights", accounts: &["vault_state", "operator", "boost_distributor"], rest: Rest::None },
+    InstructionSpec { op: 14, name: "ReconcileRent", accounts: &["vault_state", "admin", "target", "system_program"], rest: Rest::None },
+    InstructionSpec { op: 15, name: "SetRootSigners", accounts: ADMIN, rest: Rest::None },
+    InstructionSpec { op: 16, name: "SetPpsCeiling", accounts: ADMIN, rest: Rest::None },
+    InstructionSpec { op: 17, name: "RecordUpgradeAuthority", accounts: &["vault_state", "program_data"], rest: Rest::None },
+    InstructionSpec {
+        op: 18, name: "Settle",
+        accounts: &["vault_state", "vault_pda", "vault_usdc_ata", "caller_usdc_ata", "token_program", "usdc_mint"],
+        rest: Rest::None,
+    },
+    InstructionSpec { op: 19, name: "ProposeAdmin", accounts: ADMIN, rest: Rest::None },
+    InstructionSpec { op: 20, name: "AcceptAdmin", accounts: &["vault_state", "new_admin"], rest: Rest::None },
+    InstructionSpec { op: 21, name: "SetPauseFlags", accounts: &["vault_state", "authority"], rest: Rest::None },
+    InstructionSpec {
+        op: 22, name: "OpenBoostEscrow",
+        accounts: &["vault_state", "operator", "boost_distributor", "escrow", "usdc_mint", "system_program", "token_program"],
+        rest: Rest::None,
+    },
+    InstructionSpec { op: 23, name: "SetGuardian", accounts: ADMIN, rest: Rest::None },
+    InstructionSpec { op: 24, name: "EnterEmergency", accounts: &["vault_state", "authority"], rest: Rest::None },
+    InstructionSpec { op: 25, name: "QuoteClaim", accounts: &["boost_distributor", "claims_bitmap", "claimer"], rest: Rest::None },
+    InstructionSpec { op: 26, name: "SetDepositCap", accounts: ADMIN, rest: Rest::None },
+    InstructionSpec { op: 27, name: "SetUserDepositCap", accounts: ADMIN, rest: Rest::None },
+    InstructionSpec { op: 28, name: "CheckShareSupply", accounts: &["vault_state", "share_mint"], rest: Rest::None },
+    InstructionSpec { op: 29, name: "RepairShareSupply", accounts: &["vault_state", "admin", "share_mint"], rest: Rest::None },
+    InstructionSpec { op: 30, name: "WithdrawExact", accounts: WITHDRAW, rest: Rest::None },
+    InstructionSpec { op: 31, name: "WithdrawAll", accounts: WITHDRAW, rest: Rest::None },
+    InstructionSpec { op: 32, name: "DepositFor", accounts: DEPOSIT, rest: Rest::None },
+    InstructionSpec {
+        op: 33, name: "ClaimCompound",
+        accounts: &[
+            "vault_state", "vault_pda", "claimer", "boost_distributor", "claims_bitmap", "boost_usdc_ata", "vault_usdc_ata", "token_program",
+            "usdc_mint", "mint_authority", "share_mint", "claimer_share_ata",
+        ],
+        rest: Rest::None,
+    },
+    InstructionSpec {
+        op: 34, name: "OpenClaimPage", accounts: &["vault_state", "payer", "boost_distributor", "page", "system_program"], rest: Rest::None,
+    },
+    InstructionSpec {
+        op: 35, name: "ClaimEpochs",
+        accounts: &["vault_state", "vault_pda", "claimer", "claimer_usdc_ata", "token_program", "usdc_mint"],
+        rest: Rest::Groups(&["boost_distributor", "claims_bitmap", "boost_usdc_ata"]),
+    },
+    InstructionSpec { op: 36, name: "OpenDistributor", accounts: &["vault_state", "payer", "boost_distributor", "system_program"], rest: Rest::None },
+    InstructionSpec {
+        op: 37, name: "Clawback",
+        accounts: &["vault_state", "vault_pda", "operator", "boost_distributor", "boost_usdc_ata", "vault_usdc_ata", "token_program", "usdc_mint"],
+        rest: Rest::None,
+    },
+    InstructionSpec { op: 38, name: "FinalizeEpoch", accounts: &["vault_state", "operator", "boost_distributor"], rest: Rest::None },
+    InstructionSpec {
+        op: 39, name: "OpenClaimEscrow",
+        accounts: &["vault_state", "payer", "claimer", "claim_escrow", "usdc_mint", "system_program", "token_program"],
+        rest: Rest::None,
+    },
+    InstructionSpec {
+        op: 40, name: "RedeemEscrow",
+        accounts: &["vault_state", "claimer", "claim_escrow", "claimer_usdc_ata", "token_program", "usdc_mint"],
+        rest: Rest::None,
+    },
+    InstructionSpec { op: 41, name: "SetSuccessor", accounts: ADMIN, rest: Rest::None },
+    InstructionSpec {
+        op: 42, name: "MigratePosition",
+        accounts: &[
+            "vault_state", "vault_pda", "user", "user_usdc_ata", "vault_usdc_ata", "share_mint", "user_share_ata", "token_program", "usdc_mint",
+            "successor_program", "successor_vault_state", "successor_mint_authority", "successor_vault_usdc_ata", "successor_share_mint",
+            "user_successor_share_ata",
+        ],
+        rest: Rest::None,
+    },
+    InstructionSpec { op: 43, name: "SetLivenessWindow", accounts: ADMIN, rest: Rest::None },
+    InstructionSpec { op: 44, name: "TripWatchdog", accounts: &["vault_state", "caller"], rest: Rest::None },
+    InstructionSpec { op: 45, name: "RecordArchive", accounts: &["vault_state", "operator", "boost_distributor"], rest: Rest::None },
+    InstructionSpec {
+        op: 46, name: "Report", accounts: &["vault_state", "operator", "boost_distributor", "report", "system_program"], rest: Rest::None,
+    },
+];
+
+const PAUSE_NAMES: [&str; 5] = ["deposit", "withdraw", "donate", "claim", "emergency"];
+const DONATION_CATEGORIES: [&str; 4] = ["strategy yield", "grant", "rebate", "penalty"];
+const RENT_TARGETS: [&str; 4] = ["vault_state", "distributor", "claims bitmap", "user prefs"];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    Empty,
+    UnknownOp(u8),
+    // data ended before a required field
+    Truncated { instruction: &'static str, field: &'static str },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "empty instruction data"),
+            ParseError::UnknownOp(op) => write!(f, "unknown interest_vault op {op}"),
+            ParseError::Truncated { instruction, field } => write!(f, "{instruction}: data ends before {field}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub struct Parsed {
+    pub op: u8,
+    pub name: &'static str,
+    // decoded arguments in data order, already formatted for display
+    pub args: Vec<(&'static str, String)>,
+    // accounts named by position; extras the spec does not name are called "remaining"
+    pub accounts: Vec<(&'static str, Key)>,
+    pub summary: String,
+}
+
+impl Parsed {
+    pub fn account(&self, name: &str) -> Option<&Key> {
+        self.accounts.iter().find(|(n, _)| *n == name).map(|(_, k)| k)
+    }
+
+    pub fn arg(&self, name: &str) -> Option<&str> {
+        self.args.iter().find(|(n, _)| *n == name).map(|(_, v)| v.as_str())
+    }
+
+    // {"op":..,"name":..,"summary":..,"args":[[name,value],..],"accounts":[[name,key],..]}
+    pub fn to_json(&self) -> String {
+        let pairs = |items: Vec<(&str, String)>| {
+            items.iter().map(|(n, v)| format!("[{},{}]", json_str(n), json_str(v))).collect::<Vec<_>>().join(",")
+        };
+        format!(
+            "{{\"op\":{},\"name\":{},\"summary\":{},\"args\":[{}],\"accounts\":[{}]}}",
+            self.op,
+            json_str(self.name),
+            json_str(&self.summary),
+            pairs(self.args.iter().map(|(n, v)| (*n, v.clone())).collect()),
+            pairs(self.accounts.iter().map(|(n, k)| (*n, base58(k))).collect()),
+        )
+    }
+}
+
+pub fn spec(op: u8) -> Option<&'static InstructionSpec> {
+    INSTRUCTIONS.iter().find(|s| s.op == op)
+}
+
+// tag bit asking the program to return the op's compute units; not part of the op number
+const OP_MEASURE: u8 = 0x80;
+
+pub fn parse(data: &[u8], accounts: &[Key]) -> Result<Parsed, ParseError> {
+    let (&tag, body) = data.split_first().ok_or(ParseError::Empty)?;
+    let op = tag & !OP_MEASURE;
+    let spec = spec(op).ok_or(ParseError::UnknownOp(tag))?;
+    let mut named = name_accounts(spec, accounts);
+    let mut r = Reader { data: body, off: 0, instruction: spec.name, args: Vec::new() };
+    let key = |name: &str| named.iter().find(|(n, _)| *n == name).map_or_else(|| "(unknown)".to_string(), |(_, k)| base58(k));
+    let vault = key("vault_state");
+    let mut escrow_at = None;
+
+    let summary = match op {
+        0 | 9 => {
+            if let Some(d) = r.opt_u8("share_decimals") { r.push("share_decimals", d.to_string()) }
+            if let Some(id) = r.opt_u64("vault_id") { r.push("vault_id", id.to_string()) }
+            let template = if op == 9 { format!(" with settings from {}", key("template")) } else { String::new() };
+            format!("Initialize vault {vault} for mint {}{template}", key("usdc_mint"))
+        }
+        1 | 32 => {
+            let amount = r.u64("amount")?;
+            let decimals = r.u8("usdc_decimals")?;
+            r.push("amount", usdc(amount, decimals));
+            r.push("usdc_decimals", decimals.to_string());
+            let recipient = if op == 32 { Some(r.key("recipient")?) } else { None };
+            if let Some(min) = r.opt_u64("min_shares_out") { r.push("min_shares_out", min.to_string()) }
+            match recipient {
+                Some(to) => format!("Deposit {} to vault {vault} for {}", usdc(amount, decimals), base58(&to)),
+                None => format!("Deposit {} to vault {vault}", usdc(amount, decimals)),
+            }
+        }
+        2 => {
+            let shares = r.u64("shares")?;
+            r.push("shares", shares.to_string());
+            let decimals = r.u8("usdc_decimals")?;
+            r.push("usdc_decimals", decimals.to_string());
+            match r.opt_key("recipient") {
+                Some(to) => format!("Withdraw {shares} shares from vault {vault} to {}", base58(&to)),
+                None => format!("Withdraw {shares} shares from vault {vault}"),
+            }
+        }
+        30 => {
+            let amount = r.u64("amount")?;
+            let decimals = r.u8("usdc_decimals")?;
+            r.push("amount", usdc(amount, decimals));
+            r.push("usdc_decimals", decimals.to_string());
+            if let Some(max) = r.opt_u64("max_shares_in") { r.push("max_shares_in", max.to_string()) }
+            format!("Withdraw {} from vault {vault}", usdc(amount, decimals))
+        }
+        31 => {
+            let decimals = r.u8("usdc_decimals")?;
+            r.push("usdc_decimals", decimals.to_string());
+            format!("Withdraw all shares from vault {vault}")
+        }
+        3 => {
+            let amount = r.u64("amount")?;
+            let epoch = r.u64("epoch")?;
+            let boost_bps = r.u16("boost_bps")?;
+            let decimals = r.u8("usdc_decimals")?;
+            r.push("amount", usdc(amount, decimals));
+            r.push("epoch", epoch.to_string());
+            r.push("boost", percent(boost_bps));
+            r.push("usdc_decimals", decimals.to_string());
+            if let Some(c) = r.opt_u8("category") {
+                r.push("category", DONATION_CATEGORIES.get(c as usize).map_or_else(|| format!("unknown ({c})"), |n| n.to_string()));
+            }
+            if let Some(memo) = r.opt_key("memo") { r.push("memo", hex(&memo)) }
+            format!("Donate {} to vault {vault} (epoch {epoch}, {} boost)", usdc(amount, decimals), percent(boost_bps))
+        }
+        4 => {
+            let epoch = r.u64("epoch")?;
+            let total_weight = r.u128("total_weight")?;
+            let root = r.key("root")?;
+            let leaf_count = r.u32("leaf_count")?;
+            r.push("epoch", epoch.to_string());
+            r.push("total_weight", total_weight.to_string());
+            r.push("root", hex(&root));
+            r.push("leaf_count", leaf_count.to_string());
+            format!("Post the epoch {epoch} reward root ({leaf_count} leaves) on vault {vault}")
+        }
+        5 | 11 | 25 | 33 => {
+            let (epoch, index) = r.claim()?;
+            match op {
+                5 => format!("Claim epoch {epoch} leaf {index} from vault {vault}"),
+                11 => format!("Operator claim of epoch {epoch} leaf {index} for {} on vault {vault}", key("claimer")),
+                25 => format!("Quote the epoch {epoch} leaf {index} payout for {}", key("claimer")),
+                _ => format!("Claim epoch {epoch} leaf {index} from vault {vault} as shares"),
+            }
+        }
+        6 => {
+            let oracle = r.key("yield_oracle")?;
+            let tolerance = r.u16("tolerance_bps")?;
+            if oracle == [0; 32] {
+                r.push("yield_oracle", "(disabled)".into());
+                r.push("tolerance", percent(tolerance));
+                format!("Disable the yield oracle on vault {vault}")
+            } else {
+                r.push("yield_oracle", base58(&oracle));
+                r.push("tolerance", percent(tolerance));
+                format!("Set vault {vault}'s yield oracle to {} ({} tolerance)", base58(&oracle), percent(tolerance))
+            }
+        }
+        7 | 15 => {
+            let (flag, list) = if op == 7 { ("restrict", "donor") } else { ("threshold", "signer") };
+            let head = r.u8(flag)?;
+            let count = r.u8("count")?;
+            r.push(flag, head.to_string());
+            for _ in 0..count {
+                let k = r.key(list)?;
+                r.push(list, base58(&k));
+            }
+            if op == 7 {
+                let mode = if head != 0 { "only they and the operator may donate" } else { "anyone may donate" };
+                format!("Set vault {vault}'s donor allow-list to {count} keys ({mode})")
+            } else {
+                format!("Require {head} of {count} root signers on vault {vault}")
+            }
+        }
+        8 => {
+            let on = r.u8("burn_dust")?;
+            r.push("burn_dust", on_off(on).into());
+            format!("Turn dust burning {} on vault {vault}", on_off(on))
+        }
+        10 => {
+            let auto_claim = r.u8("auto_claim")?;
+            let compound = r.u8("compound")?;
+            r.push("auto_claim", on_off(auto_claim).into());
+            r.push("compound", on_off(compound).into());
+            match (auto_claim != 0, compound != 0) {
+                (false, _) => format!("Opt out of operator claims on vault {vault}"),
+                (true, false) => format!("Opt in to operator claims paid in USDC on vault {vault}"),
+                (true, true) => format!("Opt in to operator claims compounded into shares on vault {vault}"),
+            }
+        }
+        12 => {
+            let epoch = r.u64("epoch")?;
+            r.push("epoch", epoch.to_string());
+            let n = r.u8("leaf count")?;
+            for _ in 0..n {
+                let index = r.u32("index")?;
+                let weight = r.u128("weight")?;
+                r.push("leaf", format!("index {index}, weight {weight}"));
+            }
+            let nodes = r.u8("proof_len")?;
+            r.skip(nodes as usize * 32, "proof")?;
+            r.push("proof", format!("{nodes} nodes"));
+            let flags = r.u8("flags_len")?;
+            r.skip(flags as usize, "flags")?;
+            format!("Claim {n} leaves of epoch {epoch} from vault {vault}")
+        }
+        13 => {
+            let epoch = r.u64("epoch")?;
+            let commitment = r.key("commitment")?;
+            r.push("epoch", epoch.to_string());
+            r.push("commitment", hex(&commitment));
+            format!("Commit the epoch {epoch} weight tree on vault {vault}")
+        }
+        14 => {
+            let kind = r.u8("kind")?;
+            let target = RENT_TARGETS.get(kind as usize).copied().unwrap_or("unknown");
+            r.push("kind", target.into());
+            match kind {
+                1 => {
+                    let epoch = r.u64("epoch")?;
+                    r.push("epoch", epoch.to_string());
+                    format!("Reconcile rent of vault {vault}'s epoch {epoch} {target}")
+                }
+                2 => {
+                    let epoch = r.u64("epoch")?;
+                    r.push("epoch", epoch.to_string());
+                    let page = r.opt_u16("page").unwrap_or(0);
+                    r.push("page", page.to_string());
+                    format!("Reconcile rent of vault {vault}'s epoch {epoch} {target} page {page}")
+                }
+                3 => {
+                    let user = r.key("user")?;
+                    r.push("user", base58(&user));
+                    format!("Reconcile rent of {}'s prefs on vault {vault}", base58(&user))
+                }
+                _ => format!("Reconcile rent of vault {vault}'s {target}"),
+            }
+        }
+        16 => {
+            let ceiling = r.u128("pps_ceiling")?;
+            r.push("pps_ceiling", ceiling.to_string());
+            format!("Set vault {vault}'s pps ceiling to {ceiling}")
+        }
+        17 => format!("Record the program's upgrade authority on vault {vault}"),
+        18 => {
+            if named.len() > 1 { format!("Settle vault {vault} and collect the tip") } else { format!("Settle vault {vault}") }
+        }
+        19 | 23 => {
+            let field = if op == 19 { "new_admin" } else { "guardian" };
+            let k = r.key(field)?;
+            r.push(field, base58(&k));
+            match (op, k == [0; 32]) {
+                (19, true) => format!("Cancel the pending admin transfer of vault {vault}"),
+                (19, false) => format!("Propose {} as admin of vault {vault}", base58(&k)),
+                (_, true) => format!("Remove vault {vault}'s guardian"),
+                _ => format!("Set vault {vault}'s guardian to {}", base58(&k)),
+            }
+        }
+        20 => format!("Accept the admin role of vault {vault} as {}", key("new_admin")),
+        21 => {
+            let flags = r.u32("flags")?;
+            let names = pause_names(flags);
+            r.push("flags", names.clone());
+            format!("Set vault {vault}'s pause flags to {names}")
+        }
+        22 | 36 | 37 | 38 => {
+            let epoch = r.u64("epoch")?;
+            r.push("epoch", epoch.to_string());
+            match op {
+                22 => format!("Open the epoch {epoch} boost escrow of vault {vault}"),
+                36 => format!("Open the epoch {epoch} distributor of vault {vault}"),
+                38 => format!("Lock the epoch {epoch} reward root of vault {vault}"),
+                _ => format!("Claw back epoch {epoch}'s unclaimed boost into vault {vault}"),
+            }
+        }
+        24 => format!("Put vault {vault} into withdraw-only emergency mode"),
+        43 => {
+            let slots = r.u64("liveness_slots")?;
+            r.push("liveness_slots", if slots == 0 { "(off)".into() } else { slots.to_string() });
+            if slots == 0 { format!("Turn off vault {vault}'s liveness watchdog") } else { format!("Set vault {vault}'s liveness window to {slots} slots") }
+        }
+        44 => format!("Trip vault {vault}'s liveness watchdog: pause deposits, allow withdrawals"),
+        45 => {
+            let epoch = r.u64("epoch")?;
+            let hash = r.key("archive_hash")?;
+            r.push("epoch", epoch.to_string());
+            r.push("archive_hash", hex(&hash));
+            format!("Record the epoch {epoch} archive hash of vault {vault}")
+        }
+        46 => {
+            let epoch = r.u64("epoch")?;
+            let gross_yield = r.u64("gross_yield")?;
+            let fees = r.u64("fees")?;
+            let pnl = r.u64("strategy_pnl")? as i64;
+            r.push("epoch", epoch.to_string());
+            r.push("gross_yield", gross_yield.to_string());
+            r.push("fees", fees.to_string());
+            r.push("strategy_pnl", pnl.to_string());
+            format!("Report vault {vault}'s performance for epoch {epoch}")
+        }
+        26 | 27 => {
+            let cap = r.u64("cap")?;
+            r.push("cap", if cap == 0 { "(uncapped)".into() } else { cap.to_string() });
+            let what = if op == 26 { "deposit cap" } else { "per-wallet deposit cap" };
+            if cap == 0 { format!("Remove vault {vault}'s {what}") } else { format!("Set vault {vault}'s {what} to {cap}") }
+        }
+        28 => format!("Check vault {vault}'s share supply"),
+        29 => format!("Reset vault {vault}'s total_shares to the share mint supply"),
+        34 => {
+            let epoch = r.u64("epoch")?;
+            let page = r.u16("page")?;
+            r.push("epoch", epoch.to_string());
+            r.push("page", page.to_string());
+            format!("Open claim page {page} of epoch {epoch} on vault {vault}")
+        }
+        35 => {
+            let n = r.u8("entry count")?;
+            for _ in 0..n {
+                r.claim()?;
+            }
+            // one account past the groups is the claim escrow
+            escrow_at = Some(6 + 3 * n as usize);
+            format!("Claim from {n} epochs of vault {vault}")
+        }
+        39 => format!("Open {}'s claim escrow on vault {vault}", key("claimer")),
+        40 => format!("Redeem {}'s claim escrow on vault {vault}", key("claimer")),
+        41 => {
+            let program = r.key("successor_program")?;
+            let successor = r.key("successor")?;
+            r.push("successor_program", base58(&program));
+            r.push("successor", base58(&successor));
+            if successor == [0; 32] {
+                format!("Remove vault {vault}'s successor")
+            } else {
+                format!("Set vault {vault}'s successor to {} (program {})", base58(&successor), base58(&program))
+            }
+        }
+        42 => {
+            let shares = r.u64("shares")?;
+            r.push("shares", if shares == 0 { "(all)".into() } else { shares.to_string() });
+            let decimals = r.u8("usdc_decimals")?;
+            r.push("usdc_decimals", decimals.to_string());
+            let program = r.key("successor_program")?;
+            r.push("successor_program", base58(&program));
+            let successor = r.key("successor")?;
+            r.push("successor", base58(&successor));
+            if let Some(min) = r.opt_u64("min_shares_out") { r.push("min_shares_out", min.to_string()) }
+            let what = if shares == 0 { "all shares".to_string() } else { format!("{shares} shares") };
+            format!("Migrate {what} from vault {vault} to successor {} (program {})", base58(&successor), base58(&program))
+        }
+        _ => unreachable!("every op in INSTRUCTIONS is decoded"),
+    };
+    if let Some((name, _)) = escrow_at.and_then(|i| named.get_mut(i)) { *name = "claim_escrow" }
+    let summary = if tag & OP_MEASURE != 0 { format!("{summary} (measuring compute units)") } else { summary };
+    Ok(Parsed { op, name: spec.name, args: r.args, accounts: named, summary })
+}
+
+fn name_accounts(spec: &InstructionSpec, accounts: &[Key]) -> Vec<(&'static str, Key)> {
+    accounts.iter().enumerate().map(|(i, k)| {
+        let name = match (spec.accounts.get(i), spec.rest) {
+            (Some(n), _) => n,
+            (None, Rest::Each(n)) => n,
+            (None, Rest::Groups(g)) => g[(i - spec.accounts.len()) % g.len()],
+            (None, Rest::None) => "remaining",
+        };
+        (name, *k)
+    }).collect()
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    off: usize,
+    instruction: &'static str,
+    args: Vec<(&'static str, String)>,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, n: usize, field: &'static str) -> Result<&[u8], ParseError> {
+        let b = self.data.get(self.off..self.off + n).ok_or(ParseError::Truncated { instruction: self.instruction, field })?;
+        self.off += n;
+        Ok(b)
+    }
+
+    fn skip(&mut self, n: usize, field: &'static str) -> Result<(), ParseError> {
+        self.take(n, field).map(|_| ())
+    }
+
+    fn u8(&mut self, field: &'static str) -> Result<u8, ParseError> { Ok(self.take(1, field)?[0]) }
+    fn u16(&mut self, field: &'static str) -> Result<u16, ParseError> { Ok(u16::from_le_bytes(self.take(2, field)?.try_into().unwrap())) }
+    fn u32(&mut self, field: &'static str) -> Result<u32, ParseError> { Ok(u32::from_le_bytes(self.take(4, field)?.try_into().unwrap())) }
+    fn u64(&mut self, field: &'static str) -> Result<u64, ParseError> { Ok(u64::from_le_bytes(self.take(8, field)?.try_into().unwrap())) }
+    fn u128(&mut self, field: &'static str) -> Result<u128, ParseError> { Ok(u128::from_le_bytes(self.take(16, field)?.try_into().unwrap())) }
+    fn key(&mut self, field: &'static str) -> Result<Key, ParseError> { Ok(self.take(32, field)?.try_into().unwrap()) }
+
+    // trailing optional fields: absent when the data ends first, as the program reads them
+    fn opt_u8(&mut self, field: &'static str) -> Option<u8> { self.u8(field).ok() }
+    fn opt_u16(&mut self, field: &'static str) -> Option<u16> { self.u16(field).ok() }
+    fn opt_u64(&mut self, field: &'static str) -> Option<u64> { self.u64(field).ok() }
+    fn opt_key(&mut self, field: &'static str) -> Option<Key> { self.key(field).ok() }
+
+    fn push(&mut self, name: &'static str, value: String) {
+        self.args.push((name, value));
+    }
+
+    // [epoch:u64, index:u32, weight:u128, proof_len:u8, proof_nodes]
+    fn claim(&mut self) -> Result<(u64, u32), ParseError> {
+        let epoch = self.u64("epoch")?;
+        let index = self.u32("index")?;
+        let weight = self.u128("weight")?;
+        let nodes = self.u8("proof_len")?;
+        self.skip(nodes as usize * 32, "proof")?;
+        self.push("epoch", epoch.to_string());
+        self.push("index", index.to_string());
+        self.push("weight", weight.to_string());
+        self.push("proof", format!("{nodes} nodes"));
+        Ok((epoch, index))
+    }
+}
+
+// raw amount at the mint's decimals, trailing zeros dropped: 1_500_000 at 6 -> "1.5 USDC"
+pub fn usdc(amount: u64, decimals: u8) -> String {
+    let scale = 10u128.pow(decimals as u32);
+    let (whole, frac) = (amount as u128 / scale, amount as u128 % scale);
+    if frac == 0 { return format!("{whole} USDC") }
+    let frac = format!("{frac:0width$}", width = decimals as usize);
+    format!("{whole}.{} USDC", frac.trim_end_matches('0'))
+}
+
+// bps as a percentage: 2050 -> "20.5%"
+fn percent(bps: u16) -> String {
+    match bps % 100 {
+        0 => format!("{}%", bps / 100),
+        f if f % 10 == 0 => format!("{}.{}%", bps / 100, f / 10),
+        f => format!("{}.{f:02}%", bps / 100),
+    }
+}
+
+fn pause_names(flags: u32) -> String {
+    let names: Vec<&str> = PAUSE_NAMES.iter().enumerate().filter(|(i, _)| flags & (1 << i) != 0).map(|(_, n)| *n).collect();
+    if names.is_empty() { "none".into() } else { names.join(", ") }
+}
+
+fn on_off(v: u8) -> &'static str {
+    if v != 0 { "on" } else { "off" }
+}
+
+pub fn base58(k: &Key) -> String {
+    bs58::encode(k).into_string()
+}
+
+fn hex(b: &[u8]) -> String {
+    b.iter().map(|x| format!("{x:02x}")).collect()
+}
+
+fn json_str(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(n: u8) -> Vec<Key> {
+        (1..=n).map(|i| [i; 32]).collect()
+    }
+
+    #[test]
+    fn describes_deposit() {
+        let mut data = vec![1];
+        data.extend_from_slice(&100_000_000u64.to_le_bytes());
+        data.push(6);
+        let p = parse(&data, &keys(9)).unwrap();
+        assert_eq!(p.name, "Deposit");
+        assert_eq!(p.summary, format!("Deposit 100 USDC to vault {}", base58(&[1; 32])));
+        assert_eq!(p.arg("amount"), Some("100 USDC"));
+        assert_eq!(p.arg("min_shares_out"), None);
+        assert_eq!(p.account("user"), Some(&[3; 32]));
+        data[0] |= OP_MEASURE;
+        assert_eq!(parse(&data, &[]).unwrap().summary, "Deposit 100 USDC to vault (unknown) (measuring compute units)");
+    }
+
+    #[test]
+    fn names_grouped_and_extra_accounts() {
+        let mut data = vec![35, 2];
+        for epoch in [4u64, 5] {
+            data.extend_from_slice(&epoch.to_le_bytes());
+            data.extend_from_slice(&7u32.to_le_bytes());
+            data.extend_from_slice(&1u128.to_le_bytes());
+            data.push(1);
+            data.extend_from_slice(&[9; 32]);
+        }
+        let p = parse(&data, &keys(13)).unwrap();
+        assert_eq!(p.accounts[9].0, "boost_distributor");
+        assert_eq!(p.accounts[11].0, "boost_usdc_ata");
+        assert_eq!(p.accounts[12].0, "claim_escrow");
+        assert_eq!(p.args.iter().filter(|(n, _)| *n == "epoch").count(), 2);
+        assert_eq!(name_accounts(spec(8).unwrap(), &keys(3))[2].0, "remaining");
+    }
+
+    #[test]
+    fn formats_amounts_and_rejects_bad_data() {
+        assert_eq!(usdc(1_500_000, 6), "1.5 USDC");
+        assert_eq!(usdc(1, 6), "0.000001 USDC");
+        assert_eq!(usdc(42, 0), "42 USDC");
+        assert_eq!(percent(2_000), "20%");
+        assert_eq!(percent(2_050), "20.5%");
+        assert_eq!(percent(5), "0.05%");
+        assert_eq!(pause_names(0b10010), "withdraw, emergency");
+        assert_eq!(parse(&[], &[]).err(), Some(ParseError::Empty));
+        assert_eq!(parse(&[200], &[]).err(), Some(ParseError::UnknownOp(200)));
+        assert_eq!(parse(&[3, 0, 0], &[]).err(), Some(ParseError::Truncated { instruction: "DonateReward", field: "amount" }));
+        // every listed op decodes its smallest valid data without panicking
+        for s in INSTRUCTIONS {
+            let _ = parse(&[s.op], &[]);
+        }
+    }
+}
@@ -0,0 +1,28 @@
+// JS bindings. Account keys come in as base58 strings, the way explorers and wallets
+// already hold them.
+
+use wasm_bindgen::prelude::*;
+
+use crate::{parse, Key};
+
+fn keys(accounts: Vec<String>) -> Result<Vec<Key>, JsError> {
+    accounts.iter().map(|a| {
+        let mut k = [0u8; 32];
+        match bs58::decode(a).onto(&mut k) {
+            Ok(32) => Ok(k),
+            _ => Err(JsError::new(&format!("not a 32-byte base58 key: {a}"))),
+        }
+    }).collect()
+}
+
+// One-line description, e.g. "Deposit 100 USDC to vault <key>"
+#[wasm_bindgen(js_name = describeInstruction)]
+pub fn describe_instruction(data: &[u8], accounts: Vec<String>) -> Result<String, JsError> {
+    Ok(parse(data, &keys(accounts)?)?.summary)
+}
+
+// Parsed::to_json: op, name, summary, [name, value] args and [name, key] accounts
+#[wasm_bindgen(js_name = parseInstruction)]
+pub fn parse_instruction(data: &[u8], accounts: Vec<String>) -> Result<String, JsError> {
+    Ok(parse(data, &keys(accounts)?)?.to_json())
+}
@@ -0,0 +1,230 @@
+// One-command local environment for frontend work: starts solana-test-validator with
+// the vault program preloaded, creates a 6-decimal USDC mint, initializes a demo vault
+// and funds a few test wallets with SOL and USDC. Everything it creates is written to
+// .localnet/ (keypairs + env.json); the validator keeps running until Ctrl-C.
+//
+//   ./scripts/build-program.sh && cargo run -p interest_localnet
+
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    thread::sleep,
+    time::Duration,
+};
+
+use solana_commitment_config::CommitmentConfig;
+use solana_instruction::{account_meta::AccountMeta, Instruction};
+use solana_keypair::Keypair;
+use solana_pubkey::Pubkey;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+const RPC_URL: &str = "http://127.0.0.1:8899";
+const DEFAULT_SO: &str = "programs/interest_vault/target/deploy/interest_vault.so";
+const OUT_DIR: &str = ".localnet";
+
+const TOKEN_PROGRAM_ID: Pubkey = Pubkey::from_str_const("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+const ATA_PROGRAM_ID: Pubkey = Pubkey::from_str_const("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::from_str_const("11111111111111111111111111111111");
+
+const USDC_DECIMALS: u8 = 6;
+const TEST_WALLETS: usize = 3;
+const WALLET_SOL: u64 = 10_000_000_000;     // 10 SOL
+const WALLET_USDC: u64 = 1_000 * 1_000_000; // 1,000 USDC
+const MINT_LEN: u64 = 82;
+
+type Res<T> = Result<T, Box<dyn Error>>;
+
+fn main() -> Res<()> {
+    let so = std::env::args().nth(1).unwrap_or_else(|| DEFAULT_SO.to_string());
+    if !Path::new(&so).exists() {
+        return Err(format!("{so} not found; run ./scripts/build-program.sh first").into())
+    }
+    let out = PathBuf::from(OUT_DIR);
+    fs::create_dir_all(&out)?;
+
+    let program_id = Keypair::new().pubkey();
+    let mut validator = start_validator(&out, &program_id, &so)?;
+    let rpc = RpcClient::new_with_commitment(RPC_URL.to_string(), CommitmentConfig::confirmed());
+    if let Err(e) = wait_for_rpc(&rpc) {
+        let _ = validator.kill();
+        return Err(e)
+    }
+
+    let admin = Keypair::new();
+    let operator = Keypair::new();
+    let wallets: Vec<Keypair> = (0..TEST_WALLETS).map(|_| Keypair::new()).collect();
+    for kp in [&admin, &operator].into_iter().chain(&wallets) {
+        airdrop(&rpc, &kp.pubkey(), WALLET_SOL)?;
+    }
+
+    // USDC stand-in, admin is mint authority so it can fund wallets
+    let usdc_mint = Keypair::new();
+    let rent = rpc.get_minimum_balance_for_rent_exemption(MINT_LEN as usize)?;
+    send(&rpc, &admin, &[&usdc_mint], &[
+        create_account(&admin.pubkey(), &usdc_mint.pubkey(), rent, MINT_LEN, &TOKEN_PROGRAM_ID),
+        initialize_mint2(&usdc_mint.pubkey(), &admin.pubkey(), USDC_DECIMALS),
+    ])?;
+
+    // demo vault; InitializeVault creates vault_state at its PDA
+    let (vault_state, _) = Pubkey::find_program_address(
+        &[b"state", usdc_mint.pubkey().as_ref(), admin.pubkey().as_ref(), &0u64.to_le_bytes()], &program_id,
+    );
+    let (vault_pda, _) = Pubkey::find_program_address(
+        &[b"vault", usdc_mint.pubkey().as_ref(), admin.pubkey().as_ref(), &0u64.to_le_bytes()], &program_id,
+    );
+    let (share_mint, _) = Pubkey::find_program_address(&[b"shares", vault_state.as_ref()], &program_id);
+    // vault USDC is the vault_pda's ATA; the boost pool is a separate account with the same
+    // owner at its own PDA. InitializeVault creates both and stores them on the vault.
+    let vault_usdc_ata = ata(&vault_pda, &usdc_mint.pubkey());
+    let (boost_usdc, _) = Pubkey::find_program_address(&[b"boost_usdc", vault_pda.as_ref()], &program_id);
+    let init = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(vault_state, false),
+            AccountMeta::new(admin.pubkey(), true),
+            AccountMeta::new_readonly(operator.pubkey(), false),
+            AccountMeta::new_readonly(usdc_mint.pubkey(), false),
+            AccountMeta::new(share_mint, false),
+            AccountMeta::new_readonly(vault_pda, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            // fresh ledger: the demo vault is the registry's first entry, on page 0
+            AccountMeta::new(Pubkey::find_program_address(&[b"registry"], &program_id).0, false),
+            AccountMeta::new(Pubkey::find_program_address(&[b"registry", &0u32.to_le_bytes()], &program_id).0, false),
+            AccountMeta::new(vault_usdc_ata, false),
+            AccountMeta::new(boost_usdc, false),
+            AccountMeta::new_readonly(ATA_PROGRAM_ID, false),
+        ],
+        data: vec![0u8],
+    };
+    send(&rpc, &admin, &[], &[init])?;
+
+    for w in &wallets {
+        let dest = ata(&w.pubkey(), &usdc_mint.pubkey());
+        send(&rpc, &admin, &[], &[
+            create_ata(&admin.pubkey(), &w.pubkey(), &usdc_mint.pubkey()),
+            mint_to_checked(&usdc_mint.pubkey(), &dest, &admin.pubkey(), WALLET_USDC, USDC_DECIMALS),
+        ])?;
+    }
+
+    write_keypair(&out.join("admin.json"), &admin)?;
+    write_keypair(&out.join("operator.json"), &operator)?;
+    for (i, w) in wallets.iter().enumerate() {
+        write_keypair(&out.join(format!("wallet{i}.json")), w)?;
+    }
+    let wallet_list: Vec<String> = wallets.iter().map(|w| format!("\"{}\"", w.pubkey())).collect();
+    let env = format!(
+        "{{\n  \"rpc\": \"{RPC_URL}\",\n  \"program\": \"{program_id}\",\n  \"admin\": \"{}\",\n  \"operator\": \"{}\",\n  \
+         \"usdcMint\": \"{}\",\n  \"vaultState\": \"{}\",\n  \"vaultPda\": \"{vault_pda}\",\n  \"shareMint\": \"{share_mint}\",\n  \
+         \"vaultUsdcAta\": \"{vault_usdc_ata}\",\n  \"boostUsdcAta\": \"{}\",\n  \"wallets\": [{}]\n}}\n",
+        admin.pubkey(), operator.pubkey(), usdc_mint.pubkey(), vault_state, boost_usdc,
+        wallet_list.join(", "),
+    );
+    fs::write(out.join("env.json"), &env)?;
+
+    println!("localnet ready at {RPC_URL}\n{env}keypairs in {OUT_DIR}/; Ctrl-C to stop");
+    validator.wait()?;
+    Ok(())
+}
+
+fn start_validator(out: &Path, program_id: &Pubkey, so: &str) -> Res<Child> {
+    let ledger = out.join("ledger");
+    Ok(Command::new("solana-test-validator")
+        .arg("--reset")
+        .arg("--quiet")
+        .arg("--ledger").arg(&ledger)
+        .arg("--bpf-program").arg(program_id.to_string()).arg(so)
+        .stdout(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("failed to start solana-test-validator: {e}"))?)
+}
+
+fn wait_for_rpc(rpc: &RpcClient) -> Res<()> {
+    for _ in 0..60 {
+        if rpc.get_health().is_ok() { return Ok(()) }
+        sleep(Duration::from_millis(500));
+    }
+    Err("validator did not become healthy within 30s".into())
+}
+
+fn airdrop(rpc: &RpcClient, to: &Pubkey, lamports: u64) -> Res<()> {
+    let sig = rpc.request_airdrop(to, lamports)?;
+    for _ in 0..60 {
+        if rpc.confirm_transaction(&sig)? { return Ok(()) }
+        sleep(Duration::from_millis(500));
+    }
+    Err(format!("airdrop to {to} not confirmed").into())
+}
+
+fn send(rpc: &RpcClient, payer: &Keypair, extra: &[&Keypair], ixs: &[Instruction]) -> Res<()> {
+    let mut signers = vec![payer];
+    signers.extend_from_slice(extra);
+    let tx = Transaction::new_signed_with_payer(ixs, Some(&payer.pubkey()), &signers, rpc.get_latest_blockhash()?);
+    rpc.send_and_confirm_transaction(&tx)?;
+    Ok(())
+}
+
+fn write_keypair(path: &Path, kp: &Keypair) -> Res<()> {
+    // same JSON byte-array format the solana CLI reads
+    fs::write(path, format!("{:?}", kp.to_bytes()))?;
+    Ok(())
+}
+
+fn ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[owner.as_ref(), TOKEN_PROGRAM_ID.as_ref(), mint.as_ref()], &ATA_PROGRAM_ID).0
+}
+
+// ---------- Instruction builders (system, SPL Token, ATA) ----------
+fn create_account(from: &Pubkey, to: &Pubkey, lamports: u64, space: u64, owner: &Pubkey) -> Instruction {
+    let mut data = Vec::with_capacity(52);
+    data.extend_from_slice(&0u32.to_le_bytes());
+    data.extend_from_slice(&lamports.to_le_bytes());
+    data.extend_from_slice(&space.to_le_bytes());
+    data.extend_from_slice(owner.as_ref());
+    Instruction {
+        program_id: SYSTEM_PROGRAM_ID,
+        accounts: vec![AccountMeta::new(*from, true), AccountMeta::new(*to, true)],
+        data,
+    }
+}
+
+fn initialize_mint2(mint: &Pubkey, authority: &Pubkey, decimals: u8) -> Instruction {
+    let mut data = vec![20u8, decimals];
+    data.extend_from_slice(authority.as_ref());
+    data.push(0); // no freeze authority
+    Instruction { program_id: TOKEN_PROGRAM_ID, accounts: vec![AccountMeta::new(*mint, false)], data }
+}
+
+fn mint_to_checked(mint: &Pubkey, dest: &Pubkey, authority: &Pubkey, amount: u64, decimals: u8) -> Instruction {
+    let mut data = vec![14u8];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.push(decimals);
+    Instruction {
+        program_id: TOKEN_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*mint, false),
+            AccountMeta::new(*dest, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data,
+    }
+}
+
+fn create_ata(payer: &Pubkey, owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: ATA_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(ata(owner, mint), false),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+        data: vec![1], // CreateIdempotent
+    }
+}